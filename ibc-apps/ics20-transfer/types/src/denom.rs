@@ -1,5 +1,7 @@
 //! Defines types to represent "denominations" [as defined in ICS-20](https://github.com/cosmos/ibc/blob/main/spec/app/ics-020-fungible-token-transfer/README.md#data-structures)
-use core::fmt::{Display, Error as FmtError, Formatter};
+use alloc::collections::BTreeSet;
+use core::fmt::{Debug, Display, Error as FmtError, Formatter};
+use core::hash::{Hash, Hasher};
 use core::str::FromStr;
 
 use derive_more::{Display, From};
@@ -70,7 +72,7 @@ impl FromStr for BaseDenom {
 )]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
-#[derive(Clone, Debug, Ord, PartialOrd, Eq, PartialEq)]
+#[derive(Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash)]
 pub struct TracePrefix {
     port_id: PortId,
     channel_id: ChannelId,
@@ -84,6 +86,11 @@ impl TracePrefix {
         }
     }
 
+    /// Returns the prefix of the given channel on the `transfer` port.
+    pub fn transfer(channel_id: ChannelId) -> Self {
+        Self::new(PortId::transfer(), channel_id)
+    }
+
     /// Returns a string slice with [`TracePrefix`] removed.
     ///
     /// If the string starts with a [`TracePrefix`], i.e. `{port-id}/channel-{id}`,
@@ -110,6 +117,16 @@ impl TracePrefix {
 
         Some((Self::new(port_id, channel_id), remaining))
     }
+
+    /// Returns true if both prefixes refer to the same channel, regardless of
+    /// their port IDs.
+    ///
+    /// This is only meaningful when the port is implied by the caller, e.g.
+    /// routing that assumes every hop went through the transfer port. Since
+    /// channel IDs are only unique per port, use `==` whenever the port matters.
+    pub fn same_channel(&self, other: &TracePrefix) -> bool {
+        self.channel_id == other.channel_id
+    }
 }
 
 impl Display for TracePrefix {
@@ -123,6 +140,11 @@ impl Display for TracePrefix {
 /// Internally, the `TracePath` is modelled as a `Vec<TracePrefix>` but with the order reversed, i.e.
 /// "transfer/channel-0/transfer/channel-1/uatom" => `["transfer/channel-1", "transfer/channel-0"]`
 /// This is done for ease of addition/removal of prefixes.
+///
+/// Both [`Display`] and [`FromStr`] account for this reversed storage, so that
+/// every path survives a round-trip through its string representation, i.e.
+/// `TracePath::from_str(&path.to_string())` always yields `path` back. Any
+/// change to the storage order must keep both of them in sync.
 #[cfg_attr(
     feature = "parity-scale-codec",
     derive(
@@ -137,7 +159,7 @@ impl Display for TracePrefix {
 )]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
-#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Ord, From)]
+#[derive(Clone, Eq, PartialEq, PartialOrd, Ord, From)]
 pub struct TracePath(Vec<TracePrefix>);
 
 impl TracePath {
@@ -163,6 +185,119 @@ impl TracePath {
         self.0.is_empty()
     }
 
+    /// Returns the number of hops, i.e. prefixes, of the path.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns an iterator over the prefixes of the path, from the outermost to
+    /// the innermost one, i.e. in the same order as its string representation.
+    pub fn iter(&self) -> impl Iterator<Item = &TracePrefix> {
+        self.0.iter().rev()
+    }
+
+    /// Returns the path with its prefixes in the opposite order, i.e. listing
+    /// the hops from the innermost to the outermost one when displayed.
+    pub fn reverse(&self) -> TracePath {
+        TracePath(self.0.iter().rev().cloned().collect())
+    }
+
+    /// Returns the set of prefixes of the path, disregarding their order, e.g.
+    /// to check whether a token ever went through a given channel.
+    ///
+    /// Note that paths compare in order under `==`, so two paths made of the
+    /// same prefixes in different orders have equal sets but are not equal.
+    pub fn as_prefix_set(&self) -> BTreeSet<TracePrefix> {
+        self.0.iter().cloned().collect()
+    }
+
+    /// Returns true if the two outermost hops of the path cancel each other
+    /// out at the given local channel, i.e. the token left this chain through
+    /// the channel and came straight back through it.
+    ///
+    /// A trace only records the receiving end of each hop, so such a
+    /// round-trip shows up as an outermost prefix equal to the local end
+    /// `{port_id}/{channel_id}` of the channel, followed by a prefix equal to
+    /// its counterparty end `{counterparty_port_id}/{counterparty_channel_id}`.
+    ///
+    /// When this holds, the receiving chain should not mint a voucher of its
+    /// own voucher: both prefixes are to be removed and the original tokens
+    /// released from escrow, while the counterparty burns the voucher it sent.
+    pub fn is_round_trip_at(
+        &self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        counterparty_port_id: &PortId,
+        counterparty_channel_id: &ChannelId,
+    ) -> bool {
+        let local = TracePrefix::new(port_id.clone(), channel_id.clone());
+        let counterparty = TracePrefix::new(
+            counterparty_port_id.clone(),
+            counterparty_channel_id.clone(),
+        );
+        let mut hops = self.iter();
+
+        hops.next() == Some(&local) && hops.next() == Some(&counterparty)
+    }
+
+    /// Checks that every prefix of the path goes through the expected port,
+    /// as a trace built by a single module always does. A stray port hints
+    /// at a bug in the construction of the trace.
+    ///
+    /// Fails with [`TokenTransferError::InvalidPort`] on the outermost prefix
+    /// with a different port.
+    pub fn validate_consistent_port(&self, expected: &PortId) -> Result<(), TokenTransferError> {
+        match self.iter().find(|prefix| &prefix.port_id != expected) {
+            Some(prefix) => Err(TokenTransferError::InvalidPort {
+                port_id: prefix.port_id.clone(),
+                exp_port_id: expected.clone(),
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Appends the prefixes of `other` after those of `self`, i.e. the string
+    /// representation of the merged path is the one of `self` followed by the
+    /// one of `other`.
+    ///
+    /// Fails with [`TokenTransferError::DuplicateTracePrefix`], leaving `self`
+    /// untouched, if the innermost prefix of `self` is the same as the
+    /// outermost prefix of `other`, as a token never goes through the same hop
+    /// twice in a row.
+    pub fn append(&mut self, other: &TracePath) -> Result<(), TokenTransferError> {
+        // Prefixes are stored innermost first, hence the junction lies between
+        // the first prefix of `self` and the last one of `other`.
+        if let (Some(inner), Some(outer)) = (self.0.first(), other.0.last()) {
+            if inner == outer {
+                return Err(TokenTransferError::DuplicateTracePrefix {
+                    port_id: inner.port_id.clone(),
+                    channel_id: inner.channel_id.clone(),
+                });
+            }
+        }
+
+        self.0.splice(0..0, other.0.iter().cloned());
+
+        Ok(())
+    }
+
+    /// Retains only the prefixes for which the predicate returns true,
+    /// preserving the order of the remaining ones.
+    pub fn retain(&mut self, f: impl Fn(&TracePrefix) -> bool) {
+        self.0.retain(f)
+    }
+
+    /// Returns the canonical form of the path, e.g. to hash or store it.
+    ///
+    /// Parsing, [`add_prefix`](Self::add_prefix) and the conversion from
+    /// `(port_id, channel_id)` pairs all store the prefixes innermost first,
+    /// so logically equal paths are already equal under `==` and the
+    /// canonical form is the path itself. Callers relying on this method stay
+    /// independent of that internal layout.
+    pub fn canonical(&self) -> TracePath {
+        self.clone()
+    }
+
     /// Return empty trace path
     pub fn empty() -> Self {
         Self(vec![])
@@ -207,19 +342,105 @@ impl TracePath {
     }
 }
 
+/// Iterates over the prefixes of the path like [`TracePath::iter`].
+impl<'a> IntoIterator for &'a TracePath {
+    type Item = &'a TracePrefix;
+    type IntoIter = core::iter::Rev<core::slice::Iter<'a, TracePrefix>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter().rev()
+    }
+}
+
 impl FromStr for TracePath {
     type Err = TokenTransferError;
 
+    /// Parses a trace path, reporting the first malformed port or channel ID
+    /// along with its hop position and its byte offset in `s`.
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         if s.is_empty() {
             return Ok(TracePath::empty());
         }
 
         let (trace_path, remaining_parts) = TracePath::trim(s);
-        remaining_parts
-            .is_none()
-            .then_some(trace_path)
-            .ok_or_else(|| TokenTransferError::MalformedTrace(s.to_string()))
+
+        match remaining_parts {
+            None => Ok(trace_path),
+            Some(remaining) => Err(malformed_trace_error(s, remaining, trace_path.0.len())),
+        }
+    }
+}
+
+/// Returns the error for the hop at position `pos` of the trace path `s`,
+/// starting with `remaining`, which failed to parse.
+fn malformed_trace_error(s: &str, remaining: &str, pos: usize) -> TokenTransferError {
+    let port_offset = s.len() - remaining.len();
+    let (port_id_s, channel_id_s) = remaining.split_once('/').unwrap_or((remaining, ""));
+    let channel_id_s = channel_id_s.split('/').next().unwrap_or_default();
+
+    if let Err(validation_error) = PortId::from_str(port_id_s) {
+        return TokenTransferError::InvalidTracePortId {
+            pos: pos as u64,
+            offset: port_offset,
+            validation_error,
+        };
+    }
+
+    if let Err(validation_error) = ChannelId::from_str(channel_id_s) {
+        return TokenTransferError::InvalidTraceChannelId {
+            pos: pos as u64,
+            offset: (port_offset + port_id_s.len() + 1).min(s.len()),
+            validation_error,
+        };
+    }
+
+    TokenTransferError::MalformedTrace(s.to_string())
+}
+
+/// Builds a path from `(port_id, channel_id)` pairs ordered as in its string
+/// representation, i.e. from the outermost to the innermost hop. Note that
+/// this differs from the reversed order of `From<Vec<TracePrefix>>`.
+impl From<Vec<(PortId, ChannelId)>> for TracePath {
+    fn from(hops: Vec<(PortId, ChannelId)>) -> Self {
+        Self(
+            hops.into_iter()
+                .rev()
+                .map(|(port_id, channel_id)| TracePrefix::new(port_id, channel_id))
+                .collect(),
+        )
+    }
+}
+
+/// Returns the `(port_id, channel_id)` pairs of the path ordered as in its
+/// string representation, i.e. from the outermost to the innermost hop.
+impl From<&TracePath> for Vec<(PortId, ChannelId)> {
+    fn from(trace_path: &TracePath) -> Self {
+        trace_path
+            .iter()
+            .map(|prefix| (prefix.port_id.clone(), prefix.channel_id.clone()))
+            .collect()
+    }
+}
+
+/// Hashes the prefixes in the order of the string representation, so the hash
+/// does not depend on the reversed internal layout.
+impl Hash for TracePath {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_usize(self.0.len());
+        self.iter().for_each(|prefix| prefix.hash(state));
+    }
+}
+
+/// Lists the hops in the order of the string representation, outermost first,
+/// instead of the reversed internal layout.
+impl Debug for TracePath {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        f.debug_struct("TracePath")
+            .field(
+                "hops",
+                &self.iter().map(ToString::to_string).collect::<Vec<_>>(),
+            )
+            .finish()
     }
 }
 
@@ -407,6 +628,17 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn test_trace_path_debug_follows_display_order() {
+        let trace_path =
+            TracePath::from_str("transfer/channel-1/transfer/channel-0").expect("valid trace path");
+
+        assert_eq!(
+            format!("{trace_path:?}"),
+            r#"TracePath { hops: ["transfer/channel-1", "transfer/channel-0"] }"#
+        );
+    }
+
     #[rstest]
     #[case("transfer")]
     #[case("transfer/channel-1/ica")]
@@ -616,6 +848,350 @@ mod tests {
         assert_eq!(parsed_remaining, remaining);
     }
 
+    #[test]
+    fn test_trace_prefix_transfer() {
+        assert_eq!(
+            TracePrefix::transfer(ChannelId::new(0)),
+            TracePrefix::new(PortId::transfer(), "channel-0".parse().unwrap())
+        );
+    }
+
+    #[test]
+    fn test_trace_path_canonical() {
+        let parsed = TracePath::from_str("transfer/channel-0/transfer/channel-1").unwrap();
+
+        let mut prefixed = TracePath::empty();
+        prefixed.add_prefix(TracePrefix::transfer(ChannelId::new(1)));
+        prefixed.add_prefix(TracePrefix::transfer(ChannelId::new(0)));
+
+        let from_hops = TracePath::from(vec![
+            (PortId::transfer(), ChannelId::new(0)),
+            (PortId::transfer(), ChannelId::new(1)),
+        ]);
+
+        assert_eq!(parsed.canonical(), prefixed.canonical());
+        assert_eq!(parsed.canonical(), from_hops.canonical());
+        assert_eq!(parsed.canonical(), parsed);
+
+        let reversed = TracePath::from_str("transfer/channel-1/transfer/channel-0").unwrap();
+        assert_ne!(parsed.canonical(), reversed.canonical());
+    }
+
+    #[test]
+    fn test_trace_prefix_same_channel() {
+        let prefix = TracePrefix::new(PortId::transfer(), ChannelId::new(1));
+        let same = TracePrefix::new(PortId::transfer(), ChannelId::new(1));
+        let other_port = TracePrefix::new("nft-transfer".parse().unwrap(), ChannelId::new(1));
+        let other_channel = TracePrefix::new(PortId::transfer(), ChannelId::new(2));
+
+        assert!(prefix.same_channel(&other_port));
+        assert_ne!(prefix, other_port);
+
+        assert!(prefix.same_channel(&same));
+        assert_eq!(prefix, same);
+
+        assert!(!prefix.same_channel(&other_channel));
+    }
+
+    #[rstest]
+    #[case("", 0)]
+    #[case("uatom", 0)]
+    #[case("transfer/channel-0/uatom", 1)]
+    #[case("transfer/channel-0/transfer/channel-1/uatom", 2)]
+    #[case("transfer/channel-0/transfer/channel-1", 2)]
+    #[case("transfer/channel-0/transfer/channel-1/gamm/pool/1", 2)]
+    fn test_trace_path_trim_len(#[case] s: &str, #[case] len: usize) {
+        let (trace_path, _) = TracePath::trim(s);
+
+        assert_eq!(trace_path.0.len(), len);
+    }
+
+    #[test]
+    fn test_trace_path_owned_pairs_round_trip() -> Result<(), TokenTransferError> {
+        let trace_path =
+            TracePath::from_str("transfer/channel-0/transfer/channel-1/transfer/channel-2")?;
+
+        let hops = Vec::<(PortId, ChannelId)>::from(&trace_path);
+
+        assert_eq!(
+            hops,
+            vec![
+                (PortId::transfer(), ChannelId::new(0)),
+                (PortId::transfer(), ChannelId::new(1)),
+                (PortId::transfer(), ChannelId::new(2)),
+            ]
+        );
+        assert_eq!(TracePath::from(hops), trace_path);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_trace_path_as_prefix_set() -> Result<(), TokenTransferError> {
+        let trace_path_0 = TracePath::from_str("transfer/channel-0/transfer/channel-1")?;
+        let trace_path_1 = TracePath::from_str("transfer/channel-1/transfer/channel-0")?;
+
+        assert_ne!(trace_path_0, trace_path_1);
+        assert_eq!(trace_path_0.as_prefix_set(), trace_path_1.as_prefix_set());
+        assert!(trace_path_0
+            .as_prefix_set()
+            .contains(&TracePrefix::new(PortId::transfer(), ChannelId::new(1))));
+
+        Ok(())
+    }
+
+    #[rstest]
+    #[case(0)]
+    #[case(1)]
+    #[case(2)]
+    #[case(3)]
+    #[case(42)]
+    #[case(1234)]
+    #[case(0xdead_beef)]
+    #[case(u64::MAX)]
+    fn test_trace_path_string_round_trip(#[case] seed: u64) {
+        const PORT_IDS: [&str; 5] = ["transfer", "nft-transfer", "wasm.contract", "port_1", "a+b"];
+
+        // a linear congruential generator, deterministic for each seed
+        let mut state = seed;
+        let mut next = |bound: u64| {
+            state = state
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            (state >> 33) % bound
+        };
+
+        let mut trace_path = TracePath::empty();
+        for _ in 0..next(8) {
+            let port_id = PORT_IDS[next(PORT_IDS.len() as u64) as usize]
+                .parse()
+                .expect("valid port ID");
+            let channel_id = ChannelId::new(next(1_000));
+
+            trace_path.add_prefix(TracePrefix::new(port_id, channel_id));
+        }
+
+        assert_eq!(
+            TracePath::from_str(&trace_path.to_string()).expect("valid trace path"),
+            trace_path
+        );
+
+        let reversed = trace_path.reverse();
+        assert_eq!(
+            TracePath::from_str(&reversed.to_string()).expect("valid trace path"),
+            reversed
+        );
+        assert_eq!(reversed.reverse(), trace_path);
+
+        let mut hops: Vec<_> = trace_path.iter().collect();
+        hops.reverse();
+        assert_eq!(reversed.iter().collect::<Vec<_>>(), hops);
+    }
+
+    #[test]
+    fn test_trace_path_reverse() {
+        let trace_path =
+            TracePath::from_str("transfer/channel-0/transfer/channel-1").expect("valid trace path");
+
+        assert_eq!(
+            trace_path.reverse().to_string(),
+            "transfer/channel-1/transfer/channel-0"
+        );
+        assert_eq!(TracePath::empty().reverse(), TracePath::empty());
+    }
+
+    #[test]
+    fn test_trace_path_is_round_trip_at() -> Result<(), TokenTransferError> {
+        let port_id = PortId::transfer();
+        let is_round_trip_at = |trace_path: &TracePath, channel: u64, counterparty: u64| {
+            trace_path.is_round_trip_at(
+                &port_id,
+                &ChannelId::new(channel),
+                &port_id,
+                &ChannelId::new(counterparty),
+            )
+        };
+
+        // left through channel-0, whose counterparty end is channel-5, and came back
+        let trace_path =
+            TracePath::from_str("transfer/channel-0/transfer/channel-5/transfer/channel-3")?;
+        assert!(is_round_trip_at(&trace_path, 0, 5));
+        assert!(!is_round_trip_at(&trace_path, 0, 0));
+        assert!(!is_round_trip_at(&trace_path, 5, 0));
+
+        // same identifiers on both ends of the channel
+        let trace_path = TracePath::from_str("transfer/channel-0/transfer/channel-0")?;
+        assert!(is_round_trip_at(&trace_path, 0, 0));
+
+        // multi-hop trace moving further out, nothing cancels
+        let trace_path =
+            TracePath::from_str("transfer/channel-0/transfer/channel-1/transfer/channel-5")?;
+        assert!(!is_round_trip_at(&trace_path, 0, 5));
+        assert!(!is_round_trip_at(&trace_path, 1, 5));
+
+        assert!(!is_round_trip_at(
+            &TracePath::from_str("transfer/channel-0")?,
+            0,
+            5
+        ));
+        assert!(!is_round_trip_at(&TracePath::empty(), 0, 5));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_trace_path_validate_consistent_port() -> Result<(), TokenTransferError> {
+        let trace_path = TracePath::from_str("transfer/channel-0/transfer/channel-1")?;
+        trace_path.validate_consistent_port(&PortId::transfer())?;
+
+        let trace_path = TracePath::from_str("transfer/channel-0/nft-transfer/channel-1")?;
+        let err = trace_path
+            .validate_consistent_port(&PortId::transfer())
+            .expect_err("stray port");
+
+        assert!(matches!(
+            err,
+            TokenTransferError::InvalidPort { port_id, exp_port_id }
+                if port_id.as_str() == "nft-transfer" && exp_port_id == PortId::transfer()
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_trace_path_append() -> Result<(), TokenTransferError> {
+        let mut trace_path = TracePath::from_str("transfer/channel-0/transfer/channel-1")?;
+        trace_path.append(&TracePath::from_str("transfer/channel-2")?)?;
+
+        assert_eq!(
+            trace_path,
+            TracePath::from_str("transfer/channel-0/transfer/channel-1/transfer/channel-2")?
+        );
+
+        trace_path.append(&TracePath::empty())?;
+        assert_eq!(trace_path.len(), 3);
+
+        let mut empty = TracePath::empty();
+        empty.append(&trace_path)?;
+        assert_eq!(empty, trace_path);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_trace_path_append_rejects_duplicate_at_junction() -> Result<(), TokenTransferError> {
+        let mut trace_path = TracePath::from_str("transfer/channel-0/transfer/channel-1")?;
+        let other = TracePath::from_str("transfer/channel-1/transfer/channel-2")?;
+
+        let err = trace_path
+            .append(&other)
+            .expect_err("duplicate at junction");
+
+        assert!(matches!(
+            err,
+            TokenTransferError::DuplicateTracePrefix { port_id, channel_id }
+                if port_id == PortId::transfer() && channel_id == ChannelId::new(1)
+        ));
+        assert_eq!(
+            trace_path,
+            TracePath::from_str("transfer/channel-0/transfer/channel-1")?
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_trace_path_retain() -> Result<(), TokenTransferError> {
+        let mut trace_path =
+            TracePath::from_str("transfer/channel-0/transfer/channel-1/transfer/channel-2")?;
+
+        let deprecated_channel = ChannelId::new(1);
+        trace_path.retain(|prefix| prefix.channel_id != deprecated_channel);
+
+        assert_eq!(
+            trace_path,
+            TracePath::from_str("transfer/channel-0/transfer/channel-2")?
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_trace_path_hash_is_stable() {
+        use std::collections::hash_map::DefaultHasher;
+        use std::collections::HashMap;
+
+        fn hash_of(trace_path: &TracePath) -> u64 {
+            let mut hasher = DefaultHasher::new();
+            trace_path.hash(&mut hasher);
+            hasher.finish()
+        }
+
+        let parsed = TracePath::from_str("transfer/channel-0/transfer/channel-1").expect("success");
+
+        let mut built = TracePath::empty();
+        built.add_prefix(TracePrefix::new(PortId::transfer(), ChannelId::new(1)));
+        built.add_prefix(TracePrefix::new(PortId::transfer(), ChannelId::new(0)));
+
+        let (trimmed, remaining) = TracePath::trim("transfer/channel-0/transfer/channel-1/uatom");
+        assert_eq!(remaining, Some("uatom"));
+
+        assert_eq!(parsed, built);
+        assert_eq!(parsed, trimmed);
+        assert_eq!(hash_of(&parsed), hash_of(&built));
+        assert_eq!(hash_of(&parsed), hash_of(&trimmed));
+
+        let mut paths = HashMap::new();
+        paths.insert(parsed, "parsed");
+
+        assert_eq!(paths.get(&built), Some(&"parsed"));
+        assert_eq!(paths.get(&trimmed), Some(&"parsed"));
+
+        let reversed =
+            TracePath::from_str("transfer/channel-1/transfer/channel-0").expect("success");
+        assert_ne!(hash_of(&reversed), hash_of(&built));
+        assert_eq!(paths.get(&reversed), None);
+    }
+
+    #[rstest]
+    #[case::bad_channel("transfer/channel-0/transfer/chan-1", 1, 28)]
+    #[case::missing_channel("transfer//uatom", 0, 9)]
+    #[case::trailing_port("transfer/channel-0/transfer", 1, 27)]
+    fn test_trace_path_malformed_channel_offset(
+        #[case] trace_path: &str,
+        #[case] expected_pos: u64,
+        #[case] expected_offset: usize,
+    ) {
+        let err = TracePath::from_str(trace_path).expect_err("malformed trace path");
+
+        assert!(
+            matches!(
+                err,
+                TokenTransferError::InvalidTraceChannelId { pos, offset, .. }
+                    if pos == expected_pos && offset == expected_offset
+            ),
+            "unexpected error: {err}"
+        );
+    }
+
+    #[test]
+    fn test_trace_path_malformed_port_offset() {
+        let err = TracePath::from_str("transfer/channel-0/tr@nsfer/channel-1")
+            .expect_err("malformed trace path");
+
+        assert!(
+            matches!(
+                err,
+                TokenTransferError::InvalidTracePortId {
+                    pos: 1,
+                    offset: 19,
+                    ..
+                }
+            ),
+            "unexpected error: {err}"
+        );
+    }
+
     #[test]
     fn test_trace_path() -> Result<(), TokenTransferError> {
         assert!(TracePath::from_str("").is_ok(), "empty trace path");
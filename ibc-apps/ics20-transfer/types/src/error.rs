@@ -29,20 +29,27 @@ pub enum TokenTransferError {
     },
     /// base denomination is empty
     EmptyBaseDenom,
-    /// invalid prot id n trace at position: `{pos}`, validation error: `{validation_error}`
+    /// invalid port id in trace at position: `{pos}`, byte offset: `{offset}`, validation error: `{validation_error}`
     InvalidTracePortId {
         pos: u64,
+        offset: usize,
         validation_error: IdentifierError,
     },
-    /// invalid channel id in trace at position: `{pos}`, validation error: `{validation_error}`
+    /// invalid channel id in trace at position: `{pos}`, byte offset: `{offset}`, validation error: `{validation_error}`
     InvalidTraceChannelId {
         pos: u64,
+        offset: usize,
         validation_error: IdentifierError,
     },
     /// malformed trace: `{0}`
     MalformedTrace(String),
     /// trace length must be even but got: `{len}`
     InvalidTraceLength { len: u64 },
+    /// trace prefix `{port_id}/{channel_id}` repeated at the junction of two traces
+    DuplicateTracePrefix {
+        port_id: PortId,
+        channel_id: ChannelId,
+    },
     /// invalid amount error: `{0}`
     InvalidAmount(FromDecStrErr),
     /// invalid token
@@ -12,6 +12,8 @@
     rust_2018_idioms
 )]
 
+extern crate alloc;
+
 #[cfg(any(test, feature = "std"))]
 extern crate std;
 
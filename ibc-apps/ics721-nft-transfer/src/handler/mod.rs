@@ -23,11 +23,7 @@ pub fn refund_packet_nft_execute(
         .try_into()
         .map_err(|_| NftTransferError::ParseAccountFailure)?;
 
-    if is_sender_chain_source(
-        packet.port_id_on_a.clone(),
-        packet.chan_id_on_a.clone(),
-        &data.class_id,
-    ) {
+    if is_sender_chain_source(&packet.port_id_on_a, &packet.chan_id_on_a, &data.class_id) {
         data.token_ids.as_ref().iter().try_for_each(|token_id| {
             ctx_a.unescrow_nft_execute(
                 &sender,
@@ -60,11 +56,7 @@ pub fn refund_packet_nft_validate(
         .try_into()
         .map_err(|_| NftTransferError::ParseAccountFailure)?;
 
-    if is_sender_chain_source(
-        packet.port_id_on_a.clone(),
-        packet.chan_id_on_a.clone(),
-        &data.class_id,
-    ) {
+    if is_sender_chain_source(&packet.port_id_on_a, &packet.chan_id_on_a, &data.class_id) {
         data.token_ids.0.iter().try_for_each(|token_id| {
             ctx_a.unescrow_nft_validate(
                 &sender,
@@ -31,107 +31,104 @@ where
         .try_into()
         .map_err(|_| (ModuleExtras::empty(), NftTransferError::ParseAccountFailure))?;
 
-    let extras = if is_receiver_chain_source(
-        packet.port_id_on_a.clone(),
-        packet.chan_id_on_a.clone(),
-        &data.class_id,
-    ) {
-        // sender chain is not the source, unescrow the NFT
-        let prefix = TracePrefix::new(packet.port_id_on_a.clone(), packet.chan_id_on_a.clone());
-        let class_id = {
-            let mut c = data.class_id;
-            c.remove_trace_prefix(&prefix);
-            c
-        };
-
-        // Note: the validation is called before the execution.
-        // Refer to ICS-20 `process_recv_packet_execute()`.
-        for token_id in data.token_ids.as_ref() {
-            ctx_b
-                .unescrow_nft_validate(
-                    &receiver_account,
-                    &packet.port_id_on_b,
-                    &packet.chan_id_on_b,
-                    &class_id,
-                    token_id,
-                )
-                .map_err(|nft_error| (ModuleExtras::empty(), nft_error))?;
-            ctx_b
-                .unescrow_nft_execute(
-                    &receiver_account,
-                    &packet.port_id_on_b,
-                    &packet.chan_id_on_b,
-                    &class_id,
-                    token_id,
-                )
-                .map_err(|nft_error| (ModuleExtras::empty(), nft_error))?;
-        }
+    let extras =
+        if is_receiver_chain_source(&packet.port_id_on_a, &packet.chan_id_on_a, &data.class_id) {
+            // sender chain is not the source, unescrow the NFT
+            let prefix = TracePrefix::new(packet.port_id_on_a.clone(), packet.chan_id_on_a.clone());
+            let class_id = {
+                let mut c = data.class_id;
+                c.remove_trace_prefix(&prefix);
+                c
+            };
 
-        ModuleExtras::empty()
-    } else {
-        // sender chain is the source, mint vouchers
-        let prefix = TracePrefix::new(packet.port_id_on_b.clone(), packet.chan_id_on_b.clone());
-        let class_id = {
-            let mut c = data.class_id;
-            c.add_trace_prefix(prefix);
-            c
-        };
+            // Note: the validation is called before the execution.
+            // Refer to ICS-20 `process_recv_packet_execute()`.
+            for token_id in data.token_ids.as_ref() {
+                ctx_b
+                    .unescrow_nft_validate(
+                        &receiver_account,
+                        &packet.port_id_on_b,
+                        &packet.chan_id_on_b,
+                        &class_id,
+                        token_id,
+                    )
+                    .map_err(|nft_error| (ModuleExtras::empty(), nft_error))?;
+                ctx_b
+                    .unescrow_nft_execute(
+                        &receiver_account,
+                        &packet.port_id_on_b,
+                        &packet.chan_id_on_b,
+                        &class_id,
+                        token_id,
+                    )
+                    .map_err(|nft_error| (ModuleExtras::empty(), nft_error))?;
+            }
 
-        let mut extras = ModuleExtras {
-            events: vec![],
-            log: Vec::new(),
-        };
-        for (i, token_id) in data.token_ids.0.iter().enumerate() {
-            let token_uri = data.token_uris.as_ref().and_then(|uris| uris.get(i));
-            let token_data = data.token_data.as_ref().and_then(|data| data.get(i));
+            ModuleExtras::empty()
+        } else {
+            // sender chain is the source, mint vouchers
+            let prefix = TracePrefix::new(packet.port_id_on_b.clone(), packet.chan_id_on_b.clone());
+            let class_id = {
+                let mut c = data.class_id;
+                c.add_trace_prefix(prefix);
+                c
+            };
 
-            let trace_event = TokenTraceEvent {
-                trace_hash: ctx_b.token_hash_string(&class_id, token_id),
-                class: class_id.clone(),
-                token: token_id.clone(),
+            let mut extras = ModuleExtras {
+                events: vec![],
+                log: Vec::new(),
             };
-            extras.events.push(trace_event.into());
+            for (i, token_id) in data.token_ids.0.iter().enumerate() {
+                let token_uri = data.token_uris.as_ref().and_then(|uris| uris.get(i));
+                let token_data = data.token_data.as_ref().and_then(|data| data.get(i));
 
-            // Note: the validation is called before the execution.
-            // Refer to ICS-20 `process_recv_packet_execute()`.
+                let trace_event = TokenTraceEvent {
+                    trace_hash: ctx_b.token_hash_string(&class_id, token_id),
+                    class: class_id.clone(),
+                    token: token_id.clone(),
+                };
+                extras.events.push(trace_event.into());
+
+                // Note: the validation is called before the execution.
+                // Refer to ICS-20 `process_recv_packet_execute()`.
 
-            ctx_b
-                .create_or_update_class_validate(
-                    &class_id,
-                    data.class_uri.as_ref(),
-                    data.class_data.as_ref(),
-                )
-                .map_err(|nft_error| (ModuleExtras::empty(), nft_error))?;
-            ctx_b
-                .create_or_update_class_execute(
-                    &class_id,
-                    data.class_uri.as_ref(),
-                    data.class_data.as_ref(),
-                )
-                .map_err(|nft_error| (ModuleExtras::empty(), nft_error))?;
+                ctx_b
+                    .create_or_update_class_validate(
+                        &class_id,
+                        data.class_uri.as_ref(),
+                        data.class_data.as_ref(),
+                    )
+                    .map_err(|nft_error| (ModuleExtras::empty(), nft_error))?;
+                ctx_b
+                    .create_or_update_class_execute(
+                        &class_id,
+                        data.class_uri.as_ref(),
+                        data.class_data.as_ref(),
+                    )
+                    .map_err(|nft_error| (ModuleExtras::empty(), nft_error))?;
 
-            ctx_b
-                .mint_nft_validate(
-                    &receiver_account,
-                    &class_id,
-                    token_id,
-                    token_uri,
-                    token_data,
-                )
-                .map_err(|nft_error| (extras.clone(), nft_error))?;
-            ctx_b
-                .mint_nft_execute(
-                    &receiver_account,
-                    &class_id,
-                    token_id,
-                    token_uri,
-                    token_data,
-                )
-                .map_err(|nft_error| (extras.clone(), nft_error))?;
-        }
+                ctx_b
+                    .mint_nft_validate(
+                        &receiver_account,
+                        &class_id,
+                        token_id,
+                        token_uri,
+                        token_data,
+                    )
+                    .map_err(|nft_error| (extras.clone(), nft_error))?;
+                ctx_b
+                    .mint_nft_execute(
+                        &receiver_account,
+                        &class_id,
+                        token_id,
+                        token_uri,
+                        token_data,
+                    )
+                    .map_err(|nft_error| (extras.clone(), nft_error))?;
+            }
 
-        extras
-    };
+            extras
+        };
 
     Ok(extras)
 }
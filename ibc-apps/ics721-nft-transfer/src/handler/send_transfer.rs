@@ -74,7 +74,7 @@ where
         data.clear();
     }
     for token_id in token_ids.as_ref() {
-        if is_sender_chain_source(msg.port_id_on_a.clone(), msg.chan_id_on_a.clone(), class_id) {
+        if is_sender_chain_source(&msg.port_id_on_a, &msg.chan_id_on_a, class_id) {
             transfer_ctx.escrow_nft_validate(
                 &sender,
                 &msg.port_id_on_a,
@@ -177,7 +177,7 @@ where
         data.clear();
     }
     for token_id in token_ids.as_ref() {
-        if is_sender_chain_source(msg.port_id_on_a.clone(), msg.chan_id_on_a.clone(), class_id) {
+        if is_sender_chain_source(&msg.port_id_on_a, &msg.chan_id_on_a, class_id) {
             transfer_ctx.escrow_nft_execute(
                 &sender,
                 &msg.port_id_on_a,
@@ -1,8 +1,9 @@
 //! Defines Non-Fungible Token Transfer (ICS-721) class types.
-use core::fmt::{self, Display, Error as FmtError, Formatter};
+use core::fmt::{self, Debug, Display, Error as FmtError, Formatter};
 use core::str::FromStr;
 
 use http::Uri;
+use ibc_app_transfer_types::{BaseDenom, PrefixedDenom};
 pub use ibc_app_transfer_types::{TracePath, TracePrefix};
 use ibc_core::host::types::identifiers::{ChannelId, PortId};
 use ibc_core::primitives::prelude::*;
@@ -55,7 +56,89 @@ impl FromStr for ClassId {
     }
 }
 
+impl ClassId {
+    /// Parses a class ID like [`FromStr`], additionally requiring it to
+    /// satisfy the given predicate, e.g. to only accept contract addresses.
+    pub fn from_str_validated(
+        class_id: &str,
+        validate: impl Fn(&str) -> bool,
+    ) -> Result<Self, NftTransferError> {
+        let class_id = Self::from_str(class_id)?;
+
+        if !validate(&class_id.0) {
+            return Err(NftTransferError::InvalidBaseClassId {
+                class_id: class_id.0,
+            });
+        }
+
+        Ok(class_id)
+    }
+}
+
+#[cfg(feature = "homoglyph-check")]
+impl ClassId {
+    /// Parses a class ID like [`FromStr`], additionally rejecting one which
+    /// mixes letters of the Latin, Greek and Cyrillic scripts, e.g. a Cyrillic
+    /// `а` slipped into an otherwise Latin name to spoof a known collection.
+    ///
+    /// Only these scripts are checked, as they share most of the confusable
+    /// glyphs. Digits, punctuation and letters of other scripts are allowed
+    /// alongside any of them.
+    pub fn from_str_no_homoglyphs(class_id: &str) -> Result<Self, NftTransferError> {
+        let class_id = Self::from_str(class_id)?;
+
+        let mut scripts = class_id.0.chars().filter_map(confusable_script);
+        if let Some(first) = scripts.next() {
+            if scripts.any(|script| script != first) {
+                return Err(NftTransferError::MixedScriptBaseClassId {
+                    base_class_id: class_id.0,
+                });
+            }
+        }
+
+        Ok(class_id)
+    }
+}
+
+/// The scripts whose letters are commonly confused with one another.
+#[cfg(feature = "homoglyph-check")]
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ConfusableScript {
+    Latin,
+    Greek,
+    Cyrillic,
+}
+
+/// Returns the confusable script the given character is a letter of, if any.
+#[cfg(feature = "homoglyph-check")]
+fn confusable_script(c: char) -> Option<ConfusableScript> {
+    if !c.is_alphabetic() {
+        return None;
+    }
+
+    match u32::from(c) {
+        0x0041..=0x024F | 0x1E00..=0x1EFF | 0xFF21..=0xFF5A => Some(ConfusableScript::Latin),
+        0x0370..=0x03FF | 0x1F00..=0x1FFF => Some(ConfusableScript::Greek),
+        0x0400..=0x052F | 0x1C80..=0x1C8F | 0x2DE0..=0x2DFF | 0xA640..=0xA69F => {
+            Some(ConfusableScript::Cyrillic)
+        }
+        _ => None,
+    }
+}
+
+/// The maximum number of hops in the trace path of a [`PrefixedClassId`]
+/// parsed with its [`FromStr`] implementation.
+pub const DEFAULT_MAX_TRACE_HOPS: usize = 32;
+
 /// Prefixed class to trace sources like ICS-20 PrefixedDenom
+///
+/// With the `serde` feature, the class ID is represented as an object with
+/// `trace_path` and `base_class_id` string fields, e.g.
+/// `{"trace_path":"transfer/channel-0","base_class_id":"myclass"}`. Fields
+/// annotated with `#[serde(with = "serializers")]` use the combined string
+/// form instead, e.g. `"transfer/channel-0/myclass"`, as [`PacketData`] does.
+///
+/// [`PacketData`]: crate::packet::PacketData
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[cfg_attr(
@@ -70,7 +153,7 @@ impl FromStr for ClassId {
     feature = "borsh",
     derive(borsh::BorshSerialize, borsh::BorshDeserialize)
 )]
-#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[derive(Clone, Eq, PartialEq, PartialOrd, Ord)]
 pub struct PrefixedClassId {
     /// A series of `{port-id}/{channel-id}`s for tracing the source of the class.
     #[cfg_attr(feature = "serde", serde(with = "serializers"))]
@@ -81,6 +164,12 @@ pub struct PrefixedClassId {
 }
 
 impl PrefixedClassId {
+    /// Returns a builder of a class ID with the given base class, see
+    /// [`PrefixedClassIdBuilder`].
+    pub fn builder(base_class_id: ClassId) -> PrefixedClassIdBuilder {
+        PrefixedClassIdBuilder::new(base_class_id)
+    }
+
     /// Removes the specified prefix from the trace path if there is a match, otherwise does nothing.
     pub fn remove_trace_prefix(&mut self, prefix: &TracePrefix) {
         self.trace_path.remove_prefix(prefix)
@@ -90,12 +179,361 @@ impl PrefixedClassId {
     pub fn add_trace_prefix(&mut self, prefix: TracePrefix) {
         self.trace_path.add_prefix(prefix)
     }
+
+    /// Returns the outermost prefix of the trace path, i.e. the hop the class
+    /// most recently arrived through, or `None` if the class is native.
+    ///
+    /// This is the first prefix of the string representation, not the last
+    /// one: the latter is the hop closest to the origin chain.
+    pub fn last_hop(&self) -> Option<&TracePrefix> {
+        self.trace_path.iter().next()
+    }
+
+    /// Returns the class ID as it exists on the origin chain, i.e. stripped
+    /// of every trace prefix.
+    ///
+    /// This is always equal to `base_class_id`.
+    pub fn origin_class(&self) -> ClassId {
+        self.base_class_id.clone()
+    }
+
+    /// Returns a key identifying the class across chains, e.g. for off-chain
+    /// indexers, derived from its [`origin_class`](Self::origin_class) and the
+    /// trace prefix of the hop out of its origin chain, if any, as
+    /// `{port-id}/{channel-id}/{base-class-id}`.
+    ///
+    /// The key is stable as the class gets relayed further: adding or removing
+    /// the outer trace prefixes leaves it unchanged, while classes with the
+    /// same base class ID coming from different channels get different keys.
+    pub fn collection_key(&self) -> String {
+        match self.trace_path.iter().last() {
+            Some(origin_hop) => format!("{origin_hop}/{}", self.origin_class()),
+            None => self.origin_class().to_string(),
+        }
+    }
+
+    /// Returns true if the class originates from the chain at the other end of
+    /// the given port and channel, i.e. it arrived through that single hop and
+    /// sending it back unwinds it to its [`origin_class`](Self::origin_class).
+    pub fn is_fully_native_on(&self, port_id: &PortId, channel_id: &ChannelId) -> bool {
+        let prefix = TracePrefix::new(port_id.clone(), channel_id.clone());
+        let mut hops = self.trace_path.iter();
+
+        hops.next() == Some(&prefix) && hops.next().is_none()
+    }
+
+    /// Returns the class ID the token would carry on the receiving chain after
+    /// being sent from this chain through `{source_port}/{source_channel}`,
+    /// whose counterparty end is `{dest_port}/{dest_channel}`, without
+    /// modifying this one.
+    ///
+    /// If the receiving chain is the source of the class, the token is burnt
+    /// on this chain and unescrowed there, so the `{source_port}/{source_channel}`
+    /// prefix is removed. Otherwise the token is escrowed here and minted as a
+    /// voucher on the receiving chain, so `{dest_port}/{dest_channel}` is
+    /// added, as done by [`Self::apply_recv`].
+    pub fn after_send(
+        &self,
+        dest_port: &PortId,
+        dest_channel: &ChannelId,
+        source_port: &PortId,
+        source_channel: &ChannelId,
+    ) -> Self {
+        let mut class_id = self.clone();
+        class_id.apply_recv(dest_port, dest_channel, source_port, source_channel);
+        class_id
+    }
+
+    /// Transforms the class ID of a received packet into the one it carries on
+    /// the receiving chain, mirroring the ICS-20 denom transformation.
+    ///
+    /// If the receiving chain is the source of the class, as told by
+    /// [`is_receiver_chain_source`], the token returns home, so the
+    /// `{source_port}/{source_channel}` prefix added when it left is removed.
+    /// Otherwise the token moves further out, so the receiving end of the
+    /// channel, i.e. `{dest_port}/{dest_channel}`, is added.
+    pub fn apply_recv(
+        &mut self,
+        dest_port: &PortId,
+        dest_channel: &ChannelId,
+        source_port: &PortId,
+        source_channel: &ChannelId,
+    ) {
+        if is_receiver_chain_source(source_port, source_channel, self) {
+            self.remove_trace_prefix(&TracePrefix::new(
+                source_port.clone(),
+                source_channel.clone(),
+            ));
+        } else {
+            self.add_trace_prefix(TracePrefix::new(dest_port.clone(), dest_channel.clone()));
+        }
+    }
+
+    /// Returns the voucher class minted on the receiving chain for tokens of
+    /// this class, i.e. the class ID prefixed with `{dest_port}/{dest_channel}`.
+    ///
+    /// This is the receive-side transform when the receiving chain is not the
+    /// source of the class, see [`Self::apply_recv`] for the general case.
+    pub fn to_voucher(&self, dest_port: &PortId, dest_channel: &ChannelId) -> Self {
+        let mut class_id = self.clone();
+        class_id.add_trace_prefix(TracePrefix::new(dest_port.clone(), dest_channel.clone()));
+        class_id
+    }
+
+    /// Returns a copy of the class ID with its base class replaced by the given
+    /// one, preserving the trace path.
+    pub fn with_base_class(self, base_class_id: ClassId) -> Self {
+        Self {
+            trace_path: self.trace_path,
+            base_class_id,
+        }
+    }
+
+    /// Derives an ICS-20 style denom from the class ID, encoding the trace path
+    /// the same way as [`PrefixedDenom`] does, e.g. for fungible receipts of
+    /// wrapped NFTs.
+    ///
+    /// This is a one-way derivation for display and bridging purposes only:
+    /// the resulting denom is not meant to be converted back into a class ID.
+    ///
+    /// Fails with [`NftTransferError::EmptyBaseClassId`] if the base class ID
+    /// is blank, as ICS-20 rejects such a base denom.
+    pub fn to_ics20_style_denom(&self) -> Result<String, NftTransferError> {
+        let base_denom = BaseDenom::from_str(self.base_class_id.as_ref())
+            .map_err(|_| NftTransferError::EmptyBaseClassId)?;
+
+        Ok(PrefixedDenom {
+            trace_path: self.trace_path.clone(),
+            base_denom,
+        }
+        .to_string())
+    }
+
+    /// Returns the class ID as a string suitable for UIs, eliding the middle of
+    /// the trace path with `...` when it has more than `max_hops` hops.
+    ///
+    /// The outermost and innermost hops are always kept, e.g.
+    /// `transfer/channel-0/.../transfer/channel-9/myclass`, so paths of at most
+    /// two hops are never truncated. The [`Display`] output is left unchanged.
+    pub fn display_truncated(&self, max_hops: usize) -> String {
+        let hops: Vec<&TracePrefix> = self.trace_path.iter().collect();
+
+        match hops.as_slice() {
+            [outermost, .., innermost] if hops.len() > max_hops.max(2) => {
+                format!("{outermost}/.../{innermost}/{}", self.base_class_id)
+            }
+            _ => self.to_string(),
+        }
+    }
+
+    /// Parses a class ID whose trace path uses the given delimiter instead of
+    /// `/`, e.g. `transfer:channel-0:myclass` for `:`, as some non-standard
+    /// deployments do. Otherwise follows the [`FromStr`] implementation, the
+    /// trace path being capped at [`DEFAULT_MAX_TRACE_HOPS`] hops likewise.
+    ///
+    /// Delimiters that may appear in port or channel IDs are rejected, as the
+    /// trace path could not be told apart from them.
+    pub fn from_str_with_delimiter(s: &str, delimiter: char) -> Result<Self, NftTransferError> {
+        if delimiter == '/' {
+            return Self::from_str(s);
+        }
+
+        // Port IDs accept every character a channel ID does.
+        if PortId::from_str(&format!("port{delimiter}id")).is_ok() {
+            return Err(NftTransferError::InvalidTraceDelimiter { delimiter });
+        }
+
+        Self::parse(s, delimiter, DEFAULT_MAX_TRACE_HOPS)
+    }
+
+    /// Parses a class ID like the [`FromStr`] implementation, with at most
+    /// `max_hops` hops in its trace path.
+    ///
+    /// An overlong trace path is rejected with
+    /// [`NftTransferError::TraceTooLong`], without allocating the hops past
+    /// the limit. A string made of trace prefixes only is parsed as a base
+    /// class ID, hence has no hops.
+    pub fn from_str_with_max_hops(s: &str, max_hops: usize) -> Result<Self, NftTransferError> {
+        Self::parse(s, '/', max_hops)
+    }
+
+    /// Parses a class ID whose trace path uses the given delimiter and has at
+    /// most `max_hops` hops, counting the hops while stripping them.
+    fn parse(s: &str, delimiter: char, max_hops: usize) -> Result<Self, NftTransferError> {
+        let mut trace_prefixes = Vec::new();
+        let mut hops = 0;
+        let mut remaining = Some(s);
+
+        while let Some((trace_prefix, next_remaining)) =
+            remaining.and_then(|r| strip_trace_prefix(r, delimiter))
+        {
+            hops += 1;
+            // Past the limit, the hops are only counted.
+            if hops <= max_hops {
+                trace_prefixes.push(trace_prefix);
+            }
+            remaining = next_remaining;
+        }
+
+        match remaining {
+            Some(_) if hops > max_hops => Err(NftTransferError::TraceTooLong {
+                len: hops as u64,
+                max: max_hops as u64,
+            }),
+            Some(base_class_id) => {
+                // `TracePath` keeps its prefixes in reverse order.
+                trace_prefixes.reverse();
+                Ok(Self {
+                    trace_path: TracePath::from(trace_prefixes),
+                    base_class_id: ClassId::from_str(base_class_id)?,
+                })
+            }
+            None => Ok(Self {
+                trace_path: TracePath::empty(),
+                base_class_id: ClassId::from_str(s)?,
+            }),
+        }
+    }
+
+    /// Parses a class ID like the [`FromStr`] implementation, with a string
+    /// representation of at most `max_total_len` bytes, trace path and base
+    /// class ID included.
+    ///
+    /// A class ID displays as the string it was parsed from, hence the length
+    /// is checked before parsing, and an overlong input is rejected with
+    /// [`NftTransferError::ClassIdTooLong`].
+    pub fn from_str_max_len(s: &str, max_total_len: usize) -> Result<Self, NftTransferError> {
+        if s.len() > max_total_len {
+            return Err(NftTransferError::ClassIdTooLong {
+                len: s.len() as u64,
+                max: max_total_len as u64,
+            });
+        }
+
+        Self::from_str(s)
+    }
+
+    /// Parses a class ID like the [`FromStr`] implementation, but rejects a
+    /// base class ID which starts with a trace prefix, e.g. `transfer/channel-0`.
+    ///
+    /// Such a base class ID is only kept as is because it does not end with a
+    /// segment of its own, and it could not be told apart from a trace path
+    /// once prefixed. Rejecting it guarantees that the trace path and the
+    /// base class ID round-trip unambiguously, at the cost of refusing classes
+    /// which are valid on other chains and which the lenient parsing accepts.
+    pub fn from_str_strict(s: &str) -> Result<Self, NftTransferError> {
+        let class_id = Self::from_str(s)?;
+
+        if TracePrefix::strip(class_id.base_class_id.as_ref()).is_some() {
+            return Err(NftTransferError::AmbiguousBaseClassId {
+                base_class_id: class_id.base_class_id.to_string(),
+            });
+        }
+
+        Ok(class_id)
+    }
+
+    /// Parses a class ID like the [`FromStr`] implementation, additionally
+    /// requiring its base class ID to satisfy the given predicate, see
+    /// [`ClassId::from_str_validated`].
+    ///
+    /// The trace path is parsed as usual: the predicate only sees the base
+    /// class ID, never the trace prefixes.
+    pub fn from_str_validated(
+        s: &str,
+        validate: impl Fn(&str) -> bool,
+    ) -> Result<Self, NftTransferError> {
+        let class_id = Self::from_str(s)?;
+
+        if !validate(class_id.base_class_id.as_ref()) {
+            return Err(NftTransferError::InvalidBaseClassId {
+                class_id: class_id.base_class_id.to_string(),
+            });
+        }
+
+        Ok(class_id)
+    }
+
+    /// Returns the class ID as a string with the trace path separated by the
+    /// given delimiter, the counterpart of [`Self::from_str_with_delimiter`].
+    pub fn display_with_delimiter(&self, delimiter: char) -> String {
+        let delimiter = delimiter.to_string();
+
+        self.trace_path
+            .iter()
+            .map(|prefix| prefix.to_string().replace('/', &delimiter))
+            .chain([self.base_class_id.to_string()])
+            .collect::<Vec<_>>()
+            .join(&delimiter)
+    }
+}
+
+/// Assembles a [`PrefixedClassId`] from its base class and the hops of its
+/// trace path.
+///
+/// Hops are taken in the order they appear in the string form of the class
+/// ID: the first hop is the outermost one, i.e. the last channel the class
+/// was transferred through, and the last hop is the one next to the origin
+/// chain. For instance, adding the hops `transfer/channel-0` then
+/// `transfer/channel-1` to `myclass` builds
+/// `transfer/channel-0/transfer/channel-1/myclass`.
+#[derive(Clone, Debug)]
+pub struct PrefixedClassIdBuilder {
+    base_class_id: ClassId,
+    hops: Vec<TracePrefix>,
+}
+
+impl PrefixedClassIdBuilder {
+    pub fn new(base_class_id: ClassId) -> Self {
+        Self {
+            base_class_id,
+            hops: Vec::new(),
+        }
+    }
+
+    /// Appends a hop, closer to the origin chain than the ones added before.
+    pub fn hop(mut self, prefix: TracePrefix) -> Self {
+        self.hops.push(prefix);
+        self
+    }
+
+    /// Appends the given hops, ordered from the outermost to the innermost.
+    pub fn hops(mut self, prefixes: impl IntoIterator<Item = TracePrefix>) -> Self {
+        self.hops.extend(prefixes);
+        self
+    }
+
+    pub fn build(self) -> PrefixedClassId {
+        let mut hops = self.hops;
+        // `TracePath` keeps its prefixes in reverse order.
+        hops.reverse();
+
+        PrefixedClassId {
+            trace_path: TracePath::from(hops),
+            base_class_id: self.base_class_id,
+        }
+    }
+}
+
+/// Strips a `{port-id}{delimiter}{channel-id}` prefix from the string, the
+/// same way [`TracePrefix::strip`] does for `/`.
+fn strip_trace_prefix(s: &str, delimiter: char) -> Option<(TracePrefix, Option<&str>)> {
+    let (port_id_s, remaining) = s.split_once(delimiter)?;
+    let (channel_id_s, remaining) = remaining
+        .split_once(delimiter)
+        .map(|(a, b)| (a, Some(b)))
+        .unwrap_or_else(|| (remaining, None));
+
+    let port_id = port_id_s.parse().ok()?;
+    let channel_id = channel_id_s.parse().ok()?;
+
+    Some((TracePrefix::new(port_id, channel_id), remaining))
 }
 
 /// Returns true if the class ID originally came from the sender chain and false otherwise.
 pub fn is_sender_chain_source(
-    source_port: PortId,
-    source_channel: ChannelId,
+    source_port: &PortId,
+    source_channel: &ChannelId,
     class_id: &PrefixedClassId,
 ) -> bool {
     !is_receiver_chain_source(source_port, source_channel, class_id)
@@ -103,8 +541,8 @@ pub fn is_sender_chain_source(
 
 /// Returns true if the class ID originally came from the receiving chain and false otherwise.
 pub fn is_receiver_chain_source(
-    source_port: PortId,
-    source_channel: ChannelId,
+    source_port: &PortId,
+    source_channel: &ChannelId,
     class_id: &PrefixedClassId,
 ) -> bool {
     // For example, let
@@ -114,7 +552,7 @@ pub fn is_receiver_chain_source(
     // If B had originally sent the token in a previous transfer, then A would have stored the token as
     // "transfer/c2b/{token_denom}". Now, A is sending to B, so to check if B is the source of the token,
     // we need to check if the token starts with "transfer/c2b".
-    let prefix = TracePrefix::new(source_port, source_channel);
+    let prefix = TracePrefix::new(source_port.clone(), source_channel.clone());
     class_id.trace_path.starts_with(&prefix)
 }
 
@@ -122,18 +560,11 @@ impl FromStr for PrefixedClassId {
     type Err = NftTransferError;
 
     /// The parsing logic is same as [`FromStr`] impl of
-    /// [`PrefixedDenom`](ibc_app_transfer_types::PrefixedDenom) from ICS-20.
+    /// [`PrefixedDenom`](ibc_app_transfer_types::PrefixedDenom) from ICS-20,
+    /// except that the trace path is capped at [`DEFAULT_MAX_TRACE_HOPS`]
+    /// hops, see [`PrefixedClassId::from_str_with_max_hops`].
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match TracePath::trim(s) {
-            (trace_path, Some(remaining_parts)) => Ok(Self {
-                trace_path,
-                base_class_id: ClassId::from_str(remaining_parts)?,
-            }),
-            (_, None) => Ok(Self {
-                trace_path: TracePath::empty(),
-                base_class_id: ClassId::from_str(s)?,
-            }),
-        }
+        Self::from_str_with_max_hops(s, DEFAULT_MAX_TRACE_HOPS)
     }
 }
 
@@ -170,6 +601,24 @@ impl From<ClassId> for PrefixedClassId {
     }
 }
 
+/// Lists the hops of the trace path in the order of the string
+/// representation, outermost first, followed by the base class.
+impl Debug for PrefixedClassId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        f.debug_struct("PrefixedClassId")
+            .field(
+                "hops",
+                &self
+                    .trace_path
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>(),
+            )
+            .field("base_class_id", &self.base_class_id)
+            .finish()
+    }
+}
+
 impl Display for PrefixedClassId {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
         if self.trace_path.is_empty() {
@@ -181,14 +630,24 @@ impl Display for PrefixedClassId {
 }
 
 /// Class URI for an NFT
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[derive(Clone, Debug, PartialEq, Eq)]
-pub struct ClassUri(
-    #[cfg_attr(feature = "serde", serde(with = "serializers"))]
-    #[cfg_attr(feature = "schema", schemars(with = "String"))]
-    Uri,
-);
+pub struct ClassUri(#[cfg_attr(feature = "schema", schemars(with = "String"))] Uri);
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for ClassUri {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializers::serialize(self, serializer)
+    }
+}
+
+/// Goes through [`FromStr`], so that relative URIs are rejected.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for ClassUri {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        serializers::deserialize(deserializer)
+    }
+}
 
 #[cfg(feature = "borsh")]
 impl borsh::BorshSerialize for ClassUri {
@@ -241,6 +700,35 @@ impl scale_info::TypeInfo for ClassUri {
     }
 }
 
+impl ClassUri {
+    /// Returns the parsed URI, which is kept since the class URI was validated.
+    pub fn as_uri(&self) -> &Uri {
+        &self.0
+    }
+}
+
+/// Fails with [`NftTransferError::RelativeUri`] if the URI has no scheme, as
+/// [`FromStr`] does.
+impl TryFrom<Uri> for ClassUri {
+    type Error = NftTransferError;
+
+    fn try_from(uri: Uri) -> Result<Self, Self::Error> {
+        if uri.scheme().is_none() {
+            return Err(NftTransferError::RelativeUri {
+                uri: uri.to_string(),
+            });
+        }
+
+        Ok(Self(uri))
+    }
+}
+
+impl From<ClassUri> for Uri {
+    fn from(class_uri: ClassUri) -> Self {
+        class_uri.0
+    }
+}
+
 impl Display for ClassUri {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.0)
@@ -251,14 +739,28 @@ impl FromStr for ClassUri {
     type Err = NftTransferError;
 
     fn from_str(class_uri: &str) -> Result<Self, Self::Err> {
-        match Uri::from_str(class_uri) {
-            Ok(uri) => Ok(Self(uri)),
-            Err(err) => Err(NftTransferError::InvalidUri {
-                uri: class_uri.to_string(),
-                validation_error: err,
-            }),
-        }
+        parse_absolute_uri(class_uri).map(Self)
+    }
+}
+
+/// Parses an absolute URI, i.e. one with a scheme such as `https://` or
+/// `ipfs://`, as required for class and token URIs.
+///
+/// Relative references like `/images/nft.png`, or a bare host like
+/// `example.com`, are rejected.
+pub(crate) fn parse_absolute_uri(uri: &str) -> Result<Uri, NftTransferError> {
+    let parsed = Uri::from_str(uri).map_err(|err| NftTransferError::InvalidUri {
+        uri: uri.to_string(),
+        validation_error: err,
+    })?;
+
+    if parsed.scheme().is_none() {
+        return Err(NftTransferError::RelativeUri {
+            uri: uri.to_string(),
+        });
     }
+
+    Ok(parsed)
 }
 
 /// Class data for an NFT
@@ -279,6 +781,29 @@ impl FromStr for ClassUri {
 #[derive(Clone, Debug, PartialEq, Eq, derive_more::AsRef)]
 pub struct ClassData(Data);
 
+impl ClassData {
+    /// Returns the canonical JSON encoding of the class data, which is the
+    /// form to hash for commitments. Its [`Display`] output is the data as
+    /// received and may differ in key order or whitespace.
+    #[cfg(feature = "serde")]
+    pub fn to_canonical_json(&self) -> Vec<u8> {
+        self.0.to_canonical_json()
+    }
+
+    /// Returns the class data as a JSON value, failing if it does not follow
+    /// the format specified by ICS-721.
+    #[cfg(feature = "serde")]
+    pub fn to_json_value(&self) -> Result<serde_json::Value, NftTransferError> {
+        self.0.to_json_value()
+    }
+
+    /// Builds the class data from a JSON value in the format specified by ICS-721.
+    #[cfg(feature = "serde")]
+    pub fn from_json_value(value: serde_json::Value) -> Result<Self, NftTransferError> {
+        Data::from_json_value(value).map(Self)
+    }
+}
+
 impl Display for ClassData {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.0)
@@ -301,6 +826,30 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn test_prefixed_class_id_debug_follows_display_order() {
+        let class_id =
+            PrefixedClassId::from_str("nft-transfer/channel-1/transfer/channel-0/myclass")
+                .expect("success");
+
+        let debug = format!("{class_id:?}");
+        let display = class_id.to_string();
+
+        let hops: Vec<String> = class_id
+            .trace_path
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        let position = |s: &str, hop: &str| s.find(hop).expect("hop is listed");
+
+        assert!(position(&display, &hops[0]) < position(&display, &hops[1]));
+        assert!(position(&debug, &hops[0]) < position(&debug, &hops[1]));
+        assert_eq!(
+            debug,
+            r#"PrefixedClassId { hops: ["nft-transfer/channel-1", "transfer/channel-0"], base_class_id: ClassId("myclass") }"#
+        );
+    }
+
     #[rstest]
     #[case("myclass")]
     #[case("transfer/channel-0/myclass")]
@@ -343,6 +892,270 @@ mod tests {
         PrefixedClassId::from_str(class_id).expect_err("failure");
     }
 
+    #[rstest]
+    #[case("myclass")]
+    #[case("transfer/channel-0/myclass")]
+    #[case("transfer/myclass")]
+    fn test_prefixed_class_id_strict(#[case] class_id: &str) {
+        assert_eq!(
+            PrefixedClassId::from_str_strict(class_id).expect("success"),
+            PrefixedClassId::from_str(class_id).expect("success")
+        );
+    }
+
+    #[rstest]
+    #[case("transfer/channel-0")]
+    #[case("transfer/channel-0/transfer/channel-1")]
+    fn test_prefixed_class_id_strict_ambiguous_base(#[case] class_id: &str) {
+        // accepted as a base class ID by the lenient parsing
+        let lenient = PrefixedClassId::from_str(class_id).expect("success");
+        assert!(lenient.trace_path.is_empty());
+
+        assert!(matches!(
+            PrefixedClassId::from_str_strict(class_id),
+            Err(NftTransferError::AmbiguousBaseClassId { base_class_id }) if base_class_id == class_id
+        ));
+    }
+
+    fn is_address(class_id: &str) -> bool {
+        class_id.starts_with("addr1")
+    }
+
+    #[rstest]
+    #[case("addr1qxyz")]
+    #[case("transfer/channel-0/addr1qxyz")]
+    #[case("transfer/channel-0/transfer/channel-1/addr1qxyz")]
+    fn test_class_id_validated(#[case] class_id: &str) {
+        assert_eq!(
+            PrefixedClassId::from_str_validated(class_id, is_address).expect("success"),
+            PrefixedClassId::from_str(class_id).expect("success")
+        );
+    }
+
+    #[rstest]
+    #[case("myclass", "myclass")]
+    #[case("transfer/channel-0/myclass", "myclass")]
+    // the predicate only applies to the base class ID
+    #[case("addr1/channel-0/myclass", "myclass")]
+    fn test_class_id_validated_rejected(#[case] class_id: &str, #[case] base_class_id: &str) {
+        assert!(matches!(
+            PrefixedClassId::from_str_validated(class_id, is_address),
+            Err(NftTransferError::InvalidBaseClassId { class_id }) if class_id == base_class_id
+        ));
+    }
+
+    #[test]
+    fn test_base_class_id_validated() {
+        ClassId::from_str_validated("addr1qxyz", is_address).expect("success");
+
+        assert!(matches!(
+            ClassId::from_str_validated("myclass", is_address),
+            Err(NftTransferError::InvalidBaseClassId { class_id }) if class_id == "myclass"
+        ));
+        assert!(matches!(
+            ClassId::from_str_validated("  ", is_address),
+            Err(NftTransferError::EmptyBaseClassId)
+        ));
+    }
+
+    #[test]
+    fn test_class_uri_to_and_from_uri() {
+        let raw = "https://example.com/classes/myclass?page=1";
+
+        let class_uri = ClassUri::from_str(raw).expect("success");
+        assert_eq!(class_uri.as_uri(), &Uri::from_str(raw).expect("success"));
+
+        let uri = Uri::from(class_uri.clone());
+        assert_eq!(uri.to_string(), raw);
+
+        let class_uri_from_uri = ClassUri::try_from(uri).expect("success");
+        assert_eq!(class_uri_from_uri, class_uri);
+        assert_eq!(class_uri_from_uri.to_string(), raw);
+    }
+
+    #[rstest]
+    #[case("/images/nft.png")]
+    #[case("example.com")]
+    fn test_relative_class_uri(#[case] uri: &str) {
+        assert!(matches!(
+            ClassUri::from_str(uri),
+            Err(NftTransferError::RelativeUri { uri: relative }) if relative == uri
+        ));
+        assert!(matches!(
+            ClassUri::try_from(Uri::from_str(uri).expect("success")),
+            Err(NftTransferError::RelativeUri { .. })
+        ));
+
+        #[cfg(feature = "serde")]
+        assert!(serde_json::from_value::<ClassUri>(serde_json::json!(uri)).is_err());
+    }
+
+    #[rstest]
+    #[case("http://example.com/nft.png")]
+    #[case("https://example.com/nft.png")]
+    #[case("ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi/1.json")]
+    fn test_absolute_class_uri(#[case] uri: &str) {
+        let class_uri = ClassUri::from_str(uri).expect("success");
+        assert_eq!(class_uri.to_string(), uri);
+    }
+
+    #[test]
+    fn test_prefixed_class_id_builder() -> Result<(), NftTransferError> {
+        let class_id = PrefixedClassId::builder(ClassId::from_str("myclass")?)
+            .hop(TracePrefix::new(PortId::transfer(), ChannelId::new(0)))
+            .hops([TracePrefix::new(PortId::transfer(), ChannelId::new(1))])
+            .build();
+
+        assert_eq!(
+            class_id,
+            PrefixedClassId::from_str("transfer/channel-0/transfer/channel-1/myclass")?
+        );
+
+        assert_eq!(
+            PrefixedClassIdBuilder::new(ClassId::from_str("myclass")?).build(),
+            PrefixedClassId::from_str("myclass")?
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_prefixed_class_id_last_hop() -> Result<(), NftTransferError> {
+        let class_id = PrefixedClassId::from_str("transfer/channel-0/transfer/channel-1/myclass")?;
+
+        assert_eq!(
+            class_id.last_hop(),
+            Some(&TracePrefix::new(PortId::transfer(), ChannelId::new(0)))
+        );
+        assert_eq!(PrefixedClassId::from_str("myclass")?.last_hop(), None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_prefixed_class_id_origin_class() -> Result<(), NftTransferError> {
+        let base_class_id = ClassId::from_str("myclass")?;
+
+        let native = PrefixedClassId::from_str("myclass")?;
+        assert_eq!(native.origin_class(), base_class_id);
+        assert!(!native.is_fully_native_on(&PortId::transfer(), &ChannelId::new(0)));
+
+        let single_hop = PrefixedClassId::from_str("transfer/channel-0/myclass")?;
+        assert_eq!(single_hop.origin_class(), base_class_id);
+        assert!(single_hop.is_fully_native_on(&PortId::transfer(), &ChannelId::new(0)));
+        assert!(!single_hop.is_fully_native_on(&PortId::transfer(), &ChannelId::new(1)));
+
+        let multi_hop = PrefixedClassId::from_str("transfer/channel-0/transfer/channel-1/myclass")?;
+        assert_eq!(multi_hop.origin_class(), base_class_id);
+        assert!(!multi_hop.is_fully_native_on(&PortId::transfer(), &ChannelId::new(0)));
+        assert!(!multi_hop.is_fully_native_on(&PortId::transfer(), &ChannelId::new(1)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_prefixed_class_id_collection_key() -> Result<(), NftTransferError> {
+        let native = PrefixedClassId::from_str("myclass")?;
+        let one_hop = PrefixedClassId::from_str("transfer/channel-0/myclass")?;
+        let two_hops = PrefixedClassId::from_str("transfer/channel-1/transfer/channel-0/myclass")?;
+
+        assert_eq!(native.collection_key(), "myclass");
+        assert_eq!(one_hop.collection_key(), "transfer/channel-0/myclass");
+        assert_eq!(two_hops.collection_key(), one_hop.collection_key());
+
+        assert_ne!(
+            PrefixedClassId::from_str("transfer/channel-0/otherclass")?.collection_key(),
+            one_hop.collection_key()
+        );
+        assert_ne!(
+            PrefixedClassId::from_str("transfer/channel-5/myclass")?.collection_key(),
+            one_hop.collection_key()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_prefixed_class_id_after_send() -> Result<(), NftTransferError> {
+        let port_id = PortId::transfer();
+        // the channel between chains A and B, as seen from each of them
+        let channel_on_a = ChannelId::new(0);
+        let channel_on_b = ChannelId::new(1);
+
+        // forward send from A: the voucher on B gets prefixed with B's end
+        let native_on_a = PrefixedClassId::from_str("myclass")?;
+        let voucher_on_b = native_on_a.after_send(&port_id, &channel_on_b, &port_id, &channel_on_a);
+        assert_eq!(
+            voucher_on_b,
+            PrefixedClassId::from_str("transfer/channel-1/myclass")?
+        );
+        assert_eq!(native_on_a, PrefixedClassId::from_str("myclass")?);
+
+        let foreign_on_a = PrefixedClassId::from_str("transfer/channel-5/myclass")?;
+        assert_eq!(
+            foreign_on_a.after_send(&port_id, &channel_on_b, &port_id, &channel_on_a),
+            PrefixedClassId::from_str("transfer/channel-1/transfer/channel-5/myclass")?
+        );
+
+        // return send from B: the prefix of the hop back to A is removed
+        assert_eq!(
+            voucher_on_b.after_send(&port_id, &channel_on_a, &port_id, &channel_on_b),
+            native_on_a
+        );
+        assert_eq!(
+            PrefixedClassId::from_str("transfer/channel-1/transfer/channel-5/myclass")?.after_send(
+                &port_id,
+                &channel_on_a,
+                &port_id,
+                &channel_on_b
+            ),
+            foreign_on_a
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_prefixed_class_id_apply_recv() -> Result<(), NftTransferError> {
+        let port_id = PortId::transfer();
+        let source_channel = ChannelId::new(0);
+        let dest_channel = ChannelId::new(1);
+
+        // return to source: the prefix of the sender end is removed
+        let mut class_id =
+            PrefixedClassId::from_str("transfer/channel-0/transfer/channel-5/myclass")?;
+        class_id.apply_recv(&port_id, &dest_channel, &port_id, &source_channel);
+        assert_eq!(
+            class_id,
+            PrefixedClassId::from_str("transfer/channel-5/myclass")?
+        );
+
+        // forward: the prefix of the receiver end is added
+        let mut class_id = PrefixedClassId::from_str("transfer/channel-5/myclass")?;
+        class_id.apply_recv(&port_id, &dest_channel, &port_id, &source_channel);
+        assert_eq!(
+            class_id,
+            PrefixedClassId::from_str("transfer/channel-1/transfer/channel-5/myclass")?
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_prefixed_class_id_to_voucher() -> Result<(), NftTransferError> {
+        let class_id = PrefixedClassId::from_str("myclass")?;
+
+        let voucher = class_id.to_voucher(&PortId::transfer(), &ChannelId::new(1));
+
+        assert_eq!(
+            voucher,
+            PrefixedClassId::from_str("transfer/channel-1/myclass")?
+        );
+        assert_eq!(voucher.base_class_id, class_id.base_class_id);
+
+        Ok(())
+    }
+
     #[test]
     fn test_class_id_trace() -> Result<(), NftTransferError> {
         assert_eq!(
@@ -367,6 +1180,62 @@ mod tests {
         Ok(())
     }
 
+    #[rstest]
+    #[case("myclass", 0)]
+    #[case("transfer/channel-0/myclass", 1)]
+    #[case("transfer/channel-0/transfer/channel-1/myclass", 0)]
+    #[case("transfer/channel-0/transfer/channel-1/transfer/channel-2/myclass", 3)]
+    fn test_display_truncated_short_path(#[case] class_id: &str, #[case] max_hops: usize) {
+        let class_id = PrefixedClassId::from_str(class_id).expect("success");
+        assert_eq!(class_id.display_truncated(max_hops), class_id.to_string());
+    }
+
+    #[test]
+    fn test_display_truncated_long_path() -> Result<(), NftTransferError> {
+        let class_id_s =
+            "transfer/channel-0/transfer/channel-1/transfer/channel-2/transfer/channel-3/myclass";
+        let class_id = PrefixedClassId::from_str(class_id_s)?;
+
+        assert_eq!(
+            class_id.display_truncated(3),
+            "transfer/channel-0/.../transfer/channel-3/myclass"
+        );
+        assert_eq!(class_id.display_truncated(4), class_id_s);
+        assert_eq!(class_id.to_string(), class_id_s);
+
+        Ok(())
+    }
+
+    #[rstest]
+    #[case("myclass")]
+    #[case("transfer/channel-0/myclass")]
+    #[case("transfer/channel-0/transfer/channel-1/myclass")]
+    fn test_to_ics20_style_denom(#[case] class_id: &str) {
+        let class_id = PrefixedClassId::from_str(class_id).expect("success");
+        let denom = PrefixedDenom::from_str(class_id.to_string().as_str()).expect("success");
+
+        assert_eq!(
+            class_id.to_ics20_style_denom().expect("success"),
+            denom.to_string()
+        );
+    }
+
+    #[test]
+    fn test_with_base_class() -> Result<(), NftTransferError> {
+        let class_id = PrefixedClassId::from_str("transfer/channel-0/transfer/channel-1/myclass")?;
+        let trace_path = class_id.trace_path.clone();
+
+        let class_id = class_id.with_base_class(ClassId::from_str("renamed")?);
+
+        assert_eq!(class_id.trace_path, trace_path);
+        assert_eq!(
+            class_id.to_string(),
+            "transfer/channel-0/transfer/channel-1/renamed"
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn test_class_id_serde() -> Result<(), NftTransferError> {
         let dt_str = "transfer/channel-0/myclass";
@@ -423,6 +1292,122 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_trace_path_iter() -> Result<(), NftTransferError> {
+        let class_id_str = "transfer/channel-0/transfer/channel-1/myclass";
+        let class_id = PrefixedClassId::from_str(class_id_str)?;
+        let trace_path = &class_id.trace_path;
+
+        assert_eq!(trace_path.len(), 2);
+
+        let hops: Vec<String> = trace_path.iter().map(ToString::to_string).collect();
+        assert_eq!(hops, ["transfer/channel-0", "transfer/channel-1"]);
+
+        let mut rebuilt = String::new();
+        for prefix in trace_path {
+            rebuilt.push_str(&format!("{prefix}/"));
+        }
+        rebuilt.push_str(class_id.base_class_id.as_ref());
+        assert_eq!(rebuilt, class_id_str);
+
+        assert_eq!(TracePath::empty().len(), 0);
+        assert_eq!(TracePath::empty().iter().count(), 0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_prefixed_class_id_max_trace_hops() {
+        let class_id_with_hops = |hops: usize| {
+            let mut s = "transfer/channel-0/".repeat(hops);
+            s.push_str("myclass");
+            s
+        };
+
+        let class_id = PrefixedClassId::from_str_with_max_hops(&class_id_with_hops(2), 2)
+            .expect("at the limit");
+        assert_eq!(class_id.trace_path.len(), 2);
+
+        let class_id = PrefixedClassId::from_str(&class_id_with_hops(DEFAULT_MAX_TRACE_HOPS))
+            .expect("at the default limit");
+        assert_eq!(class_id.trace_path.len(), DEFAULT_MAX_TRACE_HOPS);
+
+        assert!(matches!(
+            PrefixedClassId::from_str_with_max_hops(&class_id_with_hops(3), 2),
+            Err(NftTransferError::TraceTooLong { len: 3, max: 2 })
+        ));
+        assert!(matches!(
+            PrefixedClassId::from_str(&class_id_with_hops(DEFAULT_MAX_TRACE_HOPS + 1)),
+            Err(NftTransferError::TraceTooLong { len: 33, max: 32 })
+        ));
+
+        let class_id = PrefixedClassId::from_str_with_delimiter(
+            &class_id_with_hops(DEFAULT_MAX_TRACE_HOPS).replace('/', ":"),
+            ':',
+        )
+        .expect("at the default limit");
+        assert_eq!(class_id.trace_path.len(), DEFAULT_MAX_TRACE_HOPS);
+
+        assert!(matches!(
+            PrefixedClassId::from_str_with_delimiter(
+                &class_id_with_hops(DEFAULT_MAX_TRACE_HOPS + 1).replace('/', ":"),
+                ':',
+            ),
+            Err(NftTransferError::TraceTooLong { len: 33, max: 32 })
+        ));
+
+        let class_id = PrefixedClassId::from_str_with_max_hops("myclass", 0).expect("no hops");
+        assert!(class_id.trace_path.is_empty());
+        assert_eq!(class_id.base_class_id.as_ref(), "myclass");
+    }
+
+    #[test]
+    fn test_prefixed_class_id_max_len() {
+        let s = "transfer/channel-0/myclass";
+
+        let class_id = PrefixedClassId::from_str_max_len(s, s.len()).expect("at the limit");
+        assert_eq!(class_id.to_string().len(), s.len());
+
+        assert!(matches!(
+            PrefixedClassId::from_str_max_len(s, s.len() - 1),
+            Err(NftTransferError::ClassIdTooLong { len: 26, max: 25 })
+        ));
+        assert!(matches!(
+            PrefixedClassId::from_str_max_len("myclass", 6),
+            Err(NftTransferError::ClassIdTooLong { len: 7, max: 6 })
+        ));
+    }
+
+    #[cfg(feature = "serde")]
+    #[rstest]
+    #[case("myclass", "", "myclass")]
+    #[case("transfer/channel-0/myclass", "transfer/channel-0", "myclass")]
+    #[case(
+        "transfer/channel-0/transfer/channel-1/myclass",
+        "transfer/channel-0/transfer/channel-1",
+        "myclass"
+    )]
+    fn test_prefixed_class_id_serde_object(
+        #[case] class_id: &str,
+        #[case] trace_path: &str,
+        #[case] base_class_id: &str,
+    ) {
+        let prefixed_class_id = PrefixedClassId::from_str(class_id).expect("valid class ID");
+        let object = serde_json::json!({
+            "trace_path": trace_path,
+            "base_class_id": base_class_id,
+        });
+
+        assert_eq!(
+            serde_json::to_value(&prefixed_class_id).expect("no error"),
+            object
+        );
+        assert_eq!(
+            serde_json::from_value::<PrefixedClassId>(object).expect("no error"),
+            prefixed_class_id
+        );
+    }
+
     #[test]
     fn test_serde_json_roundtrip() {
         fn serde_roundtrip(class_uri: ClassUri) {
@@ -434,7 +1419,7 @@ mod tests {
             assert_eq!(deserialized, class_uri);
         }
 
-        let uri = "/foo/bar?baz".parse::<Uri>().unwrap();
+        let uri = "https://example.com/foo/bar?baz".parse::<Uri>().unwrap();
         serde_roundtrip(ClassUri(uri));
 
         let uri = "https://www.rust-lang.org/install.html"
@@ -443,6 +1428,126 @@ mod tests {
         serde_roundtrip(ClassUri(uri));
     }
 
+    #[rstest]
+    #[case("myclass")]
+    #[case("transfer:channel-0:myclass")]
+    #[case("transfer:channel-0:transfer:channel-1:myclass")]
+    #[case("transfer:channel-0:my/class")]
+    fn test_class_id_with_delimiter_round_trip(#[case] class_id: &str) {
+        let prefixed_class_id =
+            PrefixedClassId::from_str_with_delimiter(class_id, ':').expect("success");
+
+        assert_eq!(prefixed_class_id.display_with_delimiter(':'), class_id);
+        assert_eq!(
+            prefixed_class_id,
+            PrefixedClassId::from_str(&class_id.replace(':', "/")).expect("success")
+        );
+    }
+
+    #[test]
+    fn test_class_id_with_delimiter() {
+        let class_id = PrefixedClassId::from_str_with_delimiter(
+            "transfer:channel-0:transfer:channel-1:myclass",
+            ':',
+        )
+        .expect("success");
+
+        assert_eq!(
+            class_id.to_string(),
+            "transfer/channel-0/transfer/channel-1/myclass"
+        );
+        assert!(class_id
+            .trace_path
+            .starts_with(&TracePrefix::new(PortId::transfer(), ChannelId::new(0))));
+    }
+
+    #[rstest]
+    #[case('-')]
+    #[case('.')]
+    #[case('a')]
+    #[case('0')]
+    fn test_rejected_class_id_delimiter(#[case] delimiter: char) {
+        assert!(matches!(
+            PrefixedClassId::from_str_with_delimiter("transfer-channel-0-myclass", delimiter),
+            Err(NftTransferError::InvalidTraceDelimiter { .. })
+        ));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_class_data_json_value() {
+        let class_data = ClassData::from_str(
+            r#"{
+                "name": {"value": "Cosmos Punks"},
+                "description": {"value": "A collection of 10k punks", "mime": "text/plain"},
+                "image": {"value": "ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi"}
+            }"#,
+        )
+        .expect("success");
+
+        let value = class_data.to_json_value().expect("valid ICS-721 data");
+        assert_eq!(value["description"]["mime"], "text/plain");
+        assert_eq!(value["name"]["value"], "Cosmos Punks");
+
+        let rebuilt = ClassData::from_json_value(value).expect("valid ICS-721 data");
+        assert_eq!(rebuilt.to_canonical_json(), class_data.to_canonical_json());
+
+        assert!(matches!(
+            ClassData::from_json_value(serde_json::json!({"name": "Cosmos Punks"})),
+            Err(NftTransferError::InvalidIcs721Data)
+        ));
+        assert!(matches!(
+            ClassData::from_str("not json")
+                .expect("any string is accepted")
+                .to_json_value(),
+            Err(NftTransferError::InvalidIcs721Data)
+        ));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_class_data_canonical_json() {
+        let class_data = ClassData::from_str(
+            r#"{"name": {"value": "foo", "mime": "text/plain"}, "image": {"value": "bar"}}"#,
+        )
+        .expect("success");
+        let reordered = ClassData::from_str(
+            r#"{"image":{"value":"bar"},"name":{"mime":"text/plain","value":"foo"}}"#,
+        )
+        .expect("success");
+
+        assert_ne!(class_data.to_string(), reordered.to_string());
+        assert_eq!(
+            class_data.to_canonical_json(),
+            reordered.to_canonical_json()
+        );
+        assert_eq!(
+            class_data.to_canonical_json(),
+            br#"{"image":{"value":"bar"},"name":{"mime":"text/plain","value":"foo"}}"#.to_vec()
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_class_data_canonical_json_nested() {
+        let class_data = ClassData::from_str(
+            r#"{"z": {"b": [{"y": 1, "x": {"q": null, "p": true}}], "a": "s"}, "m": []}"#,
+        )
+        .expect("success");
+        let reordered =
+            ClassData::from_str(r#"{"m":[],"z":{"a":"s","b":[{"x":{"p":true,"q":null},"y":1}]}}"#)
+                .expect("success");
+
+        assert_eq!(
+            class_data.to_canonical_json(),
+            reordered.to_canonical_json()
+        );
+        assert_eq!(
+            class_data.to_canonical_json(),
+            br#"{"m":[],"z":{"a":"s","b":[{"x":{"p":true,"q":null},"y":1}]}}"#.to_vec()
+        );
+    }
+
     #[cfg(feature = "borsh")]
     #[test]
     fn test_borsh_roundtrip() {
@@ -455,7 +1560,7 @@ mod tests {
             assert_eq!(class_uri, res);
         }
 
-        let uri = "/foo/bar?baz".parse::<Uri>().unwrap();
+        let uri = "https://example.com/foo/bar?baz".parse::<Uri>().unwrap();
         borsh_roundtrip(ClassUri(uri));
 
         let uri = "https://www.rust-lang.org/install.html"
@@ -463,4 +1568,29 @@ mod tests {
             .unwrap();
         borsh_roundtrip(ClassUri(uri));
     }
+
+    #[cfg(feature = "homoglyph-check")]
+    #[rstest]
+    #[case("myclass")]
+    #[case("my-class_01")]
+    #[case("класс")]
+    #[case("κλάση")]
+    fn test_class_id_single_script(#[case] class_id: &str) {
+        let parsed = ClassId::from_str_no_homoglyphs(class_id).expect("success");
+        assert_eq!(parsed.as_ref(), class_id);
+    }
+
+    #[cfg(feature = "homoglyph-check")]
+    #[rstest]
+    // Cyrillic `а` (U+0430) in place of the Latin `a`
+    #[case("punk\u{0430}pes")]
+    // Greek `ο` (U+03BF) in place of the Latin `o`
+    #[case("b\u{03BF}red")]
+    fn test_class_id_mixed_script_homoglyph(#[case] class_id: &str) {
+        assert!(ClassId::from_str(class_id).is_ok());
+        assert!(matches!(
+            ClassId::from_str_no_homoglyphs(class_id),
+            Err(NftTransferError::MixedScriptBaseClassId { .. })
+        ));
+    }
 }
@@ -33,6 +33,52 @@ impl Data {
     pub fn parse_as_ics721_data(&self) -> Result<Ics721Data, NftTransferError> {
         self.0.parse::<Ics721Data>()
     }
+
+    /// Returns the canonical JSON encoding of the data, i.e. with the keys of
+    /// the objects sorted at every depth and without whitespace, so that all
+    /// chains commit to the same bytes.
+    ///
+    /// Data that is not valid JSON has no canonical form, hence its raw bytes
+    /// are returned as is.
+    pub fn to_canonical_json(&self) -> Vec<u8> {
+        match serde_json::from_str::<serde_json::Value>(&self.0) {
+            Ok(value) => sort_json_keys(value).to_string().into_bytes(),
+            Err(_) => self.0.as_bytes().to_vec(),
+        }
+    }
+
+    /// Returns the data as a JSON value, after checking that it follows the
+    /// format specified by ICS-721.
+    pub fn to_json_value(&self) -> Result<serde_json::Value, NftTransferError> {
+        let data = self.parse_as_ics721_data()?;
+        serde_json::to_value(data).map_err(|_| NftTransferError::InvalidIcs721Data)
+    }
+
+    /// Builds the data from a JSON value in the format specified by ICS-721.
+    pub fn from_json_value(value: serde_json::Value) -> Result<Self, NftTransferError> {
+        serde_json::from_value::<Ics721Data>(value.clone())
+            .map_err(|_| NftTransferError::InvalidIcs721Data)?;
+        Ok(Self(value.to_string()))
+    }
+}
+
+/// Rebuilds the objects of the given JSON value, however nested, with their
+/// keys in order, whether or not `serde_json` preserves insertion order.
+#[cfg(feature = "serde")]
+fn sort_json_keys(value: serde_json::Value) -> serde_json::Value {
+    use serde_json::Value;
+
+    match value {
+        Value::Object(map) => Value::Object(
+            map.into_iter()
+                .map(|(key, value)| (key, sort_json_keys(value)))
+                .collect::<BTreeMap<_, _>>()
+                .into_iter()
+                .collect(),
+        ),
+        Value::Array(values) => Value::Array(values.into_iter().map(sort_json_keys).collect()),
+        value => value,
+    }
 }
 
 impl Display for Data {
@@ -21,6 +21,8 @@ pub enum NftTransferError {
         uri: String,
         validation_error: http::uri::InvalidUri,
     },
+    /// invalid URI: `{uri}`, expected an absolute URI with a scheme
+    RelativeUri { uri: String },
     /// destination channel not found in the counterparty of port_id `{port_id}` and channel_id `{channel_id}`
     DestinationChannelNotFound {
         port_id: PortId,
@@ -28,6 +30,12 @@ pub enum NftTransferError {
     },
     /// base class ID is empty
     EmptyBaseClassId,
+    /// base class ID `{base_class_id}` starts with a trace prefix
+    AmbiguousBaseClassId { base_class_id: String },
+    /// base class ID `{base_class_id}` mixes letters of confusable scripts
+    MixedScriptBaseClassId { base_class_id: String },
+    /// base class ID `{class_id}` is rejected by the host
+    InvalidBaseClassId { class_id: String },
     /// invalid prot id n trace at position: `{pos}`, validation error: `{validation_error}`
     InvalidTracePortId {
         pos: u64,
@@ -40,6 +48,12 @@ pub enum NftTransferError {
     },
     /// trace length must be even but got: `{len}`
     InvalidTraceLength { len: u64 },
+    /// trace path has `{len}` hops, more than the maximum of `{max}`
+    TraceTooLong { len: u64, max: u64 },
+    /// class ID has `{len}` bytes, more than the maximum of `{max}`
+    ClassIdTooLong { len: u64, max: u64 },
+    /// trace delimiter `{delimiter}` may appear in port or channel IDs
+    InvalidTraceDelimiter { delimiter: char },
     /// no token ID
     NoTokenId,
     /// invalid token ID
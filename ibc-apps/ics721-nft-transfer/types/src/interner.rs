@@ -0,0 +1,163 @@
+//! Interning of NFT class identifiers, available under the `interner` feature.
+//!
+//! Off-chain services such as indexers may hold millions of tokens belonging
+//! to a handful of classes. The [`ClassIdInterner`] keeps a single shared
+//! allocation per distinct class ID so that repeated occurrences only cost a
+//! reference-counted pointer.
+//!
+//! The interner guards its table with a [`Mutex`], hence it is `Send + Sync`
+//! and can be shared between threads, e.g. behind an [`Arc`]. The feature
+//! depends on `std` for this reason.
+use core::fmt::{self, Display, Formatter};
+use core::str::FromStr;
+use std::collections::BTreeSet;
+use std::sync::{Arc, Mutex, PoisonError};
+
+use crate::class::{ClassId, PrefixedClassId, TracePath};
+
+/// A deduplicating store of class IDs.
+#[derive(Debug, Default)]
+pub struct ClassIdInterner {
+    class_ids: Mutex<BTreeSet<Arc<str>>>,
+}
+
+impl ClassIdInterner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the shared copy of the given class ID, allocating it on first use.
+    pub fn intern(&self, class_id: &ClassId) -> InternedClassId {
+        let mut class_ids = self
+            .class_ids
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+
+        if let Some(interned) = class_ids.get(class_id.as_ref()) {
+            return InternedClassId(interned.clone());
+        }
+
+        let interned: Arc<str> = Arc::from(class_id.as_ref());
+        class_ids.insert(interned.clone());
+
+        InternedClassId(interned)
+    }
+
+    /// Interns the base class of the given prefixed class ID, keeping its trace path as is.
+    pub fn intern_prefixed(&self, class_id: &PrefixedClassId) -> InternedPrefixedClassId {
+        InternedPrefixedClassId {
+            trace_path: class_id.trace_path.clone(),
+            base_class_id: self.intern(&class_id.base_class_id),
+        }
+    }
+
+    /// Returns the number of distinct class IDs held by the interner.
+    pub fn len(&self) -> usize {
+        self.class_ids
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .len()
+    }
+
+    /// Returns true if no class ID has been interned yet.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// A class ID whose storage is shared with every other occurrence interned by
+/// the same [`ClassIdInterner`].
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct InternedClassId(Arc<str>);
+
+impl InternedClassId {
+    /// Returns the shared storage of the class ID.
+    pub fn as_arc(&self) -> &Arc<str> {
+        &self.0
+    }
+}
+
+impl AsRef<str> for InternedClassId {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Display for InternedClassId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl From<InternedClassId> for ClassId {
+    fn from(class_id: InternedClassId) -> Self {
+        // The interned string always originates from a valid `ClassId`.
+        Self::from_str(&class_id.0).expect("interned class ID is never empty")
+    }
+}
+
+/// A [`PrefixedClassId`] whose base class is interned.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct InternedPrefixedClassId {
+    /// A series of `{port-id}/{channel-id}`s for tracing the source of the class.
+    pub trace_path: TracePath,
+    /// Interned base class of the relayed non-fungible token.
+    pub base_class_id: InternedClassId,
+}
+
+impl Display for InternedPrefixedClassId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.trace_path.is_empty() {
+            write!(f, "{}", self.base_class_id)
+        } else {
+            write!(f, "{}/{}", self.trace_path, self.base_class_id)
+        }
+    }
+}
+
+impl From<InternedPrefixedClassId> for PrefixedClassId {
+    fn from(class_id: InternedPrefixedClassId) -> Self {
+        Self {
+            trace_path: class_id.trace_path,
+            base_class_id: class_id.base_class_id.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ibc_core::primitives::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn test_intern_class_id_twice() {
+        let interner = ClassIdInterner::new();
+
+        let class_id = ClassId::from_str("myclass").expect("success");
+        let first = interner.intern(&class_id);
+        let second = interner.intern(&ClassId::from_str("myclass").expect("success"));
+
+        assert!(Arc::ptr_eq(first.as_arc(), second.as_arc()));
+        assert_eq!(interner.len(), 1);
+        assert_eq!(ClassId::from(second), class_id);
+    }
+
+    #[test]
+    fn test_intern_prefixed_class_id_shares_base_class() {
+        let interner = ClassIdInterner::new();
+
+        let class_id_0 = PrefixedClassId::from_str("transfer/channel-0/myclass").expect("success");
+        let class_id_1 = PrefixedClassId::from_str("transfer/channel-1/myclass").expect("success");
+
+        let interned_0 = interner.intern_prefixed(&class_id_0);
+        let interned_1 = interner.intern_prefixed(&class_id_1);
+
+        assert!(Arc::ptr_eq(
+            interned_0.base_class_id.as_arc(),
+            interned_1.base_class_id.as_arc()
+        ));
+        assert_eq!(interned_0.to_string(), class_id_0.to_string());
+        assert_eq!(PrefixedClassId::from(interned_1), class_id_1);
+    }
+}
@@ -23,6 +23,8 @@ mod memo;
 mod token;
 
 pub mod events;
+#[cfg(feature = "interner")]
+pub mod interner;
 pub mod msgs;
 pub use class::*;
 pub use data::*;
@@ -7,6 +7,7 @@ use ibc_core::primitives::prelude::*;
 #[cfg(feature = "serde")]
 use ibc_core::primitives::serializers;
 
+use crate::class::parse_absolute_uri;
 use crate::data::Data;
 use crate::error::NftTransferError;
 
@@ -108,14 +109,24 @@ impl TryFrom<Vec<String>> for TokenIds {
 }
 
 /// Token URI for an NFT
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[derive(Clone, Debug, PartialEq, Eq)]
-pub struct TokenUri(
-    #[cfg_attr(feature = "serde", serde(with = "serializers"))]
-    #[cfg_attr(feature = "schema", schemars(with = "String"))]
-    Uri,
-);
+pub struct TokenUri(#[cfg_attr(feature = "schema", schemars(with = "String"))] Uri);
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for TokenUri {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializers::serialize(self, serializer)
+    }
+}
+
+/// Goes through [`FromStr`], so that relative URIs are rejected.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for TokenUri {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        serializers::deserialize(deserializer)
+    }
+}
 
 #[cfg(feature = "borsh")]
 impl borsh::BorshSerialize for TokenUri {
@@ -178,13 +189,7 @@ impl FromStr for TokenUri {
     type Err = NftTransferError;
 
     fn from_str(token_uri: &str) -> Result<Self, Self::Err> {
-        match Uri::from_str(token_uri) {
-            Ok(uri) => Ok(Self(uri)),
-            Err(err) => Err(NftTransferError::InvalidUri {
-                uri: token_uri.to_string(),
-                validation_error: err,
-            }),
-        }
+        parse_absolute_uri(token_uri).map(Self)
     }
 }
 
@@ -206,6 +211,29 @@ impl FromStr for TokenUri {
 #[derive(Clone, Debug, PartialEq, Eq, derive_more::AsRef)]
 pub struct TokenData(Data);
 
+impl TokenData {
+    /// Returns the canonical JSON encoding of the token data, which is the
+    /// form to hash for commitments. Its [`Display`] output is the data as
+    /// received and may differ in key order or whitespace.
+    #[cfg(feature = "serde")]
+    pub fn to_canonical_json(&self) -> Vec<u8> {
+        self.0.to_canonical_json()
+    }
+
+    /// Returns the token data as a JSON value, failing if it does not follow
+    /// the format specified by ICS-721.
+    #[cfg(feature = "serde")]
+    pub fn to_json_value(&self) -> Result<serde_json::Value, NftTransferError> {
+        self.0.to_json_value()
+    }
+
+    /// Builds the token data from a JSON value in the format specified by ICS-721.
+    #[cfg(feature = "serde")]
+    pub fn from_json_value(value: serde_json::Value) -> Result<Self, NftTransferError> {
+        Data::from_json_value(value).map(Self)
+    }
+}
+
 impl Display for TokenData {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.0)
@@ -225,6 +253,22 @@ impl FromStr for TokenData {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_token_uri_requires_scheme() {
+        for uri in ["/images/nft.png", "example.com"] {
+            assert!(matches!(
+                TokenUri::from_str(uri),
+                Err(NftTransferError::RelativeUri { .. })
+            ));
+
+            #[cfg(feature = "serde")]
+            assert!(serde_json::from_value::<TokenUri>(serde_json::json!(uri)).is_err());
+        }
+
+        let uri = "ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi/1.json";
+        assert_eq!(TokenUri::from_str(uri).expect("success").to_string(), uri);
+    }
+
     #[cfg(feature = "serde")]
     #[test]
     fn test_serde_json_roundtrip() {
@@ -237,7 +281,7 @@ mod tests {
             assert_eq!(deserialized, token_uri);
         }
 
-        let uri = "/foo/bar?baz".parse::<Uri>().unwrap();
+        let uri = "https://example.com/foo/bar?baz".parse::<Uri>().unwrap();
         serde_roundtrip(TokenUri(uri));
 
         let uri = "https://www.rust-lang.org/install.html"
@@ -258,7 +302,7 @@ mod tests {
             assert_eq!(token_uri, res);
         }
 
-        let uri = "/foo/bar?baz".parse::<Uri>().unwrap();
+        let uri = "https://example.com/foo/bar?baz".parse::<Uri>().unwrap();
         borsh_roundtrip(TokenUri(uri));
 
         let uri = "https://www.rust-lang.org/install.html"
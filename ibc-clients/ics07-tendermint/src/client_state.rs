@@ -10,7 +10,7 @@
 
 use ibc_client_tendermint_types::error::Error;
 use ibc_client_tendermint_types::proto::v1::ClientState as RawTmClientState;
-use ibc_client_tendermint_types::ClientState as ClientStateType;
+use ibc_client_tendermint_types::{ClientState as ClientStateType, TrustThreshold};
 use ibc_core_client::types::error::ClientError;
 use ibc_primitives::prelude::*;
 use ibc_primitives::proto::{Any, Protobuf};
@@ -39,6 +39,12 @@ impl ClientState {
     pub fn inner(&self) -> &ClientStateType {
         &self.0
     }
+
+    /// Returns the trust level configured for the client, i.e. the fraction of
+    /// the trusted validator set's voting power that must sign a new header.
+    pub fn trust_threshold(&self) -> TrustThreshold {
+        self.0.trust_level
+    }
 }
 
 impl Protobuf<RawTmClientState> for ClientState {}
@@ -77,9 +83,7 @@ impl From<ClientState> for Any {
 mod tests {
     use core::time::Duration;
 
-    use ibc_client_tendermint_types::{
-        AllowUpdate, ClientState as ClientStateType, TrustThreshold,
-    };
+    use ibc_client_tendermint_types::AllowUpdate;
     use ibc_core_client::types::Height;
     use ibc_core_commitment_types::specs::ProofSpecs;
     use ibc_core_host::types::identifiers::ChainId;
@@ -169,4 +173,29 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn client_state_trust_threshold() {
+        let trust_level = TrustThreshold::new(1, 4).expect("Never fails");
+
+        let client_state = ClientState(
+            ClientStateType::new(
+                ChainId::new("ibc-1").unwrap(),
+                trust_level,
+                Duration::new(64000, 0),
+                Duration::new(128_000, 0),
+                Duration::new(3, 0),
+                Height::new(1, 10).expect("Never fails"),
+                ProofSpecs::cosmos(),
+                Vec::new(),
+                AllowUpdate {
+                    after_expiry: false,
+                    after_misbehaviour: false,
+                },
+            )
+            .expect("Never fails"),
+        );
+
+        assert_eq!(client_state.trust_threshold(), trust_level);
+    }
 }
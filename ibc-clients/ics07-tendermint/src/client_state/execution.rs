@@ -336,12 +336,12 @@ where
         let tm_consensus_state: ConsensusStateType =
             consensus_state.try_into().map_err(Into::into)?;
 
-        let host_timestamp =
-            ctx.host_timestamp()?
-                .into_tm_time()
-                .ok_or_else(|| ClientError::Other {
-                    description: String::from("host timestamp is not a valid TM timestamp"),
-                })?;
+        let host_timestamp = ctx
+            .client_host_timestamp(client_id)?
+            .into_tm_time()
+            .ok_or_else(|| ClientError::Other {
+                description: String::from("host timestamp is not a valid TM timestamp"),
+            })?;
 
         let tm_consensus_state_timestamp = tm_consensus_state.timestamp();
         let tm_consensus_state_expiry = (tm_consensus_state_timestamp
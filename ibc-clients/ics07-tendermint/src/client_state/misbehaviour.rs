@@ -8,14 +8,44 @@ use ibc_core_host::types::identifiers::{ChainId, ClientId};
 use ibc_core_host::types::path::ClientConsensusStatePath;
 use ibc_primitives::prelude::*;
 use ibc_primitives::Timestamp;
+use tendermint::crypto::default::Sha256 as DefaultSha256;
 use tendermint::crypto::Sha256;
 use tendermint::merkle::MerkleHash;
 use tendermint::{Hash, Time};
 use tendermint_light_client_verifier::options::Options;
-use tendermint_light_client_verifier::Verifier;
+use tendermint_light_client_verifier::{ProdVerifier, Verifier};
 
+use super::ClientState;
 use crate::types::Header;
 
+impl ClientState {
+    /// Determines whether or not the given misbehaviour would have convinced
+    /// the light client, without any context access, e.g. for a relayer to
+    /// check the evidence it built off-chain.
+    ///
+    /// The trusted consensus states are the ones at the trusted heights of
+    /// the first and second headers respectively, and `current_timestamp` is
+    /// the time the trusting period is checked against. Verification is
+    /// carried out like [`verify_misbehaviour`] with the default verifier.
+    pub fn verify_misbehaviour_with_states(
+        &self,
+        misbehaviour: TmMisbehaviour,
+        trusted_cs_1: ConsensusStateType,
+        trusted_cs_2: ConsensusStateType,
+        current_timestamp: Timestamp,
+    ) -> Result<(), ClientError> {
+        verify_misbehaviour_with_states::<DefaultSha256>(
+            &misbehaviour,
+            &trusted_cs_1,
+            &trusted_cs_2,
+            self.inner().chain_id(),
+            &self.inner().as_light_client_options()?,
+            current_timestamp,
+            &ProdVerifier::default(),
+        )
+    }
+}
+
 /// Determines whether or not two conflicting headers at the same height would
 /// have convinced the light client.
 pub fn verify_misbehaviour<V, H>(
@@ -32,8 +62,17 @@ where
     <ConsensusStateType as TryFrom<V::ConsensusStateRef>>::Error: Into<ClientError>,
     H: MerkleHash + Sha256 + Default,
 {
+    // Malformed evidence is rejected before any state is read. It is checked
+    // again by `verify_misbehaviour_with_states`, which is cheap in comparison
+    // with the signature verification.
     misbehaviour.validate_basic::<H>()?;
 
+    // The same header submitted twice is evidence of nothing, and is not
+    // worth the verification of its signatures.
+    if misbehaviour.header1() == misbehaviour.header2() {
+        return Err(ClientError::MisbehaviourHeadersIdentical);
+    }
+
     let header_1 = misbehaviour.header1();
     let trusted_consensus_state_1: ConsensusStateType = {
         let consensus_state_path = ClientConsensusStatePath::new(
@@ -58,10 +97,41 @@ where
         consensus_state.try_into().map_err(Into::into)?
     };
 
-    let current_timestamp = ctx.host_timestamp()?;
+    let current_timestamp = ctx.client_host_timestamp(client_id)?;
+
+    verify_misbehaviour_with_states::<H>(
+        misbehaviour,
+        &trusted_consensus_state_1,
+        &trusted_consensus_state_2,
+        chain_id,
+        options,
+        current_timestamp,
+        verifier,
+    )
+}
+
+/// Determines whether or not two conflicting headers at the same height would
+/// have convinced the light client, given the consensus states trusted by the
+/// first and second headers respectively.
+///
+/// This is the context-free part of [`verify_misbehaviour`], which fetches the
+/// trusted consensus states and the current timestamp before delegating here.
+pub fn verify_misbehaviour_with_states<H>(
+    misbehaviour: &TmMisbehaviour,
+    trusted_consensus_state_1: &ConsensusStateType,
+    trusted_consensus_state_2: &ConsensusStateType,
+    chain_id: &ChainId,
+    options: &Options,
+    current_timestamp: Timestamp,
+    verifier: &impl Verifier,
+) -> Result<(), ClientError>
+where
+    H: MerkleHash + Sha256 + Default,
+{
+    misbehaviour.validate_basic::<H>()?;
 
     verify_misbehaviour_header::<H>(
-        header_1,
+        misbehaviour.header1(),
         chain_id,
         options,
         trusted_consensus_state_1.timestamp(),
@@ -70,7 +140,7 @@ where
         verifier,
     )?;
     verify_misbehaviour_header::<H>(
-        header_2,
+        misbehaviour.header2(),
         chain_id,
         options,
         trusted_consensus_state_2.timestamp(),
@@ -92,6 +162,11 @@ pub fn verify_misbehaviour_header<H>(
 where
     H: MerkleHash + Sha256 + Default,
 {
+    // a malformed submission may omit the trusted next validator set altogether
+    if header.trusted_next_validator_set.validators().is_empty() {
+        return Err(ClientError::MissingNextValidatorSet);
+    }
+
     // ensure correctness of the trusted next validator set provided by the relayer
     header.check_trusted_next_validator_set::<H>(&trusted_next_validator_hash)?;
 
@@ -216,7 +216,7 @@ where
     // Note: if the `duration_since()` is `None`, indicating that the latest
     // consensus state is in the future, then we don't consider the client
     // to be expired.
-    let now = ctx.host_timestamp()?;
+    let now = ctx.client_host_timestamp(client_id)?;
 
     if let Some(elapsed_since_latest_consensus_state) =
         now.duration_since(&latest_consensus_state.timestamp().into())
@@ -1,3 +1,4 @@
+use ibc_core_client_types::error::ClientError;
 use ibc_core_client_types::Height;
 use ibc_core_handler_types::error::ContextError;
 use ibc_core_host_types::identifiers::ClientId;
@@ -39,6 +40,27 @@ pub trait ClientValidationContext: Sized {
         client_id: &ClientId,
         height: &Height,
     ) -> Result<(Timestamp, Height), ContextError>;
+
+    /// Returns every height of the given client with stored update metadata,
+    /// i.e. for which [`client_update_meta`](Self::client_update_meta)
+    /// succeeds, sorted in ascending order.
+    ///
+    /// Along with
+    /// [`delete_update_meta`](ClientExecutionContext::delete_update_meta), it
+    /// lets callers, e.g. relayers, prune the metadata of old client updates.
+    /// The default implementation fails, as enumerating the metadata depends
+    /// on the layout of the host store.
+    fn client_update_meta_heights(
+        &self,
+        client_id: &ClientId,
+    ) -> Result<Vec<Height>, ContextError> {
+        Err(ClientError::Other {
+            description: format!(
+                "the host does not support enumerating the update metadata of client {client_id}"
+            ),
+        }
+        .into())
+    }
 }
 
 /// Defines the methods that all client `ExecutionContext`s (precisely the
@@ -117,12 +139,55 @@ pub trait ExtClientValidationContext: ClientValidationContext {
     /// Returns the current timestamp of the local chain.
     fn host_timestamp(&self) -> Result<Timestamp, ContextError>;
 
+    /// Returns the current timestamp of the local chain, as seen by the
+    /// time-based checks of the given client, e.g. its expiry.
+    ///
+    /// The default implementation returns [`host_timestamp`](Self::host_timestamp).
+    /// Hosts may override it for testing purposes, e.g. to skew the clock of
+    /// a client.
+    fn client_host_timestamp(&self, _client_id: &ClientId) -> Result<Timestamp, ContextError> {
+        self.host_timestamp()
+    }
+
     /// Returns the current height of the local chain.
     fn host_height(&self) -> Result<Height, ContextError>;
 
     /// Returns all the heights at which a consensus state is stored.
     fn consensus_state_heights(&self, client_id: &ClientId) -> Result<Vec<Height>, ContextError>;
 
+    /// Returns up to `limit` heights at which a consensus state is stored, in
+    /// ascending order and strictly above `start_after` if given, along with
+    /// the cursor to pass as `start_after` to get the next page. The cursor is
+    /// `None` once no height remains. A zero limit yields an empty page.
+    ///
+    /// The default implementation pages through
+    /// [`consensus_state_heights`](Self::consensus_state_heights). Hosts whose
+    /// store is ordered by height may override it to avoid reading every key.
+    fn consensus_state_heights_paginated(
+        &self,
+        client_id: &ClientId,
+        start_after: Option<Height>,
+        limit: usize,
+    ) -> Result<(Vec<Height>, Option<Height>), ContextError> {
+        let mut heights = self.consensus_state_heights(client_id)?;
+        heights.sort();
+
+        if let Some(start_after) = start_after {
+            heights.retain(|height| *height > start_after);
+        }
+
+        let exhausted = heights.len() <= limit;
+        heights.truncate(limit);
+
+        let next = if exhausted {
+            None
+        } else {
+            heights.last().copied()
+        };
+
+        Ok((heights, next))
+    }
+
     /// Search for the lowest consensus state higher than `height`.
     fn next_consensus_state(
         &self,
@@ -4,15 +4,18 @@ use ibc_core_client_context::prelude::*;
 use ibc_core_client_types::error::ClientError;
 use ibc_core_client_types::events::UpgradeClient;
 use ibc_core_client_types::msgs::MsgUpgradeClient;
+use ibc_core_client_types::Height;
 use ibc_core_handler_types::error::ContextError;
 use ibc_core_handler_types::events::{IbcEvent, MessageEvent};
 use ibc_core_host::types::path::ClientConsensusStatePath;
-use ibc_core_host::{ExecutionContext, ValidationContext};
+use ibc_core_host::{ClientStateRef, ExecutionContext, ValidationContext};
 use ibc_primitives::prelude::*;
+use ibc_primitives::proto::Any;
 
 pub fn validate<Ctx>(ctx: &Ctx, msg: MsgUpgradeClient) -> Result<(), ContextError>
 where
     Ctx: ValidationContext,
+    <ClientStateRef<Ctx> as TryFrom<Any>>::Error: Into<ClientError>,
 {
     let MsgUpgradeClient {
         client_id, signer, ..
@@ -30,6 +33,19 @@ where
         .status(client_val_ctx, &client_id)?
         .verify_is_active()?;
 
+    // Check that the upgrade moves the client strictly forward, regardless of
+    // what the light client implementation verifies.
+    let upgraded_client_state =
+        ClientStateRef::<Ctx>::try_from(msg.upgraded_client_state.clone()).map_err(Into::into)?;
+
+    if upgraded_client_state.latest_height() <= old_client_state.latest_height() {
+        return Err(ClientError::InvalidUpgradeHeight {
+            current: old_client_state.latest_height(),
+            upgraded: upgraded_client_state.latest_height(),
+        }
+        .into());
+    }
+
     // Read the latest consensus state from the host chain store.
     let old_client_cons_state_path = ClientConsensusStatePath::new(
         client_id.clone(),
@@ -55,7 +71,11 @@ where
     Ok(())
 }
 
-pub fn execute<Ctx>(ctx: &mut Ctx, msg: MsgUpgradeClient) -> Result<(), ContextError>
+/// Upgrades the client, then returns its new latest height.
+///
+/// The emitted [`UpgradeClient`] event carries both the new latest height and
+/// the one of the client before the upgrade.
+pub fn execute<Ctx>(ctx: &mut Ctx, msg: MsgUpgradeClient) -> Result<Height, ContextError>
 where
     Ctx: ExecutionContext,
 {
@@ -64,6 +84,7 @@ where
     let client_exec_ctx = ctx.get_client_execution_context();
 
     let old_client_state = client_exec_ctx.client_state(&client_id)?;
+    let previous_height = old_client_state.latest_height();
 
     let latest_height = old_client_state.update_state_on_upgrade(
         client_exec_ctx,
@@ -76,9 +97,10 @@ where
         client_id,
         old_client_state.client_type(),
         latest_height,
+        previous_height,
     ));
     ctx.emit_ibc_event(IbcEvent::Message(MessageEvent::Client))?;
     ctx.emit_ibc_event(event)?;
 
-    Ok(())
+    Ok(latest_height)
 }
@@ -40,6 +40,8 @@ pub enum ClientError {
     UpdateMetaDataNotFound { client_id: ClientId, height: Height },
     /// header verification failed with reason: `{reason}`
     HeaderVerificationFailure { reason: String },
+    /// header is missing the trusted next validator set
+    MissingNextValidatorSet,
     /// failed to build trust threshold from fraction: `{numerator}`/`{denominator}`
     InvalidTrustThreshold { numerator: u64, denominator: u64 },
     /// failed to build Tendermint domain type trust threshold from fraction: `{numerator}`/`{denominator}`
@@ -81,6 +83,8 @@ pub enum ClientError {
         latest_height: Height,
         proof_height: Height,
     },
+    /// upgraded client height `{upgraded}` must be greater than current client height `{current}`
+    InvalidUpgradeHeight { current: Height, upgraded: Height },
     /// invalid commitment proof bytes error: `{0}`
     InvalidCommitmentProof(CommitmentError),
     /// invalid packet timeout timestamp value error: `{0}`
@@ -97,6 +101,8 @@ pub enum ClientError {
     Ics23Verification(CommitmentError),
     /// misbehaviour handling failed with reason: `{reason}`
     MisbehaviourHandlingFailure { reason: String },
+    /// misbehaviour headers are identical
+    MisbehaviourHeadersIdentical,
     /// client specific error: `{description}`
     ClientSpecific { description: String },
     /// client counter overflow error
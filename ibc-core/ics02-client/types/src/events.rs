@@ -22,6 +22,9 @@ pub const CLIENT_TYPE_ATTRIBUTE_KEY: &str = "client_type";
 /// The content of the `key` field for the attribute containing the height.
 pub const CONSENSUS_HEIGHT_ATTRIBUTE_KEY: &str = "consensus_height";
 
+/// The content of the `key` field for the attribute containing the latest height of a client before its upgrade.
+pub const PREVIOUS_HEIGHT_ATTRIBUTE_KEY: &str = "previous_height";
+
 /// The content of the `key` field for the attribute containing the heights of consensus states that were processed.
 pub const CONSENSUS_HEIGHTS_ATTRIBUTE_KEY: &str = "consensus_heights";
 
@@ -100,6 +103,30 @@ impl From<ConsensusHeightAttribute> for abci::EventAttribute {
     }
 }
 
+#[cfg_attr(
+    feature = "parity-scale-codec",
+    derive(
+        parity_scale_codec::Encode,
+        parity_scale_codec::Decode,
+        scale_info::TypeInfo
+    )
+)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, From, PartialEq, Eq)]
+struct PreviousHeightAttribute {
+    previous_height: Height,
+}
+
+impl From<PreviousHeightAttribute> for abci::EventAttribute {
+    fn from(attr: PreviousHeightAttribute) -> Self {
+        (PREVIOUS_HEIGHT_ATTRIBUTE_KEY, attr.previous_height).into()
+    }
+}
+
 #[cfg_attr(
     feature = "parity-scale-codec",
     derive(
@@ -376,14 +403,21 @@ pub struct UpgradeClient {
     client_id: ClientIdAttribute,
     client_type: ClientTypeAttribute,
     consensus_height: ConsensusHeightAttribute,
+    previous_height: PreviousHeightAttribute,
 }
 
 impl UpgradeClient {
-    pub fn new(client_id: ClientId, client_type: ClientType, consensus_height: Height) -> Self {
+    pub fn new(
+        client_id: ClientId,
+        client_type: ClientType,
+        consensus_height: Height,
+        previous_height: Height,
+    ) -> Self {
         Self {
             client_id: ClientIdAttribute::from(client_id),
             client_type: ClientTypeAttribute::from(client_type),
             consensus_height: ConsensusHeightAttribute::from(consensus_height),
+            previous_height: PreviousHeightAttribute::from(previous_height),
         }
     }
 
@@ -399,6 +433,11 @@ impl UpgradeClient {
         &self.consensus_height.consensus_height
     }
 
+    /// Returns the latest height of the client before the upgrade.
+    pub fn previous_height(&self) -> &Height {
+        &self.previous_height.previous_height
+    }
+
     pub fn event_type(&self) -> &str {
         UPGRADE_CLIENT_EVENT
     }
@@ -412,6 +451,7 @@ impl From<UpgradeClient> for abci::Event {
                 u.client_id.into(),
                 u.client_type.into(),
                 u.consensus_height.into(),
+                u.previous_height.into(),
             ],
         }
     }
@@ -89,6 +89,37 @@ impl Height {
     pub fn decrement(&self) -> Result<Height, ClientError> {
         self.sub(1)
     }
+
+    /// Returns the number of blocks between `earlier` and this height.
+    ///
+    /// Returns `None` if the two heights belong to different revisions, or if
+    /// `earlier` is in fact greater than this height.
+    pub fn blocks_since(&self, earlier: &Height) -> Option<u64> {
+        if self.revision_number != earlier.revision_number {
+            return None;
+        }
+
+        self.revision_height.checked_sub(earlier.revision_height)
+    }
+
+    /// Iterates over the heights from `start` (inclusive) to `end` (exclusive).
+    ///
+    /// Heights are only iterated within a single revision, so nothing is
+    /// yielded when `start` and `end` belong to different revisions.
+    pub fn range(start: Height, end: Height) -> impl Iterator<Item = Height> {
+        let revision_number = start.revision_number;
+
+        let revision_heights = if start.revision_number == end.revision_number {
+            start.revision_height..end.revision_height
+        } else {
+            0..0
+        };
+
+        revision_heights.map(move |revision_height| Height {
+            revision_number,
+            revision_height,
+        })
+    }
 }
 
 impl PartialOrd for Height {
@@ -258,3 +289,45 @@ fn test_invalid_height() {
         })
     );
 }
+
+#[test]
+fn test_height_range() {
+    let heights: Vec<Height> =
+        Height::range(Height::min(0), Height::new(0, 5).expect("valid height")).collect();
+
+    assert_eq!(
+        heights,
+        (1..5)
+            .map(|h| Height::new(0, h).expect("valid height"))
+            .collect::<Vec<_>>()
+    );
+
+    let cross_revision = Height::range(
+        Height::new(0, 1).expect("valid height"),
+        Height::new(1, 5).expect("valid height"),
+    );
+
+    assert_eq!(cross_revision.count(), 0);
+}
+
+#[test]
+fn test_height_blocks_since() {
+    let height = Height::new(0, 10).expect("valid height");
+
+    assert_eq!(
+        height.blocks_since(&Height::new(0, 4).expect("valid height")),
+        Some(6)
+    );
+    assert_eq!(height.blocks_since(&height), Some(0));
+    assert_eq!(
+        height.blocks_since(&Height::new(0, 11).expect("valid height")),
+        None
+    );
+
+    assert_eq!(
+        Height::new(1, 10)
+            .expect("valid height")
+            .blocks_since(&Height::new(0, 4).expect("valid height")),
+        None
+    );
+}
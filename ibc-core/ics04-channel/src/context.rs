@@ -1,15 +1,22 @@
 //! ICS4 (channel) context.
 
+use core::time::Duration;
+
 use ibc_core_channel_types::channel::ChannelEnd;
 use ibc_core_channel_types::commitment::PacketCommitment;
+use ibc_core_channel_types::error::PacketError;
+use ibc_core_channel_types::packet::Packet;
+use ibc_core_channel_types::Version;
 use ibc_core_client::context::prelude::*;
+use ibc_core_client::types::Height;
 use ibc_core_connection::types::ConnectionEnd;
 use ibc_core_handler_types::error::ContextError;
 use ibc_core_handler_types::events::IbcEvent;
-use ibc_core_host::types::identifiers::{ConnectionId, Sequence};
+use ibc_core_host::types::identifiers::{ChannelId, ConnectionId, PortId, Sequence};
 use ibc_core_host::types::path::{ChannelEndPath, CommitmentPath, SeqSendPath};
 use ibc_core_host::{ExecutionContext, ValidationContext};
 use ibc_primitives::prelude::*;
+use ibc_primitives::{Signer, Timestamp};
 
 /// Methods required in send packet validation, to be implemented by the host
 pub trait SendPacketValidationContext {
@@ -26,6 +33,124 @@ pub trait SendPacketValidationContext {
 
     fn get_next_sequence_send(&self, seq_send_path: &SeqSendPath)
         -> Result<Sequence, ContextError>;
+
+    /// Validates the signer of a `MsgSendPacket`. Accepts any signer by
+    /// default.
+    fn validate_message_signer(&self, _signer: &Signer) -> Result<(), ContextError> {
+        Ok(())
+    }
+
+    /// Returns the sequence assigned to the next packet sent on the given
+    /// channel. It must not change between the validation and the execution
+    /// of a packet send. Defaults to the next send sequence counter.
+    ///
+    /// Returns a [`ContextError`] rather than a `PacketError`, so that the
+    /// errors of the store reads it is built upon are forwarded as is.
+    fn allocate_send_sequence(
+        &self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+    ) -> Result<Sequence, ContextError> {
+        self.get_next_sequence_send(&SeqSendPath::new(port_id, channel_id))
+    }
+
+    /// Returns the version negotiated for the given channel, if known, against
+    /// which the stored channel end version is checked. Defaults to `None`.
+    fn expected_channel_version(
+        &self,
+        _channel_end_path: &ChannelEndPath,
+    ) -> Result<Option<Version>, ContextError> {
+        Ok(None)
+    }
+
+    /// Returns the timeout timestamp of the packet committed at the given
+    /// path, if known, against which resubmitted packets are checked.
+    /// Defaults to `None`.
+    fn packet_commitment_timeout_timestamp(
+        &self,
+        _commitment_path: &CommitmentPath,
+    ) -> Result<Option<Timestamp>, ContextError> {
+        Ok(None)
+    }
+
+    /// Returns the out-of-band metadata of the packet committed at the given
+    /// path, if any. Defaults to `None`.
+    fn packet_metadata(
+        &self,
+        _commitment_path: &CommitmentPath,
+    ) -> Result<Option<Vec<u8>>, ContextError> {
+        Ok(None)
+    }
+
+    /// Validates the out-of-band metadata to attach to the packet committed
+    /// at the given path, before the packet is sent. Fails by default, since
+    /// no metadata is stored.
+    fn validate_packet_metadata(
+        &self,
+        _commitment_path: &CommitmentPath,
+        _metadata: &[u8],
+    ) -> Result<(), ContextError> {
+        Err(PacketError::Other {
+            description: "the host does not support packet metadata".to_string(),
+        }
+        .into())
+    }
+
+    /// Returns the packet commitment stored at the given path, if any. Used by
+    /// `send_packet` to detect duplicate sends, which is skipped by default.
+    fn packet_commitment(&self, _commitment_path: &CommitmentPath) -> Option<PacketCommitment> {
+        None
+    }
+
+    /// Validates the data of a packet sent on the given channel. Accepts any
+    /// data by default.
+    fn validate_packet_data(
+        &self,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+        _data: &[u8],
+    ) -> Result<(), PacketError> {
+        Ok(())
+    }
+
+    /// Checks that sending the given packet stays within the rate limit of its
+    /// channel. No limit applies by default.
+    fn check_rate_limit(
+        &self,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+        _packet: &Packet,
+    ) -> Result<(), PacketError> {
+        Ok(())
+    }
+
+    /// Returns the default timeouts of the packets sent on the given channel.
+    /// Defaults to none.
+    fn default_timeout(
+        &self,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+    ) -> (Option<Height>, Option<Timestamp>) {
+        (None, None)
+    }
+
+    /// Returns the minimum number of blocks and duration the timeout of a
+    /// packet must lie ahead of the counterparty. Defaults to none.
+    fn min_timeout_window(&self) -> (Option<u64>, Option<Duration>) {
+        (None, None)
+    }
+
+    /// Returns the domain separation tag prepended to the preimage of packet
+    /// commitments. Defaults to an empty tag.
+    fn commitment_domain_tag(&self) -> &[u8] {
+        &[]
+    }
+
+    /// Returns the version byte prepended to the stored packet commitments.
+    /// Defaults to 0, which prepends nothing.
+    fn commitment_version(&self) -> u8 {
+        0
+    }
 }
 
 impl<T> SendPacketValidationContext for T
@@ -52,6 +177,90 @@ where
     ) -> Result<Sequence, ContextError> {
         self.get_next_sequence_send(seq_send_path)
     }
+
+    fn validate_message_signer(&self, signer: &Signer) -> Result<(), ContextError> {
+        self.validate_message_signer(signer)
+    }
+
+    fn expected_channel_version(
+        &self,
+        channel_end_path: &ChannelEndPath,
+    ) -> Result<Option<Version>, ContextError> {
+        self.expected_channel_version(channel_end_path)
+    }
+
+    fn packet_commitment_timeout_timestamp(
+        &self,
+        commitment_path: &CommitmentPath,
+    ) -> Result<Option<Timestamp>, ContextError> {
+        self.packet_commitment_timeout_timestamp(commitment_path)
+    }
+
+    fn packet_metadata(
+        &self,
+        commitment_path: &CommitmentPath,
+    ) -> Result<Option<Vec<u8>>, ContextError> {
+        self.packet_metadata(commitment_path)
+    }
+
+    fn validate_packet_metadata(
+        &self,
+        commitment_path: &CommitmentPath,
+        metadata: &[u8],
+    ) -> Result<(), ContextError> {
+        self.validate_packet_metadata(commitment_path, metadata)
+    }
+
+    fn allocate_send_sequence(
+        &self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+    ) -> Result<Sequence, ContextError> {
+        self.allocate_send_sequence(port_id, channel_id)
+    }
+
+    /// Errors reading the commitment count as a missing one.
+    fn packet_commitment(&self, commitment_path: &CommitmentPath) -> Option<PacketCommitment> {
+        self.get_packet_commitment(commitment_path).ok()
+    }
+
+    fn validate_packet_data(
+        &self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        data: &[u8],
+    ) -> Result<(), PacketError> {
+        self.validate_packet_data(port_id, channel_id, data)
+    }
+
+    fn check_rate_limit(
+        &self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        packet: &Packet,
+    ) -> Result<(), PacketError> {
+        self.check_rate_limit(port_id, channel_id, packet)
+    }
+
+    fn default_timeout(
+        &self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+    ) -> (Option<Height>, Option<Timestamp>) {
+        self.default_timeout(port_id, channel_id)
+    }
+
+    fn min_timeout_window(&self) -> (Option<u64>, Option<Duration>) {
+        self.min_timeout_window()
+    }
+
+    fn commitment_domain_tag(&self) -> &[u8] {
+        self.commitment_domain_tag()
+    }
+
+    fn commitment_version(&self) -> u8 {
+        self.commitment_version()
+    }
 }
 
 /// Methods required in send packet execution, to be implemented by the host
@@ -68,11 +277,59 @@ pub trait SendPacketExecutionContext: SendPacketValidationContext {
         commitment: PacketCommitment,
     ) -> Result<(), ContextError>;
 
+    /// Stores the timeout timestamp of the packet committed at the given path.
+    /// Does nothing by default.
+    fn store_packet_commitment_timeout_timestamp(
+        &mut self,
+        _commitment_path: &CommitmentPath,
+        _timeout_timestamp: Timestamp,
+    ) -> Result<(), ContextError> {
+        Ok(())
+    }
+
+    /// Stores the out-of-band metadata of the packet committed at the given
+    /// path. Fails by default, since no metadata is stored.
+    fn store_packet_metadata(
+        &mut self,
+        _commitment_path: &CommitmentPath,
+        _metadata: Vec<u8>,
+    ) -> Result<(), ContextError> {
+        Err(PacketError::Other {
+            description: "the host does not support packet metadata".to_string(),
+        }
+        .into())
+    }
+
     /// Ibc events
     fn emit_ibc_event(&mut self, event: IbcEvent) -> Result<(), ContextError>;
 
     /// Logging facility
     fn log_message(&mut self, message: String) -> Result<(), ContextError>;
+
+    /// Application events emitted right after the core `SendPacket` event of
+    /// the given packet. Defaults to no events.
+    fn app_send_events(&self, _packet: &Packet) -> Vec<IbcEvent> {
+        Vec::new()
+    }
+
+    /// Called with the length of the packet data to charge a proportional
+    /// weight. Advisory only, does nothing by default.
+    fn charge_send_packet_weight(&mut self, _data_len: usize) {}
+
+    /// Called right after the commitment of a sent packet is stored, to count
+    /// it against the rate limit of its channel. Does nothing by default.
+    fn record_rate_limit(&mut self, _port_id: &PortId, _channel_id: &ChannelId, _packet: &Packet) {}
+
+    /// Called once the commitment of a sent packet is stored, with the exact
+    /// commitment bytes. Does nothing by default.
+    fn on_commitment_stored(
+        &self,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+        _sequence: Sequence,
+        _commitment: &[u8],
+    ) {
+    }
 }
 
 impl<T> SendPacketExecutionContext for T
@@ -95,6 +352,22 @@ where
         self.store_packet_commitment(commitment_path, commitment)
     }
 
+    fn store_packet_commitment_timeout_timestamp(
+        &mut self,
+        commitment_path: &CommitmentPath,
+        timeout_timestamp: Timestamp,
+    ) -> Result<(), ContextError> {
+        self.store_packet_commitment_timeout_timestamp(commitment_path, timeout_timestamp)
+    }
+
+    fn store_packet_metadata(
+        &mut self,
+        commitment_path: &CommitmentPath,
+        metadata: Vec<u8>,
+    ) -> Result<(), ContextError> {
+        self.store_packet_metadata(commitment_path, metadata)
+    }
+
     fn emit_ibc_event(&mut self, event: IbcEvent) -> Result<(), ContextError> {
         self.emit_ibc_event(event)
     }
@@ -102,4 +375,26 @@ where
     fn log_message(&mut self, message: String) -> Result<(), ContextError> {
         self.log_message(message)
     }
+
+    fn app_send_events(&self, packet: &Packet) -> Vec<IbcEvent> {
+        self.app_send_events(packet)
+    }
+
+    fn charge_send_packet_weight(&mut self, data_len: usize) {
+        self.charge_send_packet_weight(data_len)
+    }
+
+    fn record_rate_limit(&mut self, port_id: &PortId, channel_id: &ChannelId, packet: &Packet) {
+        self.record_rate_limit(port_id, channel_id, packet)
+    }
+
+    fn on_commitment_stored(
+        &self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        sequence: Sequence,
+        commitment: &[u8],
+    ) {
+        self.on_commitment_stored(port_id, channel_id, sequence, commitment)
+    }
 }
@@ -1,5 +1,7 @@
 use ibc_core_channel_types::channel::{Counterparty, Order, State as ChannelState};
-use ibc_core_channel_types::commitment::{compute_ack_commitment, compute_packet_commitment};
+use ibc_core_channel_types::commitment::{
+    compute_ack_commitment, compute_packet_commitment_with_domain_tag,
+};
 use ibc_core_channel_types::error::{ChannelError, PacketError};
 use ibc_core_channel_types::events::AcknowledgePacket;
 use ibc_core_channel_types::msgs::MsgAcknowledgement;
@@ -139,12 +141,16 @@ where
         return Ok(());
     };
 
+    // The commitment was stored by this host, hence under its own domain tag
+    // and version.
     if commitment_on_a
-        != compute_packet_commitment(
+        != compute_packet_commitment_with_domain_tag(
+            ctx_a.commitment_domain_tag(),
             &packet.data,
             &packet.timeout_height_on_b,
             &packet.timeout_timestamp_on_b,
         )
+        .with_version(ctx_a.commitment_version())
     {
         return Err(PacketError::IncorrectPacketCommitment {
             sequence: packet.seq_on_a,
@@ -1,5 +1,7 @@
 use ibc_core_channel_types::channel::{Counterparty, Order, State as ChannelState};
-use ibc_core_channel_types::commitment::{compute_ack_commitment, compute_packet_commitment};
+use ibc_core_channel_types::commitment::{
+    compute_ack_commitment, compute_packet_commitment_with_domain_tag,
+};
 use ibc_core_channel_types::error::{ChannelError, PacketError};
 use ibc_core_channel_types::events::{ReceivePacket, WriteAcknowledgement};
 use ibc_core_channel_types::msgs::MsgRecvPacket;
@@ -196,11 +198,15 @@ where
         let consensus_state_of_a_on_b =
             client_val_ctx_b.consensus_state(&client_cons_state_path_on_b)?;
 
-        let expected_commitment_on_a = compute_packet_commitment(
+        // The commitment was stored by the counterparty, hence under its own
+        // domain tag and version.
+        let expected_commitment_on_a = compute_packet_commitment_with_domain_tag(
+            ctx_b.counterparty_commitment_domain_tag(conn_id_on_b),
             &msg.packet.data,
             &msg.packet.timeout_height_on_b,
             &msg.packet.timeout_timestamp_on_b,
-        );
+        )
+        .with_version(ctx_b.counterparty_commitment_version(conn_id_on_b));
         let commitment_path_on_a = CommitmentPath::new(
             &msg.packet.port_id_on_a,
             &msg.packet.chan_id_on_a,
@@ -1,37 +1,533 @@
-use ibc_core_channel_types::channel::Counterparty;
-use ibc_core_channel_types::commitment::compute_packet_commitment;
-use ibc_core_channel_types::error::PacketError;
+use core::time::Duration;
+
+use ibc_core_channel_types::channel::{Counterparty, State};
+#[cfg(feature = "std")]
+use ibc_core_channel_types::commitment::compute_packet_commitment_from_reader;
+use ibc_core_channel_types::commitment::{
+    compute_packet_commitment_with_domain_tag, PacketCommitment,
+};
+use ibc_core_channel_types::error::{ChannelError, PacketError};
 use ibc_core_channel_types::events::SendPacket;
+use ibc_core_channel_types::msgs::MsgSendPacket;
 use ibc_core_channel_types::packet::Packet;
+use ibc_core_channel_types::timeout::{TimeoutHeight, TimeoutReason};
 use ibc_core_client::context::prelude::*;
+use ibc_core_client::types::error::ClientError;
+use ibc_core_client::types::Height;
+use ibc_core_connection::types::error::ConnectionError;
 use ibc_core_handler_types::error::ContextError;
 use ibc_core_handler_types::events::{IbcEvent, MessageEvent};
+use ibc_core_host::types::identifiers::{ChannelId, PortId, Sequence};
 use ibc_core_host::types::path::{
     ChannelEndPath, ClientConsensusStatePath, CommitmentPath, SeqSendPath,
 };
+use ibc_core_router::router::Router;
 use ibc_primitives::prelude::*;
-use ibc_primitives::Expiry;
+use ibc_primitives::{Expiry, Timestamp};
 
 use crate::context::{SendPacketExecutionContext, SendPacketValidationContext};
 
 /// Send the given packet, including all necessary validation.
 ///
-/// Equivalent to calling [`send_packet_validate`], followed by [`send_packet_execute`]
+/// The timeouts left unset on the packet are first filled in with the
+/// defaults of its channel, see [`apply_default_timeouts`]. Then equivalent to
+/// calling [`send_packet_validate`], followed by [`send_packet_execute`]
+///
+/// The rate limit of the channel is checked at the end of the validation, and
+/// the packet is recorded against it right after its commitment is stored.
+///
+/// Sending a packet again, e.g. by a relayer resubmitting it, is idempotent:
+/// if the packet passes the checks on the state of its channel, connection and
+/// client, and the commitment stored at its sequence matches it, it succeeds
+/// without storing anything nor emitting any event.
+///
+/// On success, returns the packet as sent along with its stored commitment,
+/// i.e. the same [`SendPacketResult`] as [`send_packet_dry_run`] would have.
 pub fn send_packet(
     ctx_a: &mut impl SendPacketExecutionContext,
     packet: Packet,
-) -> Result<(), ContextError> {
-    send_packet_validate(ctx_a, &packet)?;
-    send_packet_execute(ctx_a, packet)
+) -> Result<SendPacketResult, ContextError> {
+    let result = validate_send(ctx_a, packet, |packet| Ok(packet_commitment(ctx_a, packet)))?;
+
+    if !result.duplicate {
+        let data_len = result.packet.data.len();
+
+        for event in store_packet(
+            ctx_a,
+            result.packet.clone(),
+            result.commitment.clone(),
+            data_len,
+        )? {
+            ctx_a.emit_ibc_event(event)?;
+        }
+    }
+
+    Ok(result)
+}
+
+/// Send the given packet like [`send_packet`], then attach the given
+/// out-of-band metadata to it, e.g. the originating module or a memo, for
+/// later correlation.
+///
+/// The metadata is stored alongside the packet commitment, under the
+/// commitment path of the packet, and can be read back with
+/// [`packet_metadata`]. It is not part of the commitment, hence not relayed
+/// to the counterparty. A duplicate send stores no metadata, leaving the one
+/// of the original send in place.
+///
+/// The metadata is validated before the packet is sent, so that nothing is
+/// committed if the host does not support packet metadata or rejects the
+/// given one, see [`SendPacketValidationContext::validate_packet_metadata`].
+pub fn send_packet_with_metadata(
+    ctx_a: &mut impl SendPacketExecutionContext,
+    packet: Packet,
+    metadata: Vec<u8>,
+) -> Result<SendPacketResult, ContextError> {
+    let commitment_path_on_a =
+        CommitmentPath::new(&packet.port_id_on_a, &packet.chan_id_on_a, packet.seq_on_a);
+
+    ctx_a.validate_packet_metadata(&commitment_path_on_a, &metadata)?;
+
+    let result = send_packet(ctx_a, packet)?;
+
+    if !result.duplicate {
+        ctx_a.store_packet_metadata(&commitment_path_on_a, metadata)?;
+    }
+
+    Ok(result)
+}
+
+/// Send the given packet like [`send_packet`], after checking that its source
+/// port is bound to a module of the given router.
+///
+/// Without this check, a packet sent on a port no module owns is committed
+/// all the same, and only fails once its acknowledgement or timeout cannot be
+/// routed back. Fails with [`PacketError::PortNotBound`] instead.
+pub fn send_packet_with_router(
+    ctx_a: &mut impl SendPacketExecutionContext,
+    router: &impl Router,
+    packet: Packet,
+) -> Result<SendPacketResult, ContextError> {
+    if router.lookup_module(&packet.port_id_on_a).is_none() {
+        return Err(PacketError::PortNotBound {
+            port_id: packet.port_id_on_a,
+        }
+        .into());
+    }
+
+    send_packet(ctx_a, packet)
+}
+
+/// Returns the out-of-band metadata attached to the packet sent with the
+/// given sequence on the given channel by [`send_packet_with_metadata`], if
+/// any.
+pub fn packet_metadata(
+    ctx_a: &impl SendPacketValidationContext,
+    port_id: &PortId,
+    channel_id: &ChannelId,
+    sequence: Sequence,
+) -> Result<Option<Vec<u8>>, ContextError> {
+    ctx_a.packet_metadata(&CommitmentPath::new(port_id, channel_id, sequence))
+}
+
+/// Send a packet whose data is read from the given reader, e.g. to commit to
+/// a large payload as it is produced, without a second pass over it.
+///
+/// The commitment is computed as the data is read, in a single pass, instead
+/// of hashing the data once it is fully in memory. The data read replaces the
+/// one carried by `packet_header`, and the packet is then sent like with
+/// [`send_packet`]: it is validated, recorded against the rate limit and
+/// reported by the emitted events with the data read. Only the commitment is
+/// stored, as for any other packet.
+///
+/// The handler itself puts no limit on the size of the data: the reader is
+/// read until its end. However, the counterparty only accepts the packet if
+/// the very same data is submitted to it, which is bound by its own message
+/// size limits. Callers should hence cap the reader, e.g. with
+/// [`Read::take`](std::io::Read::take), at the size the counterparty accepts.
+///
+/// Fails with [`PacketError::DataRead`] if the reader fails, before anything
+/// is validated or stored.
+#[cfg(feature = "std")]
+pub fn send_packet_streaming(
+    ctx_a: &mut impl SendPacketExecutionContext,
+    packet_header: Packet,
+    data_reader: impl std::io::Read,
+) -> Result<SendPacketResult, ContextError> {
+    let result = validate_send(ctx_a, packet_header, |packet| {
+        packet.data = Vec::new();
+
+        let (commitment, _) = compute_packet_commitment_from_reader(
+            ctx_a.commitment_domain_tag(),
+            CopyingReader {
+                inner: data_reader,
+                copy: &mut packet.data,
+            },
+            &packet.timeout_height_on_b,
+            &packet.timeout_timestamp_on_b,
+        )
+        .map_err(|e| PacketError::DataRead {
+            description: e.to_string(),
+        })?;
+
+        Ok(commitment.with_version(ctx_a.commitment_version()))
+    })?;
+
+    if !result.duplicate {
+        let data_len = result.packet.data.len();
+
+        for event in store_packet(
+            ctx_a,
+            result.packet.clone(),
+            result.commitment.clone(),
+            data_len,
+        )? {
+            ctx_a.emit_ibc_event(event)?;
+        }
+    }
+
+    Ok(result)
+}
+
+/// Reader copying the bytes read from the inner reader into the given buffer.
+#[cfg(feature = "std")]
+struct CopyingReader<'a, R> {
+    inner: R,
+    copy: &'a mut Vec<u8>,
+}
+
+#[cfg(feature = "std")]
+impl<R: std::io::Read> std::io::Read for CopyingReader<'_, R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        self.copy.extend_from_slice(&buf[..read]);
+
+        Ok(read)
+    }
+}
+
+/// Send the packet of the given message on behalf of its signer.
+///
+/// The signer is validated first, so that a message with an invalid signer is
+/// rejected before anything is committed. Then equivalent to calling
+/// [`send_packet`] with the packet of the message.
+pub fn send_packet_msg(
+    ctx_a: &mut impl SendPacketExecutionContext,
+    msg: MsgSendPacket,
+) -> Result<SendPacketResult, ContextError> {
+    ctx_a.validate_message_signer(&msg.signer)?;
+
+    send_packet(ctx_a, msg.packet)
+}
+
+/// A stable, machine-readable code classifying why [`send_packet`] rejected
+/// a packet, e.g. for relayer automation to act upon without parsing errors.
+///
+/// The numeric value of each code, see [`PacketRejectionCode::code`], is part
+/// of the API: existing codes are never renumbered.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum PacketRejectionCode {
+    /// Any failure not covered by another code, e.g. a failing store read, or
+    /// a packet data or rate limit check defined by the host.
+    Other = 0,
+    /// The channel the packet is sent on does not exist.
+    ChannelNotFound = 1,
+    /// The channel the packet is sent on is closed.
+    ChannelClosed = 2,
+    /// The destination of the packet is not the counterparty of its channel.
+    CounterpartyMismatch = 3,
+    /// The channel version differs from the one negotiated.
+    ChannelVersionMismatch = 4,
+    /// The connection underlying the channel is not open.
+    ConnectionNotOpen = 5,
+    /// The client of the counterparty is frozen, expired or otherwise inactive.
+    ClientInactive = 6,
+    /// Neither a timeout height nor a timeout timestamp is set.
+    TimeoutUnset = 7,
+    /// A timeout has already passed on the counterparty.
+    TimeoutPassed = 8,
+    /// A timeout lies closer than the minimum timeout window.
+    TimeoutTooSoon = 9,
+    /// A resubmitted packet shortens the timeout of the committed one.
+    DecreasingTimeout = 10,
+    /// The sequence of the packet is not the next send sequence.
+    SequenceGap = 11,
+    /// The send sequence of the channel is exhausted.
+    SequenceOverflow = 12,
+}
+
+impl PacketRejectionCode {
+    /// Returns the numeric value of the code.
+    pub fn code(self) -> u32 {
+        self as u32
+    }
+
+    /// Classifies an error returned by [`send_packet`].
+    pub fn from_error(error: &ContextError) -> Self {
+        match error {
+            ContextError::ChannelError(e) | ContextError::PacketError(PacketError::Channel(e)) => {
+                Self::from_channel_error(e)
+            }
+            ContextError::ClientError(ClientError::ClientNotActive { .. }) => Self::ClientInactive,
+            ContextError::PacketError(e) => Self::from_packet_error(e),
+            _ => Self::Other,
+        }
+    }
+
+    fn from_channel_error(error: &ChannelError) -> Self {
+        match error {
+            ChannelError::ChannelNotFound { .. } | ChannelError::MissingChannel => {
+                Self::ChannelNotFound
+            }
+            ChannelError::ChannelClosed => Self::ChannelClosed,
+            ChannelError::InvalidCounterparty { .. } => Self::CounterpartyMismatch,
+            _ => Self::Other,
+        }
+    }
+
+    fn from_packet_error(error: &PacketError) -> Self {
+        match error {
+            PacketError::ChannelNotFound { .. } => Self::ChannelNotFound,
+            PacketError::InvalidChannelState {
+                state: State::Closed,
+                ..
+            } => Self::ChannelClosed,
+            PacketError::ChannelVersionMismatch { .. } => Self::ChannelVersionMismatch,
+            PacketError::ConnectionNotOpen { .. } => Self::ConnectionNotOpen,
+            PacketError::SendTimeout { reason } => match reason {
+                TimeoutReason::BothUnset => Self::TimeoutUnset,
+                TimeoutReason::HeightAlreadyPassed { .. }
+                | TimeoutReason::TimestampAlreadyPassed { .. } => Self::TimeoutPassed,
+                TimeoutReason::HeightTooSoon { .. } | TimeoutReason::TimestampTooSoon { .. } => {
+                    Self::TimeoutTooSoon
+                }
+            },
+            PacketError::DecreasingTimeoutTimestamp { .. } => Self::DecreasingTimeout,
+            PacketError::InvalidPacketSequence { .. } => Self::SequenceGap,
+            PacketError::SequenceOverflow { .. } => Self::SequenceOverflow,
+            _ => Self::Other,
+        }
+    }
+}
+
+/// Send the given packet like [`send_packet`], returning the
+/// [`PacketRejectionCode`] of the failure alongside its error.
+pub fn send_packet_with_rejection_code(
+    ctx_a: &mut impl SendPacketExecutionContext,
+    packet: Packet,
+) -> Result<SendPacketResult, (PacketRejectionCode, ContextError)> {
+    send_packet(ctx_a, packet).map_err(|e| (PacketRejectionCode::from_error(&e), e))
+}
+
+/// The outcome of a successful [`send_packet`] or [`send_packet_dry_run`].
+///
+/// The sequence, port and channel IDs and timeouts the packet is committed
+/// under are the ones of [`SendPacketResult::packet`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SendPacketResult {
+    /// The packet as sent, with the default timeouts of its channel filled in.
+    pub packet: Packet,
+    /// The commitment stored for the packet, or that would be stored by a
+    /// dry run.
+    pub commitment: PacketCommitment,
+    /// Whether the packet was already sent, in which case sending it again
+    /// neither stores anything nor emits any event.
+    pub duplicate: bool,
+    /// The number of blocks left until the timeout height of the packet,
+    /// counted from the latest height of the counterparty client.
+    ///
+    /// `None` if the timeout height is unset or lies in another revision.
+    pub blocks_until_height_timeout: Option<u64>,
+    /// The time left until the timeout timestamp of the packet, counted from
+    /// the timestamp of the latest consensus state of the counterparty client.
+    ///
+    /// `None` if the timeout timestamp is unset or has already passed.
+    pub duration_until_timestamp_timeout: Option<Duration>,
+}
+
+/// Checks whether [`send_packet`] would succeed for the given packet, without
+/// mutating the context.
+///
+/// Runs the same steps as [`send_packet`] up to the execution: the default
+/// timeouts are applied, the packet is validated and a duplicate send is
+/// detected. On success, returns the commitment that would be stored, which
+/// is neither stored nor reported by any event, along with how far the
+/// timeouts of the packet lie ahead of the counterparty.
+pub fn send_packet_dry_run(
+    ctx_a: &impl SendPacketValidationContext,
+    packet: Packet,
+) -> Result<SendPacketResult, PacketError> {
+    validate_send(ctx_a, packet, |packet| Ok(packet_commitment(ctx_a, packet)))
+        .map_err(into_packet_error)
+}
+
+/// Converts an error of the send path into the [`PacketError`] returned by
+/// [`send_packet_dry_run`] and [`send_packet_deferred`].
+///
+/// Client errors, e.g. an inactive client, are reported as errors of the
+/// connection the client underlies.
+fn into_packet_error(error: ContextError) -> PacketError {
+    match error {
+        ContextError::PacketError(e) => e,
+        ContextError::ChannelError(e) => PacketError::Channel(e),
+        ContextError::ConnectionError(e) => PacketError::Connection(e),
+        ContextError::ClientError(e) => PacketError::Connection(ConnectionError::Client(e)),
+        ContextError::RouterError(e) => PacketError::Other {
+            description: e.to_string(),
+        },
+    }
+}
+
+/// Applies the default timeouts to the given packet and computes its
+/// commitment with `commit`. Then validates the packet against the state of
+/// its channel, connection and client, and checks whether its commitment is
+/// already stored at its sequence. Only a packet which is not such a duplicate
+/// goes through the checks specific to a new send, see
+/// [`send_packet_validate`].
+///
+/// Shared by all the ways to send a packet, so that the default timeouts are
+/// applied in a single place, and a duplicate send never bypasses the checks
+/// on the state, e.g. of a channel closed since.
+fn validate_send(
+    ctx_a: &impl SendPacketValidationContext,
+    packet: Packet,
+    commit: impl FnOnce(&mut Packet) -> Result<PacketCommitment, ContextError>,
+) -> Result<SendPacketResult, ContextError> {
+    let mut packet = apply_default_timeouts(ctx_a, packet);
+    let commitment = commit(&mut packet)?;
+
+    let (latest_height_on_a, latest_timestamp) = validate_send_state(ctx_a, &packet)?;
+
+    let commitment_path_on_a =
+        CommitmentPath::new(&packet.port_id_on_a, &packet.chan_id_on_a, packet.seq_on_a);
+    let duplicate = ctx_a
+        .packet_commitment(&commitment_path_on_a)
+        .is_some_and(|stored_commitment| stored_commitment == commitment);
+
+    if !duplicate {
+        validate_new_send(ctx_a, &packet)?;
+    }
+
+    let (blocks_until_height_timeout, duration_until_timestamp_timeout) =
+        timeout_budget(&packet, latest_height_on_a, &latest_timestamp);
+
+    Ok(SendPacketResult {
+        packet,
+        commitment,
+        duplicate,
+        blocks_until_height_timeout,
+        duration_until_timestamp_timeout,
+    })
+}
+
+/// Returns how far the timeouts of the given packet lie ahead of the given
+/// latest height and timestamp of the counterparty, as known by its client.
+fn timeout_budget(
+    packet: &Packet,
+    latest_height_on_a: Height,
+    latest_timestamp: &Timestamp,
+) -> (Option<u64>, Option<Duration>) {
+    let blocks = match packet.timeout_height_on_b {
+        TimeoutHeight::At(timeout_height)
+            if timeout_height.revision_number() == latest_height_on_a.revision_number() =>
+        {
+            Some(
+                timeout_height
+                    .revision_height()
+                    .saturating_sub(latest_height_on_a.revision_height()),
+            )
+        }
+        _ => None,
+    };
+
+    let duration = packet
+        .timeout_timestamp_on_b
+        .duration_since(latest_timestamp);
+
+    (blocks, duration)
+}
+
+/// Returns true if the commitment stored at the sequence of the given packet
+/// is identical to the one of the packet, i.e. the packet was already sent.
+pub fn is_duplicate_send(ctx_a: &impl SendPacketValidationContext, packet: &Packet) -> bool {
+    let commitment_path_on_a =
+        CommitmentPath::new(&packet.port_id_on_a, &packet.chan_id_on_a, packet.seq_on_a);
+
+    ctx_a
+        .packet_commitment(&commitment_path_on_a)
+        .is_some_and(|stored_commitment| stored_commitment == packet_commitment(ctx_a, packet))
+}
+
+/// Computes the commitment of the given packet, under the domain tag and
+/// commitment version of the context.
+fn packet_commitment(
+    ctx_a: &impl SendPacketValidationContext,
+    packet: &Packet,
+) -> PacketCommitment {
+    compute_packet_commitment_with_domain_tag(
+        ctx_a.commitment_domain_tag(),
+        &packet.data,
+        &packet.timeout_height_on_b,
+        &packet.timeout_timestamp_on_b,
+    )
+    .with_version(ctx_a.commitment_version())
+}
+
+/// Fills in the timeout height and timestamp left unset on the given packet
+/// with the defaults of its channel, as returned by the context.
+///
+/// The timeouts set on the packet take precedence and are never overridden.
+pub fn apply_default_timeouts(
+    ctx_a: &impl SendPacketValidationContext,
+    mut packet: Packet,
+) -> Packet {
+    let (default_height, default_timestamp) =
+        ctx_a.default_timeout(&packet.port_id_on_a, &packet.chan_id_on_a);
+
+    if let (false, Some(height)) = (packet.timeout_height_on_b.is_set(), default_height) {
+        packet.timeout_height_on_b = TimeoutHeight::At(height);
+    }
+
+    if let (false, Some(timestamp)) = (packet.timeout_timestamp_on_b.is_set(), default_timestamp) {
+        packet.timeout_timestamp_on_b = timestamp;
+    }
+
+    packet
 }
 
 /// Validate that sending the given packet would succeed.
+///
+/// A packet carrying both a timeout height and a timeout timestamp times out
+/// on the counterparty as soon as the earliest of the two passes. Hence both
+/// must still lie in the future when sending it: the packet is rejected if
+/// either one has already passed.
+///
+/// Each set timeout must also lie at least as far ahead of the counterparty as
+/// required by [`SendPacketValidationContext::min_timeout_window`].
 pub fn send_packet_validate(
     ctx_a: &impl SendPacketValidationContext,
     packet: &Packet,
 ) -> Result<(), ContextError> {
+    validate_send_state(ctx_a, packet)?;
+    validate_new_send(ctx_a, packet)
+}
+
+/// Validates the timeouts of the given packet against the state of its
+/// channel, connection and counterparty client, i.e. the checks a duplicate
+/// send must pass too.
+///
+/// Returns the latest height and timestamp of the counterparty the timeouts
+/// were checked against.
+fn validate_send_state(
+    ctx_a: &impl SendPacketValidationContext,
+    packet: &Packet,
+) -> Result<(Height, Timestamp), ContextError> {
     if !packet.timeout_height_on_b.is_set() && !packet.timeout_timestamp_on_b.is_set() {
-        return Err(ContextError::PacketError(PacketError::MissingTimeout));
+        return Err(PacketError::SendTimeout {
+            reason: TimeoutReason::BothUnset,
+        }
+        .into());
     }
 
     let chan_end_path_on_a = ChannelEndPath::new(&packet.port_id_on_a, &packet.chan_id_on_a);
@@ -48,10 +544,29 @@ pub fn send_packet_validate(
 
     chan_end_on_a.verify_counterparty_matches(&counterparty)?;
 
+    if let Some(expected_version) = ctx_a.expected_channel_version(&chan_end_path_on_a)? {
+        if chan_end_on_a.version() != &expected_version {
+            return Err(PacketError::ChannelVersionMismatch {
+                channel_id: packet.chan_id_on_a.clone(),
+                expected: expected_version,
+                actual: chan_end_on_a.version().clone(),
+            }
+            .into());
+        }
+    }
+
     let conn_id_on_a = &chan_end_on_a.connection_hops()[0];
 
     let conn_end_on_a = ctx_a.connection_end(conn_id_on_a)?;
 
+    // The channel may still be open while its underlying connection is not.
+    if !conn_end_on_a.is_open() {
+        return Err(PacketError::ConnectionNotOpen {
+            connection_id: conn_id_on_a.clone(),
+        }
+        .into());
+    }
+
     let client_id_on_a = conn_end_on_a.client_id();
 
     let client_val_ctx_a = ctx_a.get_client_validation_context();
@@ -64,14 +579,35 @@ pub fn send_packet_validate(
 
     let latest_height_on_a = client_state_of_b_on_a.latest_height();
 
+    // Each set timeout is checked on its own, as the earliest one wins.
     if packet.timeout_height_on_b.has_expired(latest_height_on_a) {
-        return Err(PacketError::LowPacketHeight {
-            chain_height: latest_height_on_a,
-            timeout_height: packet.timeout_height_on_b,
+        return Err(PacketError::SendTimeout {
+            reason: TimeoutReason::HeightAlreadyPassed {
+                chain_height: latest_height_on_a,
+                timeout_height: packet.timeout_height_on_b,
+            },
         }
         .into());
     }
 
+    let (min_blocks, min_duration) = ctx_a.min_timeout_window();
+
+    if let (TimeoutHeight::At(timeout_height), Some(min_blocks)) =
+        (packet.timeout_height_on_b, min_blocks)
+    {
+        let min_timeout_height = latest_height_on_a.add(min_blocks);
+
+        if timeout_height < min_timeout_height {
+            return Err(PacketError::SendTimeout {
+                reason: TimeoutReason::HeightTooSoon {
+                    min_timeout_height,
+                    timeout_height,
+                },
+            }
+            .into());
+        }
+    }
+
     let client_cons_state_path_on_a = ClientConsensusStatePath::new(
         client_id_on_a.clone(),
         latest_height_on_a.revision_number(),
@@ -82,11 +618,60 @@ pub fn send_packet_validate(
     let latest_timestamp = consensus_state_of_b_on_a.timestamp();
     let packet_timestamp = packet.timeout_timestamp_on_b;
     if let Expiry::Expired = latest_timestamp.check_expiry(&packet_timestamp) {
-        return Err(PacketError::LowPacketTimestamp.into());
+        return Err(PacketError::SendTimeout {
+            reason: TimeoutReason::TimestampAlreadyPassed {
+                chain_timestamp: latest_timestamp,
+                timeout_timestamp: packet_timestamp,
+            },
+        }
+        .into());
     }
 
-    let seq_send_path_on_a = SeqSendPath::new(&packet.port_id_on_a, &packet.chan_id_on_a);
-    let next_seq_send_on_a = ctx_a.get_next_sequence_send(&seq_send_path_on_a)?;
+    if let (true, Some(min_duration)) = (packet_timestamp.is_set(), min_duration) {
+        let min_timeout_timestamp =
+            (latest_timestamp + min_duration).map_err(ConnectionError::TimestampOverflow)?;
+
+        if packet_timestamp < min_timeout_timestamp {
+            return Err(PacketError::SendTimeout {
+                reason: TimeoutReason::TimestampTooSoon {
+                    min_timeout_timestamp,
+                    timeout_timestamp: packet_timestamp,
+                },
+            }
+            .into());
+        }
+    }
+
+    // A packet resubmitted at an already committed sequence must not shorten
+    // its timeout. An unset timeout timestamp never expires.
+    let commitment_path_on_a =
+        CommitmentPath::new(&packet.port_id_on_a, &packet.chan_id_on_a, packet.seq_on_a);
+    if let Some(committed_timestamp) =
+        ctx_a.packet_commitment_timeout_timestamp(&commitment_path_on_a)?
+    {
+        if packet_timestamp.is_set()
+            && (!committed_timestamp.is_set() || packet_timestamp < committed_timestamp)
+        {
+            return Err(PacketError::DecreasingTimeoutTimestamp {
+                sequence: packet.seq_on_a,
+                committed: committed_timestamp,
+                given: packet_timestamp,
+            }
+            .into());
+        }
+    }
+
+    Ok((latest_height_on_a, latest_timestamp))
+}
+
+/// Validates the sequence, data and rate limit of the given packet, i.e. the
+/// checks only a packet not sent yet must pass.
+fn validate_new_send(
+    ctx_a: &impl SendPacketValidationContext,
+    packet: &Packet,
+) -> Result<(), ContextError> {
+    let next_seq_send_on_a =
+        ctx_a.allocate_send_sequence(&packet.port_id_on_a, &packet.chan_id_on_a)?;
 
     if packet.seq_on_a != next_seq_send_on_a {
         return Err(PacketError::InvalidPacketSequence {
@@ -96,47 +681,137 @@ pub fn send_packet_validate(
         .into());
     }
 
+    if next_seq_send_on_a.checked_increment().is_none() {
+        return Err(PacketError::SequenceOverflow {
+            channel_id: packet.chan_id_on_a.clone(),
+        }
+        .into());
+    }
+
+    ctx_a.validate_packet_data(&packet.port_id_on_a, &packet.chan_id_on_a, &packet.data)?;
+
+    // The rate limit is checked last, so that only otherwise valid packets
+    // are rejected for exceeding it.
+    ctx_a.check_rate_limit(&packet.port_id_on_a, &packet.chan_id_on_a, packet)?;
+
     Ok(())
 }
 
 /// Send the packet without any validation.
 ///
 /// A prior call to [`send_packet_validate`] MUST have succeeded.
+///
+/// Events are emitted in the following order: the channel `Message` event, the
+/// `SendPacket` event, then any events returned by
+/// [`SendPacketExecutionContext::app_send_events`].
 pub fn send_packet_execute(
     ctx_a: &mut impl SendPacketExecutionContext,
     packet: Packet,
 ) -> Result<(), ContextError> {
+    for event in commit_packet(ctx_a, packet)? {
+        ctx_a.emit_ibc_event(event)?;
+    }
+
+    Ok(())
+}
+
+/// Send the given packet like [`send_packet`], but return the events instead
+/// of emitting them, e.g. for the caller to emit the events of many packets
+/// in a batch.
+///
+/// The state is committed as usual, while the events of the context are left
+/// untouched. The events are returned in the order documented in
+/// [`send_packet_execute`]. A duplicate send returns no events.
+pub fn send_packet_deferred(
+    ctx_a: &mut impl SendPacketExecutionContext,
+    packet: Packet,
+) -> Result<Vec<IbcEvent>, PacketError> {
+    let result = validate_send(ctx_a, packet, |packet| Ok(packet_commitment(ctx_a, packet)))
+        .map_err(into_packet_error)?;
+
+    if result.duplicate {
+        return Ok(Vec::new());
+    }
+
+    let data_len = result.packet.data.len();
+
+    store_packet(ctx_a, result.packet, result.commitment, data_len).map_err(into_packet_error)
+}
+
+/// Stores the sequence and commitment of the given packet, then returns the
+/// events to emit for it.
+fn commit_packet(
+    ctx_a: &mut impl SendPacketExecutionContext,
+    packet: Packet,
+) -> Result<Vec<IbcEvent>, ContextError> {
+    let commitment = packet_commitment(ctx_a, &packet);
+    let data_len = packet.data.len();
+
+    store_packet(ctx_a, packet, commitment, data_len)
+}
+
+/// Stores the sequence of the given packet along with its precomputed
+/// commitment, charging `data_len` as the size of its data, then returns the
+/// events to emit for it.
+///
+/// The sequence of the packet must be the one allocated to it, as checked by
+/// the validation of a new send: the send sequence is advanced past it.
+fn store_packet(
+    ctx_a: &mut impl SendPacketExecutionContext,
+    packet: Packet,
+    commitment: PacketCommitment,
+    data_len: usize,
+) -> Result<Vec<IbcEvent>, ContextError> {
+    ctx_a.charge_send_packet_weight(data_len);
+
     {
         let seq_send_path_on_a = SeqSendPath::new(&packet.port_id_on_a, &packet.chan_id_on_a);
-        let next_seq_send_on_a = ctx_a.get_next_sequence_send(&seq_send_path_on_a)?;
+        let incremented_seq_send_on_a =
+            packet
+                .seq_on_a
+                .checked_increment()
+                .ok_or_else(|| PacketError::SequenceOverflow {
+                    channel_id: packet.chan_id_on_a.clone(),
+                })?;
 
-        ctx_a.store_next_sequence_send(&seq_send_path_on_a, next_seq_send_on_a.increment())?;
+        ctx_a.store_next_sequence_send(&seq_send_path_on_a, incremented_seq_send_on_a)?;
     }
 
-    ctx_a.store_packet_commitment(
-        &CommitmentPath::new(&packet.port_id_on_a, &packet.chan_id_on_a, packet.seq_on_a),
-        compute_packet_commitment(
-            &packet.data,
-            &packet.timeout_height_on_b,
-            &packet.timeout_timestamp_on_b,
-        ),
-    )?;
-
-    // emit events and logs
     {
-        let chan_end_path_on_a = ChannelEndPath::new(&packet.port_id_on_a, &packet.chan_id_on_a);
-        let chan_end_on_a = ctx_a.channel_end(&chan_end_path_on_a)?;
-        let conn_id_on_a = &chan_end_on_a.connection_hops()[0];
-
-        ctx_a.log_message("success: packet send".to_string())?;
-        let event = IbcEvent::SendPacket(SendPacket::new(
-            packet,
-            chan_end_on_a.ordering,
-            conn_id_on_a.clone(),
-        ));
-        ctx_a.emit_ibc_event(IbcEvent::Message(MessageEvent::Channel))?;
-        ctx_a.emit_ibc_event(event)?;
+        let commitment_path =
+            CommitmentPath::new(&packet.port_id_on_a, &packet.chan_id_on_a, packet.seq_on_a);
+
+        ctx_a.store_packet_commitment(&commitment_path, commitment.clone())?;
+        ctx_a.store_packet_commitment_timeout_timestamp(
+            &commitment_path,
+            packet.timeout_timestamp_on_b,
+        )?;
+
+        ctx_a.record_rate_limit(&packet.port_id_on_a, &packet.chan_id_on_a, &packet);
+
+        ctx_a.on_commitment_stored(
+            &packet.port_id_on_a,
+            &packet.chan_id_on_a,
+            packet.seq_on_a,
+            commitment.as_ref(),
+        );
     }
 
-    Ok(())
+    // log and collect events
+    let chan_end_path_on_a = ChannelEndPath::new(&packet.port_id_on_a, &packet.chan_id_on_a);
+    let chan_end_on_a = ctx_a.channel_end(&chan_end_path_on_a)?;
+    let conn_id_on_a = &chan_end_on_a.connection_hops()[0];
+
+    ctx_a.log_message("success: packet send".to_string())?;
+    let app_events = ctx_a.app_send_events(&packet);
+    let event = IbcEvent::SendPacket(SendPacket::new(
+        packet,
+        chan_end_on_a.ordering,
+        conn_id_on_a.clone(),
+    ));
+
+    let mut events = vec![IbcEvent::Message(MessageEvent::Channel), event];
+    events.extend(app_events);
+
+    Ok(events)
 }
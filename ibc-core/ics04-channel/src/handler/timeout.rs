@@ -1,5 +1,5 @@
 use ibc_core_channel_types::channel::{Counterparty, Order, State};
-use ibc_core_channel_types::commitment::compute_packet_commitment;
+use ibc_core_channel_types::commitment::compute_packet_commitment_with_domain_tag;
 use ibc_core_channel_types::error::{ChannelError, PacketError};
 use ibc_core_channel_types::events::{ChannelClosed, TimeoutPacket};
 use ibc_core_channel_types::msgs::{MsgTimeout, MsgTimeoutOnClose};
@@ -163,11 +163,15 @@ where
         return Ok(());
     };
 
-    let expected_commitment_on_a = compute_packet_commitment(
+    // The commitment was stored by this host, hence under its own domain tag
+    // and version.
+    let expected_commitment_on_a = compute_packet_commitment_with_domain_tag(
+        ctx_a.commitment_domain_tag(),
         &msg.packet.data,
         &msg.packet.timeout_height_on_b,
         &msg.packet.timeout_timestamp_on_b,
-    );
+    )
+    .with_version(ctx_a.commitment_version());
     if commitment_on_a != expected_commitment_on_a {
         return Err(PacketError::IncorrectPacketCommitment {
             sequence: msg.packet.seq_on_a,
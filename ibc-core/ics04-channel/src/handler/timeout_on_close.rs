@@ -1,5 +1,5 @@
 use ibc_core_channel_types::channel::{ChannelEnd, Counterparty, Order, State};
-use ibc_core_channel_types::commitment::compute_packet_commitment;
+use ibc_core_channel_types::commitment::compute_packet_commitment_with_domain_tag;
 use ibc_core_channel_types::error::{ChannelError, PacketError};
 use ibc_core_channel_types::msgs::MsgTimeoutOnClose;
 use ibc_core_client::context::prelude::*;
@@ -44,11 +44,15 @@ where
         return Ok(());
     };
 
-    let expected_commitment_on_a = compute_packet_commitment(
+    // The commitment was stored by this host, hence under its own domain tag
+    // and version.
+    let expected_commitment_on_a = compute_packet_commitment_with_domain_tag(
+        ctx_a.commitment_domain_tag(),
         &packet.data,
         &packet.timeout_height_on_b,
         &packet.timeout_timestamp_on_b,
-    );
+    )
+    .with_version(ctx_a.commitment_version());
     if commitment_on_a != expected_commitment_on_a {
         return Err(PacketError::IncorrectPacketCommitment {
             sequence: packet.seq_on_a,
@@ -280,10 +280,7 @@ impl ChannelEnd {
     /// Checks if the state of this channel end is not closed.
     pub fn verify_not_closed(&self) -> Result<(), ChannelError> {
         if self.state.eq(&State::Closed) {
-            return Err(ChannelError::InvalidState {
-                expected: "Channel state cannot be Closed".to_string(),
-                actual: self.state.to_string(),
-            });
+            return Err(ChannelError::ChannelClosed);
         }
         Ok(())
     }
@@ -4,6 +4,7 @@ use ibc_primitives::prelude::*;
 use ibc_primitives::Timestamp;
 
 use super::acknowledgement::Acknowledgement;
+use crate::packet::Packet;
 use crate::timeout::TimeoutHeight;
 
 /// Packet commitment
@@ -28,6 +29,17 @@ impl PacketCommitment {
     pub fn into_vec(self) -> Vec<u8> {
         self.0
     }
+
+    /// Prepends the given version byte to the commitment.
+    ///
+    /// Version 0 denotes the unversioned ICS-04 format, for which the
+    /// commitment is returned unchanged.
+    pub fn with_version(mut self, version: u8) -> Self {
+        if version != 0 {
+            self.0.insert(0, version);
+        }
+        self
+    }
 }
 
 impl AsRef<[u8]> for PacketCommitment {
@@ -89,14 +101,108 @@ pub fn compute_packet_commitment(
     timeout_height: &TimeoutHeight,
     timeout_timestamp: &Timestamp,
 ) -> PacketCommitment {
-    let mut hash_input = [0; 8 * 3 + 32];
+    compute_packet_commitment_with_domain_tag(&[], packet_data, timeout_height, timeout_timestamp)
+}
 
-    hash_input[..8].copy_from_slice(&timeout_timestamp.nanoseconds().to_be_bytes());
-    hash_input[8..16].copy_from_slice(&timeout_height.commitment_revision_number().to_be_bytes());
-    hash_input[16..24].copy_from_slice(&timeout_height.commitment_revision_height().to_be_bytes());
-    hash_input[24..].copy_from_slice(&hash(packet_data));
+/// Compute the commitment for the given packet, i.e. the hash of its timeout
+/// timestamp, timeout height and data hash laid out as specified by ICS-04.
+///
+/// This is the commitment stored by `send_packet` for hosts using neither a
+/// domain separation tag nor a commitment version, and depends on nothing
+/// but the packet, e.g. to cross-check other implementations.
+pub fn compute_commitment_for_packet(packet: &Packet) -> PacketCommitment {
+    compute_packet_commitment(
+        &packet.data,
+        &packet.timeout_height_on_b,
+        &packet.timeout_timestamp_on_b,
+    )
+}
 
-    hash(&hash_input).to_vec().into()
+/// Compute the commitment for a packet, prepending the given domain
+/// separation tag to the hashed preimage.
+///
+/// An empty tag yields the same commitment as [`compute_packet_commitment`].
+/// Any other tag produces commitments that counterparties hashing without
+/// the very same tag cannot verify.
+pub fn compute_packet_commitment_with_domain_tag(
+    domain_tag: &[u8],
+    packet_data: &[u8],
+    timeout_height: &TimeoutHeight,
+    timeout_timestamp: &Timestamp,
+) -> PacketCommitment {
+    commitment_from_data_hash(
+        domain_tag,
+        hash(packet_data),
+        timeout_height,
+        timeout_timestamp,
+    )
+}
+
+/// Compute the commitment for a packet like
+/// [`compute_packet_commitment_with_domain_tag`], reading its data from the
+/// given reader until the end instead of from memory.
+///
+/// The data is hashed chunk by chunk as it is read, so that memory use does
+/// not depend on its size. Returns the commitment along with the number of
+/// bytes read, or the first error raised by the reader.
+#[cfg(feature = "std")]
+pub fn compute_packet_commitment_from_reader(
+    domain_tag: &[u8],
+    mut packet_data: impl std::io::Read,
+    timeout_height: &TimeoutHeight,
+    timeout_timestamp: &Timestamp,
+) -> std::io::Result<(PacketCommitment, u64)> {
+    use sha2::Digest;
+
+    let mut hasher = sha2::Sha256::new();
+    let data_len = std::io::copy(&mut packet_data, &mut hasher)?;
+
+    let commitment = commitment_from_data_hash(
+        domain_tag,
+        hasher.finalize().into(),
+        timeout_height,
+        timeout_timestamp,
+    );
+
+    Ok((commitment, data_len))
+}
+
+fn commitment_from_data_hash(
+    domain_tag: &[u8],
+    data_hash: [u8; 32],
+    timeout_height: &TimeoutHeight,
+    timeout_timestamp: &Timestamp,
+) -> PacketCommitment {
+    use sha2::Digest;
+
+    let mut hasher = sha2::Sha256::new();
+
+    hasher.update(domain_tag);
+    hasher.update(timeout_timestamp.nanoseconds().to_be_bytes());
+    hasher.update(timeout_height.commitment_revision_number().to_be_bytes());
+    hasher.update(timeout_height.commitment_revision_height().to_be_bytes());
+    hasher.update(data_hash);
+
+    hasher.finalize().to_vec().into()
+}
+
+/// Returns the size in bytes of the commitment that `send_packet` would store
+/// under the given commitment version, e.g. to estimate storage costs before
+/// sending a packet.
+///
+/// Packet commitments are SHA-256 digests, prefixed by the version byte for
+/// any version but 0, see [`PacketCommitment::with_version`]. Their size
+/// depends neither on the packet contents nor on the domain tag.
+pub fn estimate_packet_commitment_size(commitment_version: u8) -> usize {
+    use sha2::Digest;
+
+    let digest_size = sha2::Sha256::output_size();
+
+    if commitment_version == 0 {
+        digest_size
+    } else {
+        digest_size + 1
+    }
 }
 
 /// Compute the commitment for an acknowledgement.
@@ -133,6 +239,57 @@ mod test {
         assert_eq!(&expected[..], actual.as_ref());
     }
 
+    #[test]
+    fn test_compute_commitment_for_packet() {
+        use ibc_core_host_types::identifiers::{ChannelId, PortId};
+
+        // preimage: timeout timestamp, timeout revision number and height,
+        // each as 8 big-endian bytes, followed by the SHA-256 of the data
+        let expected: [u8; 32] = [
+            0xa9, 0x28, 0xb5, 0x1f, 0x62, 0xbd, 0x54, 0x00, 0x91, 0xec, 0x45, 0x1f, 0x4e, 0xf3,
+            0x45, 0x79, 0x4f, 0x05, 0x9e, 0x65, 0x91, 0x08, 0x16, 0x86, 0x61, 0x26, 0xdc, 0x36,
+            0x4f, 0x84, 0xcc, 0x15,
+        ];
+        let packet = Packet {
+            seq_on_a: 1.into(),
+            port_id_on_a: PortId::transfer(),
+            chan_id_on_a: ChannelId::new(0),
+            port_id_on_b: PortId::transfer(),
+            chan_id_on_b: ChannelId::new(1),
+            data: b"packet data".to_vec(),
+            timeout_height_on_b: TimeoutHeight::At(
+                ibc_core_client_types::Height::new(42, 24).unwrap(),
+            ),
+            timeout_timestamp_on_b: Timestamp::from_nanoseconds(0x42).unwrap(),
+        };
+
+        assert_eq!(
+            &expected[..],
+            compute_commitment_for_packet(&packet).as_ref()
+        );
+    }
+
+    #[test]
+    fn test_estimate_packet_commitment_size() {
+        for version in [0, 1] {
+            let commitment = compute_packet_commitment_with_domain_tag(
+                b"domain",
+                b"packet data",
+                &TimeoutHeight::Never,
+                &Timestamp::from_nanoseconds(0x42).unwrap(),
+            )
+            .with_version(version);
+
+            assert_eq!(
+                estimate_packet_commitment_size(version),
+                commitment.as_ref().len()
+            );
+        }
+
+        assert_eq!(estimate_packet_commitment_size(0), 32);
+        assert_eq!(estimate_packet_commitment_size(1), 33);
+    }
+
     #[test]
     fn test_compute_ack_commitment() {
         let expected: [u8; 32] = [
@@ -9,7 +9,7 @@ use ibc_primitives::prelude::*;
 use ibc_primitives::{ParseTimestampError, Timestamp};
 
 use super::channel::Counterparty;
-use super::timeout::TimeoutHeight;
+use super::timeout::{TimeoutHeight, TimeoutReason};
 use crate::channel::State;
 use crate::Version;
 
@@ -21,6 +21,8 @@ pub enum ChannelError {
     InvalidChannelId { expected: String, actual: String },
     /// invalid channel state: expected `{expected}`, actual `{actual}`
     InvalidState { expected: String, actual: String },
+    /// channel is closed
+    ChannelClosed,
     /// invalid channel order type: expected `{expected}`, actual `{actual}`
     InvalidOrderType { expected: String, actual: String },
     /// invalid connection hops length: expected `{expected}`; actual `{actual}`
@@ -88,6 +90,14 @@ pub enum PacketError {
     },
     /// Receiving chain block timestamp >= packet timeout timestamp
     LowPacketTimestamp,
+    /// packet cannot be sent: `{reason}`
+    SendTimeout { reason: TimeoutReason },
+    /// Packet `{sequence}` resubmitted with timeout timestamp `{given}` earlier than the committed `{committed}`
+    DecreasingTimeoutTimestamp {
+        sequence: Sequence,
+        committed: Timestamp,
+        given: Timestamp,
+    },
     /// Invalid packet sequence `{given_sequence}` ≠ next send sequence `{next_sequence}`
     InvalidPacketSequence {
         given_sequence: Sequence,
@@ -95,6 +105,12 @@ pub enum PacketError {
     },
     /// Channel `{channel_id}` should not be state `{state}`
     InvalidChannelState { channel_id: ChannelId, state: State },
+    /// Channel `{channel_id}` version `{actual}` differs from the negotiated version `{expected}`
+    ChannelVersionMismatch {
+        channel_id: ChannelId,
+        expected: Version,
+        actual: Version,
+    },
     /// the associated connection `{connection_id}` is not OPEN
     ConnectionNotOpen { connection_id: ConnectionId },
     /// Receipt for the packet `{sequence}` not found
@@ -132,10 +148,14 @@ pub enum PacketError {
     AppModule { description: String },
     /// route not found
     RouteNotFound,
+    /// port `{port_id}` is not bound to any module
+    PortNotBound { port_id: PortId },
     /// packet sequence cannot be 0
     ZeroPacketSequence,
     /// packet data bytes cannot be empty
     ZeroPacketData,
+    /// failed to read packet data: `{description}`
+    DataRead { description: String },
     /// invalid timeout height for the packet
     InvalidTimeoutHeight,
     /// Invalid packet timeout timestamp value error: `{0}`
@@ -156,6 +176,8 @@ pub enum PacketError {
     },
     /// Commitment for the packet `{sequence}` not found
     PacketCommitmentNotFound { sequence: Sequence },
+    /// Send sequence overflow on channel `{channel_id}`
+    SequenceOverflow { channel_id: ChannelId },
     /// Missing sequence number for receiving packets on port `{port_id}` and channel `{channel_id}`
     MissingNextRecvSeq {
         port_id: PortId,
@@ -641,6 +641,12 @@ impl SendPacket {
         &self.seq_attr_on_a.sequence
     }
 
+    /// Returns the sequence of the sent packet, same as [`Self::seq_on_a`]
+    /// but by value, e.g. for assertions.
+    pub fn packet_sequence(&self) -> Sequence {
+        self.seq_attr_on_a.sequence
+    }
+
     pub fn port_id_on_a(&self) -> &PortId {
         &self.port_id_attr_on_a.src_port_id
     }
@@ -1264,4 +1270,35 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn send_packet_accessors() {
+        let packet = Packet {
+            seq_on_a: 1.into(),
+            port_id_on_a: PortId::transfer(),
+            chan_id_on_a: ChannelId::zero(),
+            port_id_on_b: PortId::transfer(),
+            chan_id_on_b: ChannelId::new(1),
+            data: vec![1, 2, 3],
+            timeout_height_on_b: TimeoutHeight::no_timeout(),
+            timeout_timestamp_on_b: Timestamp::from_nanoseconds(10).unwrap(),
+        };
+
+        let event = SendPacket::new(packet.clone(), Order::Unordered, ConnectionId::zero());
+
+        assert_eq!(event.packet_sequence(), Sequence::from(1));
+        assert_eq!(event.seq_on_a(), &packet.seq_on_a);
+        assert_eq!(event.port_id_on_a(), &packet.port_id_on_a);
+        assert_eq!(event.chan_id_on_a(), &packet.chan_id_on_a);
+        assert_eq!(event.port_id_on_b(), &packet.port_id_on_b);
+        assert_eq!(event.chan_id_on_b(), &packet.chan_id_on_b);
+        assert_eq!(event.packet_data(), packet.data.as_slice());
+        assert_eq!(event.timeout_height_on_b(), &packet.timeout_height_on_b);
+        assert_eq!(
+            event.timeout_timestamp_on_b(),
+            &packet.timeout_timestamp_on_b
+        );
+        assert_eq!(event.channel_ordering(), &Order::Unordered);
+        assert_eq!(event.conn_id_on_a(), &ConnectionId::zero());
+    }
 }
@@ -9,6 +9,7 @@ mod chan_open_confirm;
 mod chan_open_init;
 mod chan_open_try;
 mod recv_packet;
+mod send_packet;
 mod timeout;
 mod timeout_on_close;
 
@@ -25,6 +26,7 @@ pub use chan_open_try::*;
 use ibc_core_host_types::identifiers::*;
 use ibc_primitives::prelude::*;
 pub use recv_packet::*;
+pub use send_packet::*;
 pub use timeout::*;
 pub use timeout_on_close::*;
 
@@ -0,0 +1,29 @@
+use ibc_primitives::Signer;
+
+use crate::packet::Packet;
+
+///
+/// Message definition for sending a packet on behalf of a signer.
+///
+/// Unlike the other channel messages, it has no protobuf counterpart: packets
+/// are sent by the modules of the host chain rather than submitted by
+/// relayers, hence this message is never decoded from a transaction.
+///
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MsgSendPacket {
+    /// The packet to be sent
+    pub packet: Packet,
+    /// The signer of the message
+    pub signer: Signer,
+}
+
+impl MsgSendPacket {
+    pub fn new(packet: Packet, signer: Signer) -> Self {
+        Self { packet, signer }
+    }
+}
@@ -5,6 +5,7 @@ use core::fmt::{Display, Error as FmtError, Formatter};
 use ibc_core_client_types::error::ClientError;
 use ibc_core_client_types::Height;
 use ibc_primitives::prelude::*;
+use ibc_primitives::Timestamp;
 use ibc_proto::ibc::core::client::v1::Height as RawHeight;
 
 /// Indicates a consensus height on the destination chain after which the packet
@@ -142,6 +143,33 @@ impl Display for TimeoutHeight {
     }
 }
 
+/// The reason a packet is considered timed out before it is even sent.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, displaydoc::Display)]
+pub enum TimeoutReason {
+    /// timeout height `{timeout_height}` has already passed on the counterparty chain at height `{chain_height}`
+    HeightAlreadyPassed {
+        chain_height: Height,
+        timeout_height: TimeoutHeight,
+    },
+    /// timeout timestamp `{timeout_timestamp}` has already passed on the counterparty chain at `{chain_timestamp}`
+    TimestampAlreadyPassed {
+        chain_timestamp: Timestamp,
+        timeout_timestamp: Timestamp,
+    },
+    /// neither the timeout height nor the timeout timestamp is set
+    BothUnset,
+    /// timeout height `{timeout_height}` is earlier than the minimum `{min_timeout_height}` required by the host
+    HeightTooSoon {
+        min_timeout_height: Height,
+        timeout_height: Height,
+    },
+    /// timeout timestamp `{timeout_timestamp}` is earlier than the minimum `{min_timeout_timestamp}` required by the host
+    TimestampTooSoon {
+        min_timeout_timestamp: Timestamp,
+        timeout_timestamp: Timestamp,
+    },
+}
+
 #[cfg(feature = "serde")]
 mod tests {
     use serde::{Deserialize, Serialize};
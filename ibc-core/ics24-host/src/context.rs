@@ -2,7 +2,9 @@ use core::time::Duration;
 
 use ibc_core_channel_types::channel::ChannelEnd;
 use ibc_core_channel_types::commitment::{AcknowledgementCommitment, PacketCommitment};
-use ibc_core_channel_types::packet::Receipt;
+use ibc_core_channel_types::error::PacketError;
+use ibc_core_channel_types::packet::{Packet, Receipt};
+use ibc_core_channel_types::Version as ChannelVersion;
 use ibc_core_client_context::prelude::*;
 use ibc_core_client_types::Height;
 use ibc_core_commitment_types::commitment::CommitmentPrefix;
@@ -10,7 +12,7 @@ use ibc_core_connection_types::version::{pick_version, Version as ConnectionVers
 use ibc_core_connection_types::ConnectionEnd;
 use ibc_core_handler_types::error::ContextError;
 use ibc_core_handler_types::events::IbcEvent;
-use ibc_core_host_types::identifiers::{ConnectionId, Sequence};
+use ibc_core_host_types::identifiers::{ChannelId, ConnectionId, PortId, Sequence};
 use ibc_core_host_types::path::{
     AckPath, ChannelEndPath, ClientConnectionPath, CommitmentPath, ConnectionPath, ReceiptPath,
     SeqAckPath, SeqRecvPath, SeqSendPath,
@@ -95,10 +97,194 @@ pub trait ValidationContext {
     /// Returns the `ChannelEnd` for the given `port_id` and `chan_id`.
     fn channel_end(&self, channel_end_path: &ChannelEndPath) -> Result<ChannelEnd, ContextError>;
 
+    /// Returns the version negotiated during the handshake of the given
+    /// channel, if the host keeps track of it.
+    ///
+    /// When `Some`, `send_packet` asserts that the stored channel end still
+    /// carries this version, which catches store corruption or faulty channel
+    /// upgrades early. Returns `None` by default, which skips the check.
+    fn expected_channel_version(
+        &self,
+        _channel_end_path: &ChannelEndPath,
+    ) -> Result<Option<ChannelVersion>, ContextError> {
+        Ok(None)
+    }
+
+    /// Returns the timeout timestamp of the packet committed at the given
+    /// path, if the host keeps track of it.
+    ///
+    /// Packet commitments only store a hash, so `send_packet` relies on this
+    /// to reject a packet resubmitted at the same sequence with an earlier
+    /// timeout. Returns `None` by default, which skips the check.
+    fn packet_commitment_timeout_timestamp(
+        &self,
+        _commitment_path: &CommitmentPath,
+    ) -> Result<Option<Timestamp>, ContextError> {
+        Ok(None)
+    }
+
+    /// Returns the out-of-band metadata attached to the packet committed at
+    /// the given path by `send_packet_with_metadata`, if any.
+    ///
+    /// The metadata is keyed by the commitment path of the packet, i.e.
+    /// `commitments/ports/{port_id}/channels/{channel_id}/sequences/{sequence}`,
+    /// and is not part of the commitment itself: the counterparty never sees
+    /// it. Returns `None` by default.
+    fn packet_metadata(
+        &self,
+        _commitment_path: &CommitmentPath,
+    ) -> Result<Option<Vec<u8>>, ContextError> {
+        Ok(None)
+    }
+
+    /// Validates the out-of-band metadata to attach to the packet committed
+    /// at the given path, before the packet is sent.
+    ///
+    /// Once this succeeds, [`ExecutionContext::store_packet_metadata`] is
+    /// expected to succeed too, so that a packet is never committed without
+    /// its metadata. Fails by default, since the host stores no metadata.
+    fn validate_packet_metadata(
+        &self,
+        _commitment_path: &CommitmentPath,
+        _metadata: &[u8],
+    ) -> Result<(), ContextError> {
+        Err(PacketError::Other {
+            description: "the host does not support packet metadata".to_string(),
+        }
+        .into())
+    }
+
+    /// Validates the data of a packet sent on the given channel, e.g. to check
+    /// that it decodes in the format expected by the application.
+    ///
+    /// `send_packet` calls it before storing the packet commitment, so that a
+    /// malformed packet is rejected before being sent. Accepts any data by
+    /// default.
+    fn validate_packet_data(
+        &self,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+        _data: &[u8],
+    ) -> Result<(), PacketError> {
+        Ok(())
+    }
+
+    /// Checks that sending the given packet on the given channel stays within
+    /// the rate limit of the channel, e.g. to cap the transferred volume.
+    ///
+    /// `send_packet` calls it last during validation, once the packet is
+    /// otherwise known to be valid. The packet is then only counted against
+    /// the limit once its commitment is stored, see
+    /// [`ExecutionContext::record_rate_limit`]. No limit applies by default.
+    fn check_rate_limit(
+        &self,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+        _packet: &Packet,
+    ) -> Result<(), PacketError> {
+        Ok(())
+    }
+
+    /// Returns the default timeout height and timestamp of the packets sent on
+    /// the given channel, e.g. to apply channel-level timeouts.
+    ///
+    /// `send_packet` fills in the timeouts left unset on a packet with these
+    /// defaults before validating it. The timeouts set on the packet always
+    /// take precedence. Returns no default by default.
+    fn default_timeout(
+        &self,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+    ) -> (Option<Height>, Option<Timestamp>) {
+        (None, None)
+    }
+
+    /// Returns the minimum number of blocks and the minimum duration a packet
+    /// timeout must lie ahead of the latest height and timestamp of the
+    /// counterparty, as known by its client, e.g. to prevent packets from
+    /// timing out right after being sent.
+    ///
+    /// `send_packet` rejects the packets whose timeout height or timestamp is
+    /// set to less than that. An unset timeout is never too soon. No minimum
+    /// applies by default.
+    fn min_timeout_window(&self) -> (Option<u64>, Option<Duration>) {
+        (None, None)
+    }
+
+    /// Returns the domain separation tag prepended to the preimage of packet
+    /// commitments before hashing. Empty by default, as mandated by ICS-04.
+    ///
+    /// Changing it breaks compatibility: commitments stored before the change
+    /// no longer match, and counterparties must hash with the same tag to
+    /// verify the packets of the host, see
+    /// [`Self::counterparty_commitment_domain_tag`].
+    fn commitment_domain_tag(&self) -> &[u8] {
+        &[]
+    }
+
+    /// Returns the domain separation tag used by the counterparty at the other
+    /// end of the given connection for its packet commitments, i.e. its own
+    /// [`Self::commitment_domain_tag`].
+    ///
+    /// `recv_packet` rebuilds the commitment of a received packet under this
+    /// tag to verify it against the commitment proven on the counterparty.
+    /// Empty by default, as mandated by ICS-04.
+    fn counterparty_commitment_domain_tag(&self, _connection_id: &ConnectionId) -> &[u8] {
+        &[]
+    }
+
+    /// Returns the version byte prepended to the stored packet commitments,
+    /// see [`PacketCommitment::with_version`]. Defaults to 0, the unversioned
+    /// ICS-04 format, which prepends nothing.
+    ///
+    /// The version lets a chain tag commitments with their format, so that a
+    /// future format can be told apart from the current one. Bumping it does
+    /// not rewrite the commitments already stored: packets sent before the
+    /// bump can only be acknowledged or timed out once the host reports the
+    /// version they were stored with again, hence in-flight packets should be
+    /// drained beforehand. Counterparties must also expect the same version
+    /// when verifying the packets of the host, see
+    /// [`Self::counterparty_commitment_version`].
+    fn commitment_version(&self) -> u8 {
+        0
+    }
+
+    /// Returns the version byte prepended by the counterparty at the other end
+    /// of the given connection to its packet commitments, i.e. its own
+    /// [`Self::commitment_version`].
+    ///
+    /// `recv_packet` rebuilds the commitment of a received packet with this
+    /// version to verify it against the commitment proven on the
+    /// counterparty. Defaults to 0, the unversioned ICS-04 format.
+    fn counterparty_commitment_version(&self, _connection_id: &ConnectionId) -> u8 {
+        0
+    }
+
     /// Returns the sequence number for the next packet to be sent for the given store path
     fn get_next_sequence_send(&self, seq_send_path: &SeqSendPath)
         -> Result<Sequence, ContextError>;
 
+    /// Returns the sequence that `send_packet` assigns to the next packet sent
+    /// on the given channel, for hosts with custom sequence numbering, e.g.
+    /// sharded sequences.
+    ///
+    /// It is called when validating a packet send, which only accepts a packet
+    /// carrying the returned sequence. Once the packet is sent, the send
+    /// sequence counter is set right past it. Defaults to the counter read by
+    /// [`Self::get_next_sequence_send`].
+    ///
+    /// Returns a [`ContextError`] rather than a `PacketError`, like the other
+    /// sequence reads of this context, so that an override can forward the
+    /// errors of [`Self::get_next_sequence_send`] or of any other store read
+    /// as is.
+    fn allocate_send_sequence(
+        &self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+    ) -> Result<Sequence, ContextError> {
+        self.get_next_sequence_send(&SeqSendPath::new(port_id, channel_id))
+    }
+
     /// Returns the sequence number for the next packet to be received for the given store path
     fn get_next_sequence_recv(&self, seq_recv_path: &SeqRecvPath)
         -> Result<Sequence, ContextError>;
@@ -184,6 +370,36 @@ pub trait ExecutionContext: ValidationContext {
         commitment_path: &CommitmentPath,
     ) -> Result<(), ContextError>;
 
+    /// Stores the out-of-band metadata of the packet committed at the given
+    /// path, as read back by [`ValidationContext::packet_metadata`].
+    ///
+    /// Hosts are expected to keep the metadata at least as long as the packet
+    /// commitment, e.g. for correlating the acknowledgement of the packet with
+    /// its origin. Fails by default, since the host stores no metadata.
+    fn store_packet_metadata(
+        &mut self,
+        _commitment_path: &CommitmentPath,
+        _metadata: Vec<u8>,
+    ) -> Result<(), ContextError> {
+        Err(PacketError::Other {
+            description: "the host does not support packet metadata".to_string(),
+        }
+        .into())
+    }
+
+    /// Stores the timeout timestamp of the packet committed at the given path,
+    /// as read back by [`ValidationContext::packet_commitment_timeout_timestamp`].
+    ///
+    /// Called by `send_packet` right after the commitment is stored. Does
+    /// nothing by default, for hosts which do not keep track of timeouts.
+    fn store_packet_commitment_timeout_timestamp(
+        &mut self,
+        _commitment_path: &CommitmentPath,
+        _timeout_timestamp: Timestamp,
+    ) -> Result<(), ContextError> {
+        Ok(())
+    }
+
     /// Stores the given packet receipt at the given store path
     fn store_packet_receipt(
         &mut self,
@@ -238,6 +454,42 @@ pub trait ExecutionContext: ValidationContext {
 
     /// Log the given message.
     fn log_message(&mut self, message: String) -> Result<(), ContextError>;
+
+    /// Returns the application events to emit along with the sending of the
+    /// given packet.
+    ///
+    /// `send_packet` emits them right after its core `SendPacket` event, within
+    /// the same handler call. Returns no events by default.
+    fn app_send_events(&self, _packet: &Packet) -> Vec<IbcEvent> {
+        Vec::new()
+    }
+
+    /// Called by `send_packet` with the length of the packet data, so that the
+    /// host can charge a proportional gas or weight, e.g. on Substrate-based
+    /// chains. Does nothing by default.
+    ///
+    /// This is advisory only: the handler does not enforce any limit itself.
+    fn charge_send_packet_weight(&mut self, _data_len: usize) {}
+
+    /// Called by `send_packet` right after the commitment of a packet is
+    /// stored, to count the packet against the rate limit of its channel as
+    /// checked by [`ValidationContext::check_rate_limit`]. Does nothing by
+    /// default.
+    ///
+    /// Duplicate sends, which store nothing, are not recorded.
+    fn record_rate_limit(&mut self, _port_id: &PortId, _channel_id: &ChannelId, _packet: &Packet) {}
+
+    /// Called by `send_packet` once the commitment of a packet is stored, with
+    /// the exact commitment bytes, e.g. for keeping an audit trail. Does
+    /// nothing by default.
+    fn on_commitment_stored(
+        &self,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+        _sequence: Sequence,
+        _commitment: &[u8],
+    ) {
+    }
 }
 
 /// Convenient type alias for `ClientStateRef`, providing access to client
@@ -49,6 +49,11 @@ impl Sequence {
         Sequence(self.0 + 1)
     }
 
+    /// Increments the sequence number by one, returning `None` on overflow.
+    pub fn checked_increment(&self) -> Option<Sequence> {
+        self.0.checked_add(1).map(Sequence)
+    }
+
     /// Encodes the sequence number into a byte array in big endian.
     pub fn to_vec(&self) -> Vec<u8> {
         self.0.to_be_bytes().to_vec()
@@ -140,7 +140,7 @@ where
             ClientMsg::Misbehaviour(msg) => {
                 update_client::execute(ctx, MsgUpdateOrMisbehaviour::Misbehaviour(msg))
             }
-            ClientMsg::UpgradeClient(msg) => upgrade_client::execute(ctx, msg),
+            ClientMsg::UpgradeClient(msg) => upgrade_client::execute(ctx, msg).map(|_| ()),
             ClientMsg::RecoverClient(_msg) => {
                 // Recover client messages are not dispatched by ibc-rs as they can only be
                 // authorized via a passing governance proposal
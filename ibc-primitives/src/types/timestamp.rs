@@ -230,6 +230,8 @@ impl Display for Timestamp {
 pub enum TimestampOverflowError {
     /// Timestamp overflow when modifying with duration
     TimestampOverflow,
+    /// Timestamp underflow below the Unix epoch when subtracting a duration
+    TimestampUnderflow,
 }
 
 #[cfg(feature = "std")]
@@ -247,15 +249,26 @@ impl Add<Duration> for Timestamp {
     }
 }
 
+/// Subtracting a duration from a timestamp fails if the result would be
+/// earlier than the Unix epoch, which the IBC protocol cannot represent. As
+/// with addition, a timestamp that is not set stays unset.
 impl Sub<Duration> for Timestamp {
     type Output = Result<Self, TimestampOverflowError>;
 
     fn sub(self, duration: Duration) -> Result<Self, TimestampOverflowError> {
         self.time
-            .map(|time| time - duration)
+            .map(|time| {
+                let time =
+                    (time - duration).map_err(|_| TimestampOverflowError::TimestampOverflow)?;
+
+                if time < Time::unix_epoch() {
+                    return Err(TimestampOverflowError::TimestampUnderflow);
+                }
+
+                Ok(time)
+            })
             .transpose()
             .map(|time| Self { time })
-            .map_err(|_| TimestampOverflowError::TimestampOverflow)
     }
 }
 
@@ -356,6 +369,25 @@ mod tests {
         assert_eq!(time0, (time0 - duration).unwrap());
     }
 
+    #[test]
+    fn test_timestamp_sub_underflow() {
+        let time = Timestamp::from_nanoseconds(100).unwrap();
+
+        assert_eq!(
+            (time - Duration::from_nanos(60)).unwrap(),
+            Timestamp::from_nanoseconds(40).unwrap()
+        );
+        assert_eq!((time - Duration::from_nanos(100)).unwrap().nanoseconds(), 0);
+        assert!(matches!(
+            time - Duration::from_nanos(101),
+            Err(TimestampOverflowError::TimestampUnderflow)
+        ));
+
+        let now = Timestamp::now();
+        let past = (now - Duration::from_secs(10)).unwrap();
+        assert_eq!(now.duration_since(&past), Some(Duration::from_secs(10)));
+    }
+
     #[test]
     fn subtract_compare() {
         let sleep_duration = Duration::from_micros(100);
@@ -1,34 +1,58 @@
 use core::fmt::Debug;
+use core::sync::atomic::Ordering;
 use core::time::Duration;
 
-use basecoin_store::context::ProvableStore;
+use basecoin_store::context::{ProvableStore, Store};
 use basecoin_store::impls::InMemoryStore;
-use ibc::core::channel::types::channel::ChannelEnd;
-use ibc::core::channel::types::commitment::PacketCommitment;
+use basecoin_store::types::Height as StoreHeight;
+use ibc::core::channel::handler::{send_packet, SendPacketResult};
+use ibc::core::channel::types::channel::{ChannelEnd, IdentifiedChannelEnd};
+use ibc::core::channel::types::commitment::{AcknowledgementCommitment, PacketCommitment};
+use ibc::core::channel::types::events::SendPacket;
+use ibc::core::channel::types::packet::{Packet, Receipt};
+use ibc::core::channel::types::timeout::TimeoutHeight;
+use ibc::core::channel::types::Version as ChannelVersion;
 use ibc::core::client::context::client_state::ClientStateValidation;
-use ibc::core::client::context::{ClientExecutionContext, ClientValidationContext};
-use ibc::core::client::types::Height;
-use ibc::core::connection::types::ConnectionEnd;
+use ibc::core::client::context::consensus_state::ConsensusState;
+use ibc::core::client::context::{
+    ClientExecutionContext, ClientValidationContext, ExtClientValidationContext,
+};
+use ibc::core::client::types::error::ClientError;
+use ibc::core::client::types::msgs::{ClientMsg, MsgUpdateClient};
+use ibc::core::client::types::{Height, Status};
+use ibc::core::commitment_types::commitment::CommitmentProofBytes;
+use ibc::core::connection::types::{ConnectionEnd, IdentifiedConnectionEnd};
 use ibc::core::entrypoint::{dispatch, execute, validate};
 use ibc::core::handler::types::error::ContextError;
 use ibc::core::handler::types::events::IbcEvent;
 use ibc::core::handler::types::msgs::MsgEnvelope;
-use ibc::core::host::types::identifiers::{ChannelId, ClientId, ConnectionId, PortId, Sequence};
+use ibc::core::host::types::identifiers::{
+    ChannelId, ClientId, ClientType, ConnectionId, PortId, Sequence,
+};
 use ibc::core::host::types::path::{
-    ChannelEndPath, ClientConsensusStatePath, ClientStatePath, CommitmentPath, ConnectionPath,
-    SeqAckPath, SeqRecvPath, SeqSendPath,
+    AckPath, ChannelEndPath, ClientConnectionPath, ClientConsensusStatePath, ClientStatePath,
+    CommitmentPath, ConnectionPath, NextChannelSequencePath, NextClientSequencePath,
+    NextConnectionSequencePath, Path, ReceiptPath, SeqAckPath, SeqRecvPath, SeqSendPath,
 };
 use ibc::core::host::{ExecutionContext, ValidationContext};
 use ibc::primitives::prelude::*;
+use ibc::primitives::proto::{Any, Protobuf};
 use ibc::primitives::Timestamp;
+use ibc_query::core::context::{ProvableContext, QueryContext};
+use parking_lot::Mutex;
 
-use super::testapp::ibc::core::types::{LightClientState, MockIbcStore};
+use super::testapp::ibc::core::types::{
+    ClientTimeOffset, EventSink, LightClientState, MockIbcStore,
+};
 use crate::fixtures::core::context::TestContextConfig;
+use crate::fixtures::core::signer::dummy_account_id;
 use crate::hosts::{HostClientState, MockHost, TendermintHost, TestBlock, TestHeader, TestHost};
 use crate::relayer::error::RelayerError;
+use crate::testapp::ibc::clients::mock::client_state::MockClientState;
+use crate::testapp::ibc::clients::mock::consensus_state::MockConsensusState;
+use crate::testapp::ibc::clients::mock::header::MockHeader;
 use crate::testapp::ibc::clients::{AnyClientState, AnyConsensusState};
 use crate::testapp::ibc::core::router::MockRouter;
-use crate::testapp::ibc::core::types::DEFAULT_BLOCK_TIME_SECS;
 
 /// A context implementing the dependencies necessary for testing any IBC module.
 #[derive(Debug)]
@@ -50,6 +74,56 @@ where
 
     /// A router that can route messages to the appropriate IBC application.
     pub ibc_router: MockRouter,
+
+    /// The time elapsed between two consecutive blocks of the host chain.
+    /// Every height advanced through [`Self::advance_block_height`] moves the
+    /// host timestamp forward by this duration.
+    pub block_time: Duration,
+
+    /// The log of the handler calls made through the context, kept only when
+    /// enabled with [`Self::record_calls`].
+    pub handler_calls: Option<Vec<HandlerCall>>,
+}
+
+/// An entry of the handler call log of a [`StoreGenericTestContext`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HandlerCall {
+    /// The name of the invoked handler.
+    pub handler: String,
+    /// The key arguments of the invocation, as `(name, value)` pairs.
+    pub args: Vec<(String, String)>,
+}
+
+/// A handle to the IBC store of a [`StoreGenericTestContext`] that can be
+/// shared between threads, as given by
+/// [`StoreGenericTestContext::with_shared_ibc_store`].
+///
+/// Handlers run through the handle are serialized: each one holds the lock of
+/// the store until it returns, so that it never observes the partial writes of
+/// a concurrent one. Within a handler, the store only takes its inner locks
+/// for the duration of a single read or write and never holds one while
+/// calling back into itself, so handlers cannot deadlock on it.
+#[derive(Debug)]
+pub struct SharedIbcStore<'a, S>
+where
+    S: ProvableStore + Debug,
+{
+    ibc_store: Mutex<&'a mut MockIbcStore<S>>,
+}
+
+impl<S> SharedIbcStore<'_, S>
+where
+    S: ProvableStore + Debug,
+{
+    /// Runs the given closure with exclusive access to the IBC store.
+    pub fn with<R>(&self, f: impl FnOnce(&mut MockIbcStore<S>) -> R) -> R {
+        f(&mut self.ibc_store.lock())
+    }
+
+    /// Calls [`send_packet`] function on [`Packet`] using the shared IBC store.
+    pub fn send_packet(&self, packet: Packet) -> Result<SendPacketResult, ContextError> {
+        self.with(|ibc_store| send_packet(ibc_store, packet))
+    }
 }
 
 /// A mock store type using basecoin-storage implementations.
@@ -61,6 +135,172 @@ pub type MockContext = TestContext<MockHost>;
 /// A [`StoreGenericTestContext`] using [`MockStore`] and [`TendermintHost`].
 pub type TendermintContext = TestContext<TendermintHost>;
 
+/// A serializable snapshot of the IBC state of a [`StoreGenericTestContext`].
+///
+/// It covers the clients (along with their consensus states and update
+/// metadata), connections, channels, sequences, and the packet commitments,
+/// receipts and acknowledgements.
+/// Volatile data is excluded: the emitted events and logs, and the host chain
+/// itself, i.e. its blocks, clock and host consensus states. Those remain the
+/// ones of the context the snapshot is imported into.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, Default)]
+pub struct SerializableIbcState {
+    pub client_counter: u64,
+    pub connection_counter: u64,
+    pub channel_counter: u64,
+    pub clients: Vec<ClientSnapshot>,
+    pub connections: Vec<IdentifiedConnectionEnd>,
+    pub client_connections: Vec<ClientConnectionsSnapshot>,
+    pub channels: Vec<IdentifiedChannelEnd>,
+    pub send_sequences: Vec<SequenceSnapshot>,
+    pub recv_sequences: Vec<SequenceSnapshot>,
+    pub ack_sequences: Vec<SequenceSnapshot>,
+    pub packet_commitments: Vec<PacketCommitmentSnapshot>,
+    pub packet_receipts: Vec<PacketReceiptSnapshot>,
+    pub packet_acknowledgements: Vec<PacketAcknowledgementSnapshot>,
+}
+
+/// A client state and its consensus states, as part of a [`SerializableIbcState`].
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug)]
+pub struct ClientSnapshot {
+    pub client_id: ClientId,
+    pub client_state: Any,
+    pub consensus_states: Vec<ConsensusStateSnapshot>,
+}
+
+/// A consensus state along with the host time and height at which it was
+/// processed, if recorded.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug)]
+pub struct ConsensusStateSnapshot {
+    pub height: Height,
+    pub consensus_state: Any,
+    pub update_meta: Option<(Timestamp, Height)>,
+}
+
+/// The connections associated with a client.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug)]
+pub struct ClientConnectionsSnapshot {
+    pub client_id: ClientId,
+    pub connection_ids: Vec<ConnectionId>,
+}
+
+/// A next send, receive or ack sequence of a channel.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug)]
+pub struct SequenceSnapshot {
+    pub port_id: PortId,
+    pub channel_id: ChannelId,
+    pub sequence: Sequence,
+}
+
+/// A packet commitment stored at the given port, channel and sequence.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug)]
+pub struct PacketCommitmentSnapshot {
+    pub port_id: PortId,
+    pub channel_id: ChannelId,
+    pub sequence: Sequence,
+    pub commitment: PacketCommitment,
+}
+
+/// A packet receipt stored at the given port, channel and sequence.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug)]
+pub struct PacketReceiptSnapshot {
+    pub port_id: PortId,
+    pub channel_id: ChannelId,
+    pub sequence: Sequence,
+}
+
+/// A packet acknowledgement commitment stored at the given port, channel and
+/// sequence.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug)]
+pub struct PacketAcknowledgementSnapshot {
+    pub port_id: PortId,
+    pub channel_id: ChannelId,
+    pub sequence: Sequence,
+    pub ack_commitment: AcknowledgementCommitment,
+}
+
+/// A step of a scenario replayed by [`StoreGenericTestContext::replay_scenario`].
+///
+/// A scenario is a JSON array of steps, each an object tagged by its `type`:
+///
+/// - `{"type": "message", "type_url": <string>, "value": <hex>}` dispatches
+///   the protobuf encoded IBC message, e.g. a `MsgRecvPacket`.
+/// - `{"type": "send_packet", "packet": <packet>}` sends the packet, whose
+///   fields are those of [`ScenarioPacket`].
+/// - `{"type": "advance_block"}` advances the host chain by one block.
+///
+/// Hex strings are lowercase.
+#[cfg(feature = "serde")]
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ScenarioStep {
+    Message { type_url: String, value: String },
+    SendPacket { packet: ScenarioPacket },
+    AdvanceBlock,
+}
+
+/// A packet sent by a [`ScenarioStep::SendPacket`].
+///
+/// The `data` is a lowercase hex string. The `timeout_height` is either
+/// `null` or an object with a `revision_number` and a `revision_height`, and
+/// the `timeout_timestamp` is in nanoseconds since the Unix epoch, `0`
+/// standing for no timeout.
+#[cfg(feature = "serde")]
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct ScenarioPacket {
+    pub sequence: Sequence,
+    pub port_id_on_a: PortId,
+    pub chan_id_on_a: ChannelId,
+    pub port_id_on_b: PortId,
+    pub chan_id_on_b: ChannelId,
+    pub data: String,
+    pub timeout_height: Option<Height>,
+    pub timeout_timestamp: u64,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<ScenarioPacket> for Packet {
+    type Error = ibc::core::router::types::error::RouterError;
+
+    fn try_from(packet: ScenarioPacket) -> Result<Self, Self::Error> {
+        Ok(Packet {
+            seq_on_a: packet.sequence,
+            port_id_on_a: packet.port_id_on_a,
+            chan_id_on_a: packet.chan_id_on_a,
+            port_id_on_b: packet.port_id_on_b,
+            chan_id_on_b: packet.chan_id_on_b,
+            data: decode_scenario_hex(&packet.data)?,
+            timeout_height_on_b: packet
+                .timeout_height
+                .map_or(TimeoutHeight::Never, TimeoutHeight::At),
+            timeout_timestamp_on_b: Timestamp::from_nanoseconds(packet.timeout_timestamp).map_err(
+                |e| Self::Error::MalformedMessageBytes {
+                    reason: e.to_string(),
+                },
+            )?,
+        })
+    }
+}
+
+#[cfg(feature = "serde")]
+fn decode_scenario_hex(
+    value: &str,
+) -> Result<Vec<u8>, ibc::core::router::types::error::RouterError> {
+    subtle_encoding::hex::decode(value).map_err(|e| {
+        ibc::core::router::types::error::RouterError::MalformedMessageBytes {
+            reason: format!("invalid hex `{value}`: {e}"),
+        }
+    })
+}
+
 /// Returns a [`StoreGenericTestContext`] with bare minimum initialization: no clients, no connections, and no channels are
 /// present, and the chain has Height(5). This should be used sparingly, mostly for testing the
 /// creation of new domain objects.
@@ -236,17 +476,276 @@ where
         block_time: Duration,
         params: &H::BlockParams,
     ) {
+        // the block resuming a halted chain comes after the whole halt
+        let halt = core::mem::take(&mut *self.ibc_store.host_halt.lock());
+
         self.end_block();
-        self.commit_state_to_host(block_time, params);
+        self.commit_state_to_host(halt + block_time, params);
         self.begin_block();
     }
 
-    /// Convenience method to advance the host chain height using default parameters.
+    /// Convenience method to advance the host chain height by [`Self::block_time`]
+    /// using default block parameters.
     pub fn advance_block_height(&mut self) {
-        self.advance_block_height_with_params(
-            Duration::from_secs(DEFAULT_BLOCK_TIME_SECS),
-            &Default::default(),
-        )
+        self.advance_block_height_with_params(self.block_time, &Default::default())
+    }
+
+    /// Advances the host chain height by `n` blocks, each one
+    /// [`Self::block_time`] after the previous one.
+    pub fn advance_host_height(&mut self, n: u64) {
+        for _ in 0..n {
+            self.advance_block_height();
+        }
+    }
+
+    /// Moves the host time forward by `delta`, e.g. to get past the trusting
+    /// period of a client.
+    ///
+    /// The host time is the one of the latest block, hence a single block is
+    /// produced `delta` after the latest one, which also advances the host
+    /// height by one.
+    pub fn advance_host_time(&mut self, delta: Duration) {
+        self.advance_block_by(delta, 1)
+    }
+
+    /// Halts the host chain for `duration`: the host timestamp seen by the IBC
+    /// handlers moves forward, while the host height stays the same. Halting
+    /// again extends the halt.
+    ///
+    /// No block is produced during the halt, hence no host consensus state is
+    /// stored and [`Self::latest_timestamp`] remains the one of the latest
+    /// block. The chain resumes with the next block, produced the whole halt
+    /// plus its block time after the latest one.
+    pub fn halt(&mut self, duration: Duration) {
+        *self.ibc_store.host_halt.lock() += duration;
+    }
+
+    /// Advances the host chain by `n` blocks spanning `delta` in total.
+    ///
+    /// The blocks are evenly spaced, except for the last one which also takes
+    /// the remainder of the division, so that the host time moves forward by
+    /// exactly `delta`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is zero, as the host time cannot move without a block,
+    /// or does not fit in a `u32`.
+    pub fn advance_block_by(&mut self, delta: Duration, n: u64) {
+        assert_ne!(
+            n, 0,
+            "at least one block is needed to advance the host time"
+        );
+
+        let n = u32::try_from(n).expect("number of blocks fits in a u32");
+        let block_time = delta / n;
+
+        for _ in 1..n {
+            self.advance_block_height_with_params(block_time, &Default::default());
+        }
+
+        self.advance_block_height_with_params(delta - block_time * (n - 1), &Default::default());
+    }
+
+    /// Rewinds the host chain to the given height, e.g. to simulate a chain
+    /// reorganization.
+    ///
+    /// The blocks above `height` are discarded, along with the matching host
+    /// consensus states and the consensus states above `height` of every
+    /// client, together with their update metadata. The host height and
+    /// timestamp are then the ones of the block at `height`, any ongoing
+    /// [`Self::halt`] being discarded as well.
+    ///
+    /// The rest of the IBC state is kept as is, i.e. the state written above
+    /// `height`, like packet commitments, receipts and acknowledgements, is
+    /// not reverted. However, the stores are recommitted for each kept block,
+    /// which then commits to the current IBC state instead of the one it was
+    /// produced with, hence proofs taken before the rewind do not verify
+    /// against the kept blocks anymore.
+    pub fn rewind_to(&mut self, height: Height)
+    where
+        S: Default,
+    {
+        let latest_height = self.latest_height();
+        assert!(
+            height.revision_number() == latest_height.revision_number() && height <= latest_height,
+            "cannot rewind the chain from {latest_height} to {height}"
+        );
+
+        for ClientStatePath(client_id) in self.ibc_store.typed_keys::<ClientStatePath>() {
+            let discarded_heights = self
+                .ibc_store
+                .consensus_state_heights(&client_id)
+                .expect("no error")
+                .into_iter()
+                .filter(|consensus_height| consensus_height > &height);
+
+            for consensus_height in discarded_heights {
+                self.ibc_store
+                    .delete_consensus_state(ClientConsensusStatePath::new(
+                        client_id.clone(),
+                        consensus_height.revision_number(),
+                        consensus_height.revision_height(),
+                    ))
+                    .expect("no error");
+                self.ibc_store
+                    .delete_update_meta(client_id.clone(), consensus_height)
+                    .expect("no error");
+            }
+        }
+
+        self.ibc_store
+            .host_consensus_states
+            .lock()
+            .retain(|h, _| h <= &height.revision_height());
+        self.ibc_store
+            .ibc_commiment_proofs
+            .lock()
+            .retain(|h, _| h <= &height.revision_height());
+        *self.ibc_store.host_halt.lock() = Duration::ZERO;
+
+        // the store versions follow the host height, hence the current IBC
+        // state is moved to a fresh store, committed again for each kept block
+        let mut store = S::default();
+        for path in self.ibc_store.store.get_keys(&String::new().into()) {
+            let value = self
+                .ibc_store
+                .store
+                .get(StoreHeight::Pending, &path)
+                .expect("key exists");
+            store.set(path, value).expect("no error");
+        }
+        *self.ibc_store.store.write().expect("no error") = store;
+        self.multi_store = S::default();
+
+        let blocks = self.host.history()[..height.revision_height() as usize].to_vec();
+        self.host.truncate_history(0);
+
+        // replays the kept blocks, the same way the context is bootstrapped
+        for block in blocks {
+            self.end_block();
+            self.multi_store.commit().expect("no error");
+            self.host.push_block(block);
+            self.begin_block();
+        }
+    }
+
+    /// Sets the time elapsed between two consecutive blocks produced from now on.
+    pub fn with_block_time(mut self, block_time: Duration) -> Self {
+        self.block_time = block_time;
+        self
+    }
+
+    /// Forces the given client to report the given status, regardless of its
+    /// actual state, e.g. to exercise handlers against frozen or expired
+    /// clients.
+    ///
+    /// This is a test-only override, which is only honored by clients of the
+    /// mock type: the status of the other clients is computed by their own
+    /// implementation.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the client does not exist or is not a mock client, rather
+    /// than silently leaving its status unchanged.
+    pub fn set_client_status(&mut self, client_id: &ClientId, status: Status) {
+        match self.ibc_store.client_state(client_id) {
+            Ok(AnyClientState::Mock(_)) => {}
+            Ok(_) => panic!("the status of `{client_id}` cannot be overridden: not a mock client"),
+            Err(_) => panic!("the status of `{client_id}` cannot be overridden: no such client"),
+        }
+
+        self.ibc_store
+            .client_status_overrides
+            .lock()
+            .insert(client_id.clone(), status);
+    }
+
+    /// Makes the given mock client report each given status once the host
+    /// reaches the paired height, until the next scheduled height, e.g.
+    /// `Active` until `H`, then `Expired`. Before the first scheduled height,
+    /// the status is computed as usual.
+    ///
+    /// The schedule is kept in the store, apart from the client state, hence
+    /// it survives the updates of the client. A frozen client or a status
+    /// forced with [`Self::set_client_status`] takes precedence over it. An
+    /// empty schedule clears the one of the client.
+    pub fn with_client_status_schedule(
+        self,
+        client_id: &ClientId,
+        mut status_schedule: Vec<(Height, Status)>,
+    ) -> Self {
+        {
+            let mut client_status_schedules = self.ibc_store.client_status_schedules.lock();
+
+            if status_schedule.is_empty() {
+                client_status_schedules.remove(client_id);
+            } else {
+                status_schedule.sort_by_key(|(height, _)| *height);
+                client_status_schedules.insert(client_id.clone(), status_schedule);
+            }
+        }
+
+        self
+    }
+
+    /// Makes the verification of the proofs checked by the next handler fail
+    /// with a verification error, while the proofs themselves are generated as
+    /// usual, e.g. to simulate a tampered proof.
+    ///
+    /// This only applies to clients of the mock type: the failure stays
+    /// pending in the store until a membership or non-membership proof is
+    /// verified against a mock client state read from it, regardless of the
+    /// other reads of the client states in between.
+    pub fn fail_next_proof_verification(&mut self) {
+        self.ibc_store
+            .fail_next_proof_verification
+            .store(true, Ordering::SeqCst);
+    }
+
+    /// Makes the next read of a connection end from the store fail, e.g. to
+    /// exercise handlers against a faulty storage backend.
+    ///
+    /// The failure is reported as [`ConnectionError::Other`] and only affects
+    /// a single read: the following ones succeed as usual.
+    ///
+    /// [`ConnectionError::Other`]: ibc::core::connection::types::error::ConnectionError::Other
+    pub fn fail_next_connection_read(&mut self) {
+        *self.ibc_store.fail_next_connection_read.lock() = true;
+    }
+
+    /// Makes the next read of a channel end from the store fail, e.g. to
+    /// exercise handlers against a faulty storage backend.
+    ///
+    /// The failure is reported as [`ChannelError::Other`] and only affects a
+    /// single read: the following ones succeed as usual.
+    ///
+    /// [`ChannelError::Other`]: ibc::core::channel::types::error::ChannelError::Other
+    pub fn fail_next_channel_read(&mut self) {
+        *self.ibc_store.fail_next_channel_read.lock() = true;
+    }
+
+    /// Skews the host time seen by the given client by `offset`, a test-only
+    /// knob to exercise clock drift scenarios.
+    ///
+    /// The offset applies to the host time used by the time-based checks of
+    /// the light client, as given by
+    /// [`ExtClientValidationContext::client_host_timestamp`], e.g. its expiry
+    /// or the trusting period and max clock drift of the headers it verifies.
+    /// The host time of the core handlers, e.g. for packet timeouts, is left
+    /// untouched.
+    pub fn set_client_time_offset(&mut self, client_id: &ClientId, offset: ClientTimeOffset) {
+        self.ibc_store
+            .client_time_offsets
+            .lock()
+            .insert(client_id.clone(), offset);
+    }
+
+    /// Enables or disables the rejection of client update metadata stored
+    /// with a host time or height earlier than the one of an existing update
+    /// of the same client.
+    pub fn with_strict_update_meta(self, strict: bool) -> Self {
+        *self.ibc_store.strict_update_meta.lock() = strict;
+        self
     }
 
     /// Returns the latest height of the host chain.
@@ -301,6 +800,53 @@ where
         self
     }
 
+    /// Associates a pair of conflicting consensus states to the given client
+    /// at the given height, e.g. to build misbehaviour evidence.
+    ///
+    /// Only `state_a` is stored as the accepted consensus state, since a
+    /// single consensus state can be stored per height. `state_b` is kept
+    /// aside and can be retrieved with
+    /// [`conflicting_consensus_state`](Self::conflicting_consensus_state).
+    pub fn with_conflicting_consensus_state(
+        self,
+        client_id: &ClientId,
+        height: Height,
+        state_a: AnyConsensusState,
+        state_b: AnyConsensusState,
+    ) -> Self {
+        let consensus_state_path = ClientConsensusStatePath::new(
+            client_id.clone(),
+            height.revision_number(),
+            height.revision_height(),
+        );
+        self.ibc_store
+            .conflicting_consensus_states
+            .lock()
+            .insert(consensus_state_path, state_b);
+
+        self.with_consensus_state(client_id, height, state_a)
+    }
+
+    /// Returns the consensus state conflicting with the stored one of the
+    /// given client at the given height, if any was set with
+    /// [`with_conflicting_consensus_state`](Self::with_conflicting_consensus_state).
+    pub fn conflicting_consensus_state(
+        &self,
+        client_id: &ClientId,
+        height: Height,
+    ) -> Option<AnyConsensusState> {
+        let consensus_state_path = ClientConsensusStatePath::new(
+            client_id.clone(),
+            height.revision_number(),
+            height.revision_height(),
+        );
+        self.ibc_store
+            .conflicting_consensus_states
+            .lock()
+            .get(&consensus_state_path)
+            .cloned()
+    }
+
     /// Generates a light client for the host by generating a client
     /// state, as well as generating consensus states for each
     /// consensus height.
@@ -366,146 +912,2757 @@ where
         self
     }
 
-    /// Bootstraps a IBC connection to this context.
+    /// Bootstraps a mock light client with a consensus state at each of the
+    /// given heights.
     ///
-    /// This does not bootstrap any light client.
-    pub fn with_connection(
-        mut self,
-        connection_id: ConnectionId,
-        connection_end: ConnectionEnd,
-    ) -> Self {
-        let connection_path = ConnectionPath::new(&connection_id);
-        self.ibc_store
-            .store_connection(&connection_path, connection_end)
-            .expect("error writing to store");
-        self
-    }
-
-    /// Bootstraps a IBC channel to this context.
+    /// The client state is installed at the highest of the given heights and
+    /// all consensus states carry the latest timestamp of the host chain.
     ///
-    /// This does not bootstrap any corresponding IBC connection or light client.
-    pub fn with_channel(
-        mut self,
-        port_id: PortId,
-        chan_id: ChannelId,
-        channel_end: ChannelEnd,
-    ) -> Self {
-        let channel_end_path = ChannelEndPath::new(&port_id, &chan_id);
-        self.ibc_store
-            .store_channel(&channel_end_path, channel_end)
-            .expect("error writing to store");
-        self
+    /// # Panics
+    ///
+    /// Panics if no height is given, as the client state would have no latest
+    /// height.
+    pub fn with_client_at_heights(self, client_id: &ClientId, heights: &[Height]) -> Self {
+        let timestamp = self.latest_timestamp();
+
+        let latest_height = *heights
+            .iter()
+            .max()
+            .expect("at least one height must be given");
+
+        let light_client = LightClientState::<MockHost> {
+            client_state: MockClientState::new(
+                MockHeader::new(latest_height).with_timestamp(timestamp),
+            ),
+            consensus_states: heights
+                .iter()
+                .map(|&height| {
+                    (
+                        height,
+                        MockConsensusState::new(MockHeader::new(height).with_timestamp(timestamp)),
+                    )
+                })
+                .collect(),
+        };
+
+        self.with_light_client(client_id, light_client)
     }
 
-    /// Bootstraps a send sequence to this context.
+    /// Seeds the given mock client with all the given consensus states at
+    /// once, along with their update metadata.
     ///
-    /// This does not bootstrap any corresponding IBC channel, connection or light client.
-    pub fn with_send_sequence(
+    /// The client state is set to a mock one at the highest of the given
+    /// heights, carrying the timestamp of the consensus state there. An
+    /// existing mock client state keeps its other settings, e.g. its trusting
+    /// period, while previously stored consensus states are left in place.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no consensus state is given, or if the client exists but is
+    /// not a mock client.
+    pub fn with_consensus_states(
         mut self,
-        port_id: PortId,
-        chan_id: ChannelId,
-        seq_number: Sequence,
+        client_id: &ClientId,
+        states: impl IntoIterator<Item = (Height, AnyConsensusState)>,
     ) -> Self {
-        let seq_send_path = SeqSendPath::new(&port_id, &chan_id);
-        self.ibc_store
-            .store_next_sequence_send(&seq_send_path, seq_number)
-            .expect("error writing to store");
+        let states: BTreeMap<_, _> = states.into_iter().collect();
+
+        let (&latest_height, latest_consensus_state) = states
+            .last_key_value()
+            .expect("at least one consensus state must be given");
+
+        let header =
+            MockHeader::new(latest_height).with_timestamp(latest_consensus_state.timestamp());
+        let client_state = match self.ibc_store.client_state(client_id) {
+            Ok(AnyClientState::Mock(client_state)) => MockClientState {
+                header,
+                ..client_state
+            },
+            Ok(_) => panic!("consensus states can only be seeded into a mock client"),
+            Err(_) => MockClientState::new(header),
+        };
+
+        self = self.with_client_state(client_id, client_state.into());
+
+        for (height, consensus_state) in states {
+            self = self.with_consensus_state(client_id, height, consensus_state);
+
+            self.ibc_store
+                .store_update_meta(
+                    client_id.clone(),
+                    height,
+                    self.latest_timestamp(),
+                    self.latest_height(),
+                )
+                .expect("error writing to store");
+        }
+
         self
     }
 
-    /// Bootstraps a receive sequence to this context.
+    /// Returns the identifier that the create client handler assigns to the
+    /// next client of the given type.
     ///
-    /// This does not bootstrap any corresponding IBC channel, connection or light client.
-    pub fn with_recv_sequence(
-        mut self,
-        port_id: PortId,
-        chan_id: ChannelId,
-        seq_number: Sequence,
-    ) -> Self {
-        let seq_recv_path = SeqRecvPath::new(&port_id, &chan_id);
-        self.ibc_store
-            .store_next_sequence_recv(&seq_recv_path, seq_number)
-            .expect("error writing to store");
-        self
+    /// Identifiers are allocated from the client counter of this context's
+    /// IBC store. The counter is shared by all client types, so clients
+    /// created one after another get `{type}-0`, `{type}-1`, and so on,
+    /// regardless of any other context.
+    pub fn next_client_id(&self, client_type: &ClientType) -> ClientId {
+        client_type.build_client_id(
+            self.ibc_store
+                .client_counter()
+                .expect("client counter exists"),
+        )
     }
 
-    /// Bootstraps a ack sequence to this context.
+    /// Updates the given client to `new_height` the way a relayer would, by
+    /// submitting a [`MsgUpdateClient`] with a header at that height and
+    /// timestamp through the update client handler.
     ///
-    /// This does not bootstrap any corresponding IBC channel, connection or light client.
-    pub fn with_ack_sequence(
+    /// Mock clients are updated with a [`MockHeader`]. Tendermint clients are
+    /// updated with a block of a [`TendermintHost`] signed by the default
+    /// validators, hence the client must have been created from such a host.
+    /// The timestamp must be ahead of the client's latest consensus state,
+    /// but not ahead of this host beyond the client's maximum clock drift.
+    pub fn with_updated_client(
         mut self,
-        port_id: PortId,
-        chan_id: ChannelId,
-        seq_number: Sequence,
+        client_id: &ClientId,
+        new_height: Height,
+        timestamp: Timestamp,
     ) -> Self {
-        let seq_ack_path = SeqAckPath::new(&port_id, &chan_id);
-        self.ibc_store
-            .store_next_sequence_ack(&seq_ack_path, seq_number)
-            .expect("error writing to store");
+        self.update_client_to(client_id, new_height, timestamp)
+            .expect("client update succeeds");
+
         self
     }
 
-    /// Bootstraps a packet commitment to this context.
+    /// Updates the given client one height at a time up to `target`, so that
+    /// it holds a consensus state at every height in between, e.g. to prove
+    /// packets at any of these heights.
     ///
-    /// This does not bootstrap any corresponding IBC channel, connection or light client.
-    pub fn with_packet_commitment(
-        mut self,
-        port_id: PortId,
-        chan_id: ChannelId,
-        seq: Sequence,
-        data: PacketCommitment,
-    ) -> Self {
-        let commitment_path = CommitmentPath::new(&port_id, &chan_id, seq);
-        self.ibc_store
-            .store_packet_commitment(&commitment_path, data)
-            .expect("error writing to store");
-        self
-    }
+    /// Each header is built like in [`Self::with_updated_client`], hence a
+    /// Tendermint client is updated with blocks signed by the default
+    /// validators and goes through the full header verification. Headers are
+    /// timestamped with the host time, which is advanced by one block after
+    /// each update, as well as beforehand if needed to get past the latest
+    /// consensus state of the client. Does nothing if the client is already
+    /// at or above `target`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `target` is of another revision than the latest height of
+    /// the client.
+    pub fn advance_client(
+        &mut self,
+        client_id: &ClientId,
+        target: Height,
+    ) -> Result<(), ContextError> {
+        let latest_height = self.light_client_latest_height(client_id);
 
-    /// Calls [`validate`] function on [`MsgEnvelope`] using the context's IBC store and router.
+        assert_eq!(
+            latest_height.revision_number(),
+            target.revision_number(),
+            "cannot advance a client to another revision"
+        );
+
+        if latest_height >= target {
+            return Ok(());
+        }
+
+        let latest_consensus_timestamp = self
+            .ibc_store
+            .consensus_state(&ClientConsensusStatePath::new(
+                client_id.clone(),
+                latest_height.revision_number(),
+                latest_height.revision_height(),
+            ))?
+            .timestamp();
+
+        while self.latest_timestamp() <= latest_consensus_timestamp {
+            self.advance_block_height();
+        }
+
+        for revision_height in latest_height.revision_height() + 1..=target.revision_height() {
+            let height = Height::new(target.revision_number(), revision_height)?;
+
+            self.update_client_to(client_id, height, self.latest_timestamp())?;
+            self.advance_block_height();
+        }
+
+        Ok(())
+    }
+
+    /// Submits a [`MsgUpdateClient`] with a header of the given client type
+    /// at the given height and timestamp, see [`Self::with_updated_client`].
+    fn update_client_to(
+        &mut self,
+        client_id: &ClientId,
+        new_height: Height,
+        timestamp: Timestamp,
+    ) -> Result<(), ContextError> {
+        let client_message: Any = match self.ibc_store.client_state(client_id)? {
+            AnyClientState::Mock(_) => MockHeader::new(new_height).with_timestamp(timestamp).into(),
+            AnyClientState::Tendermint(cs) => {
+                let mut header = TendermintHost::builder()
+                    .chain_id(cs.inner().chain_id.clone())
+                    .build()
+                    .generate_block(
+                        Vec::new(),
+                        new_height.revision_height(),
+                        timestamp,
+                        &Default::default(),
+                    )
+                    .into_header();
+                header.set_trusted_height(cs.inner().latest_height);
+                header.into()
+            }
+            AnyClientState::Unknown(cs) => {
+                panic!("client of type `{}` cannot be updated", cs.type_url())
+            }
+        };
+
+        let msg = MsgEnvelope::from(ClientMsg::from(MsgUpdateClient {
+            client_id: client_id.clone(),
+            client_message,
+            signer: dummy_account_id(),
+        }));
+
+        self.dispatch(msg)
+    }
+
+    /// Returns a copy of all the consensus states of the given client, keyed
+    /// by their heights, or `None` if the client does not exist.
+    pub fn consensus_states(
+        &self,
+        client_id: &ClientId,
+    ) -> Option<BTreeMap<Height, AnyConsensusState>> {
+        self.ibc_store.client_state(client_id).ok()?;
+
+        let consensus_states = self
+            .ibc_store
+            .consensus_state_heights(client_id)
+            .expect("no error")
+            .into_iter()
+            .map(|height| {
+                let consensus_state = self
+                    .ibc_store
+                    .consensus_state(&ClientConsensusStatePath::new(
+                        client_id.clone(),
+                        height.revision_number(),
+                        height.revision_height(),
+                    ))
+                    .expect("consensus state exists");
+
+                (height, consensus_state)
+            })
+            .collect();
+
+        Some(consensus_states)
+    }
+
+    /// Returns whether the given client has a consensus state at exactly the
+    /// given height, looking up that single entry instead of listing the
+    /// heights of all of them.
+    ///
+    /// Fails if the client does not exist.
+    pub fn consensus_state_exists(
+        &self,
+        client_id: &ClientId,
+        height: &Height,
+    ) -> Result<bool, ContextError> {
+        self.ibc_store.client_state(client_id)?;
+
+        let consensus_state_path = ClientConsensusStatePath::new(
+            client_id.clone(),
+            height.revision_number(),
+            height.revision_height(),
+        );
+
+        Ok(self
+            .ibc_store
+            .consensus_state_store
+            .get(StoreHeight::Pending, &consensus_state_path)
+            .is_some())
+    }
+
+    /// Removes the consensus states of the given client that are older than
+    /// its trusting period at `current_time`, along with their update
+    /// metadata, and returns how many were removed.
+    ///
+    /// Consensus states still within the trusting period are never pruned.
+    /// Clients of an unknown type have no trusting period and are left as is.
+    pub fn prune_expired_consensus_states(
+        &mut self,
+        client_id: &ClientId,
+        current_time: Timestamp,
+    ) -> usize {
+        let trusting_period = match self
+            .ibc_store
+            .client_state(client_id)
+            .expect("client state exists")
+        {
+            AnyClientState::Tendermint(cs) => cs.inner().trusting_period,
+            AnyClientState::Mock(cs) => cs.trusting_period,
+            AnyClientState::Unknown(_) => return 0,
+        };
+
+        let expired_heights: Vec<Height> = self
+            .ibc_store
+            .consensus_state_heights(client_id)
+            .expect("no error")
+            .into_iter()
+            .filter(|height| {
+                let consensus_state = self
+                    .ibc_store
+                    .consensus_state(&ClientConsensusStatePath::new(
+                        client_id.clone(),
+                        height.revision_number(),
+                        height.revision_height(),
+                    ))
+                    .expect("consensus state exists");
+
+                current_time
+                    .duration_since(&consensus_state.timestamp())
+                    .is_some_and(|elapsed| elapsed > trusting_period)
+            })
+            .collect();
+
+        for height in &expired_heights {
+            self.ibc_store
+                .delete_consensus_state(ClientConsensusStatePath::new(
+                    client_id.clone(),
+                    height.revision_number(),
+                    height.revision_height(),
+                ))
+                .expect("error writing to store");
+            self.ibc_store
+                .delete_update_meta(client_id.clone(), *height)
+                .expect("error writing to store");
+        }
+
+        expired_heights.len()
+    }
+
+    /// Bootstraps a IBC connection to this context.
+    ///
+    /// This does not bootstrap any light client.
+    pub fn with_connection(
+        mut self,
+        connection_id: ConnectionId,
+        connection_end: ConnectionEnd,
+    ) -> Self {
+        let connection_path = ConnectionPath::new(&connection_id);
+        self.ibc_store
+            .store_connection(&connection_path, connection_end)
+            .expect("error writing to store");
+        self
+    }
+
+    /// Sets the delay period of every connection bootstrapped so far.
+    ///
+    /// Packets relayed over such a connection are only accepted once `delay`
+    /// has elapsed, in both host time and blocks, since the host processed the
+    /// client update at their proof height. That update metadata is recorded
+    /// by [`Self::with_light_client`] and by the client updates, and can be
+    /// pushed back with [`Self::advance_host_time`].
+    pub fn with_connection_delay(mut self, delay: Duration) -> Self {
+        for path in self.ibc_store.typed_keys::<ConnectionPath>() {
+            let conn_end = self
+                .ibc_store
+                .connection_end_store
+                .get(StoreHeight::Pending, &path)
+                .expect("connection end exists");
+
+            let conn_end = ConnectionEnd::new(
+                *conn_end.state(),
+                conn_end.client_id().clone(),
+                conn_end.counterparty().clone(),
+                conn_end.versions().to_vec(),
+                delay,
+            )
+            .expect("valid connection end");
+
+            self.ibc_store
+                .store_connection(&path, conn_end)
+                .expect("error writing to store");
+        }
+
+        self
+    }
+
+    /// Bootstraps a IBC channel to this context.
+    ///
+    /// This does not bootstrap any corresponding IBC connection or light client.
+    pub fn with_channel(
+        mut self,
+        port_id: PortId,
+        chan_id: ChannelId,
+        channel_end: ChannelEnd,
+    ) -> Self {
+        let channel_end_path = ChannelEndPath::new(&port_id, &chan_id);
+        self.ibc_store
+            .store_channel(&channel_end_path, channel_end)
+            .expect("error writing to store");
+        self
+    }
+
+    /// Records the version negotiated for the given channel, which
+    /// `send_packet` then expects the stored channel end to carry.
+    pub fn with_expected_channel_version(
+        self,
+        port_id: PortId,
+        chan_id: ChannelId,
+        version: ChannelVersion,
+    ) -> Self {
+        self.ibc_store
+            .expected_channel_versions
+            .lock()
+            .insert(ChannelEndPath::new(&port_id, &chan_id), version);
+        self
+    }
+
+    /// Bootstraps a send sequence to this context.
+    ///
+    /// This does not bootstrap any corresponding IBC channel, connection or light client.
+    pub fn with_send_sequence(
+        mut self,
+        port_id: PortId,
+        chan_id: ChannelId,
+        seq_number: Sequence,
+    ) -> Self {
+        let seq_send_path = SeqSendPath::new(&port_id, &chan_id);
+        self.ibc_store
+            .store_next_sequence_send(&seq_send_path, seq_number)
+            .expect("error writing to store");
+        self
+    }
+
+    /// Bootstraps a receive sequence to this context.
+    ///
+    /// This does not bootstrap any corresponding IBC channel, connection or light client.
+    pub fn with_recv_sequence(
+        mut self,
+        port_id: PortId,
+        chan_id: ChannelId,
+        seq_number: Sequence,
+    ) -> Self {
+        let seq_recv_path = SeqRecvPath::new(&port_id, &chan_id);
+        self.ibc_store
+            .store_next_sequence_recv(&seq_recv_path, seq_number)
+            .expect("error writing to store");
+        self
+    }
+
+    /// Bootstraps a ack sequence to this context.
+    ///
+    /// This does not bootstrap any corresponding IBC channel, connection or light client.
+    pub fn with_ack_sequence(
+        mut self,
+        port_id: PortId,
+        chan_id: ChannelId,
+        seq_number: Sequence,
+    ) -> Self {
+        let seq_ack_path = SeqAckPath::new(&port_id, &chan_id);
+        self.ibc_store
+            .store_next_sequence_ack(&seq_ack_path, seq_number)
+            .expect("error writing to store");
+        self
+    }
+
+    /// Bootstraps a packet commitment to this context.
+    ///
+    /// This does not bootstrap any corresponding IBC channel, connection or light client.
+    pub fn with_packet_commitment(
+        mut self,
+        port_id: PortId,
+        chan_id: ChannelId,
+        seq: Sequence,
+        data: PacketCommitment,
+    ) -> Self {
+        let commitment_path = CommitmentPath::new(&port_id, &chan_id, seq);
+        self.ibc_store
+            .store_packet_commitment(&commitment_path, data)
+            .expect("error writing to store");
+        self
+    }
+
+    /// Returns the acknowledgements stored on the given channel that are not
+    /// yet confirmed as relayed back to the sender chain, see
+    /// [`Self::confirm_acknowledgement_relayed`], along with their sequences.
+    ///
+    /// As the store only keeps the commitments of the acknowledgements, these
+    /// are returned rather than the acknowledgements themselves.
+    pub fn pending_acknowledgements(
+        &self,
+        port_id: &PortId,
+        chan_id: &ChannelId,
+    ) -> Vec<(Sequence, Vec<u8>)> {
+        let relayed_acks = self.ibc_store.relayed_acks.lock();
+
+        self.ibc_store
+            .packet_acknowledgements(&ChannelEndPath::new(port_id, chan_id), core::iter::empty())
+            .expect("no error")
+            .into_iter()
+            .filter(|ack| !relayed_acks.contains(&AckPath::new(port_id, chan_id, ack.seq)))
+            .map(|ack| (ack.seq, ack.data))
+            .collect()
+    }
+
+    /// Marks the acknowledgement stored at the given sequence as relayed back
+    /// to the sender chain, removing it from the
+    /// [`pending acknowledgements`](Self::pending_acknowledgements).
+    pub fn confirm_acknowledgement_relayed(
+        &mut self,
+        port_id: &PortId,
+        chan_id: &ChannelId,
+        seq: Sequence,
+    ) {
+        self.ibc_store
+            .relayed_acks
+            .lock()
+            .insert(AckPath::new(port_id, chan_id, seq));
+    }
+
+    /// Returns the commitment of the packet sent at the given sequence along
+    /// with the proof of its membership in the state of the latest block, i.e.
+    /// at [`Self::latest_height`], e.g. to verify it end to end against the
+    /// commitment root of that block.
+    ///
+    /// The packet must have been committed to a block beforehand, e.g. by
+    /// calling [`Self::advance_block_height`] after sending it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no commitment of the packet is part of the latest block.
+    pub fn prove_packet_commitment(
+        &self,
+        port_id: &PortId,
+        chan_id: &ChannelId,
+        seq: Sequence,
+    ) -> (Vec<u8>, CommitmentProofBytes) {
+        let commitment_path = CommitmentPath::new(port_id, chan_id, seq);
+
+        let commitment = self
+            .ibc_store
+            .get_packet_commitment(&commitment_path)
+            .expect("packet commitment exists");
+
+        let proof = self
+            .ibc_store
+            .get_proof(self.latest_height(), &commitment_path.into())
+            .expect("packet commitment is part of the latest block")
+            .try_into()
+            .expect("value merkle proof");
+
+        (commitment.into_vec(), proof)
+    }
+
+    /// Returns the proof of membership of the consensus state of the given
+    /// client at the given height in the state of the latest block, i.e. at
+    /// [`Self::latest_height`], e.g. to verify a connection handshake end to
+    /// end.
+    ///
+    /// Consensus states are stored in the provable store at their standard
+    /// [`ClientConsensusStatePath`], hence the proof is checked against the
+    /// protobuf [`Any`] encoding of the consensus state. Returns `None` if no
+    /// such consensus state is part of the latest block, e.g. if it was stored
+    /// after the last call to [`Self::advance_block_height`].
+    pub fn get_consensus_proof(
+        &self,
+        client_id: &ClientId,
+        height: Height,
+    ) -> Option<CommitmentProofBytes> {
+        let consensus_state_path = ClientConsensusStatePath::new(
+            client_id.clone(),
+            height.revision_number(),
+            height.revision_height(),
+        );
+
+        self.ibc_store
+            .get_proof(self.latest_height(), &consensus_state_path.into())?
+            .try_into()
+            .ok()
+    }
+
+    /// Removes all the channel ends of the context, along with their
+    /// negotiated versions, e.g. to reuse the context across scenarios.
+    ///
+    /// Clients and their consensus states are untouched, as are connections,
+    /// packet sequences and the channel counter.
+    pub fn clear_channels(&mut self) {
+        for path in self.ibc_store.typed_keys::<ChannelEndPath>() {
+            self.ibc_store.channel_end_store.delete(path);
+        }
+
+        self.ibc_store.expected_channel_versions.lock().clear();
+    }
+
+    /// Removes all the connection ends of the context, along with the lists
+    /// of connections of each client.
+    ///
+    /// Clients and their consensus states are untouched, as are channels and
+    /// the connection counter.
+    pub fn clear_connections(&mut self) {
+        for path in self.ibc_store.typed_keys::<ConnectionPath>() {
+            self.ibc_store.connection_end_store.delete(path);
+        }
+
+        for path in self.ibc_store.typed_keys::<ClientConnectionPath>() {
+            self.ibc_store.connection_ids_store.delete(path);
+        }
+    }
+
+    /// Removes the next send, receive and ack sequences of all channels.
+    ///
+    /// Clients and their consensus states are untouched, as are connections
+    /// and channels.
+    pub fn reset_sequences(&mut self) {
+        let ibc_store = &mut self.ibc_store;
+
+        for path in ibc_store.typed_keys::<SeqSendPath>() {
+            ibc_store.send_sequence_store.delete(path);
+        }
+
+        for path in ibc_store.typed_keys::<SeqRecvPath>() {
+            ibc_store.recv_sequence_store.delete(path);
+        }
+
+        for path in ibc_store.typed_keys::<SeqAckPath>() {
+            ibc_store.ack_sequence_store.delete(path);
+        }
+    }
+
+    /// Asserts that the clients, consensus states, connections and channels
+    /// held by the store take up at most `max_bytes` once encoded, e.g. to
+    /// catch pruning bugs in long running simulations.
+    ///
+    /// Panics with the size of each kind of entry if over budget. Other
+    /// entries, such as sequences or packet commitments, are not counted.
+    pub fn assert_store_under(&self, max_bytes: usize) {
+        let ibc_store = &self.ibc_store;
+
+        let clients: usize = ibc_store
+            .typed_keys::<ClientStatePath>()
+            .into_iter()
+            .filter_map(|path| {
+                ibc_store
+                    .client_state_store
+                    .get(StoreHeight::Pending, &path)
+            })
+            .map(|client_state| Protobuf::<Any>::encode_vec(client_state).len())
+            .sum();
+
+        let consensus_states: usize = ibc_store
+            .typed_keys::<ClientConsensusStatePath>()
+            .into_iter()
+            .filter_map(|path| {
+                ibc_store
+                    .consensus_state_store
+                    .get(StoreHeight::Pending, &path)
+            })
+            .map(|consensus_state| Protobuf::<Any>::encode_vec(consensus_state).len())
+            .sum();
+
+        let connections: usize = ibc_store
+            .typed_keys::<ConnectionPath>()
+            .into_iter()
+            .filter_map(|path| {
+                ibc_store
+                    .connection_end_store
+                    .get(StoreHeight::Pending, &path)
+            })
+            .map(|connection_end| connection_end.encode_vec().len())
+            .sum();
+
+        let channels: usize = ibc_store
+            .typed_keys::<ChannelEndPath>()
+            .into_iter()
+            .filter_map(|path| ibc_store.channel_end_store.get(StoreHeight::Pending, &path))
+            .map(|channel_end| channel_end.encode_vec().len())
+            .sum();
+
+        let total = clients + consensus_states + connections + channels;
+
+        assert!(
+            total <= max_bytes,
+            "store size of {total} bytes exceeds the budget of {max_bytes} bytes \
+             (clients: {clients}, consensus states: {consensus_states}, \
+             connections: {connections}, channels: {channels})"
+        );
+    }
+
+    /// Returns a [`ClientSnapshot`] of the given client, i.e. its client state
+    /// and consensus states along with their update metadata, or `None` if the
+    /// client does not exist.
+    ///
+    /// The consensus states are ordered by height, so that the serialized form
+    /// of the snapshot is stable.
+    pub fn snapshot_client(&self, client_id: &ClientId) -> Option<ClientSnapshot> {
+        let ibc_store = &self.ibc_store;
+
+        let client_state = ibc_store.client_state_store.get(
+            StoreHeight::Pending,
+            &ClientStatePath::new(client_id.clone()),
+        )?;
+
+        let mut consensus_states: Vec<_> = ibc_store
+            .consensus_state_store
+            .get_keys(&format!("clients/{client_id}/consensusStates").into())
+            .into_iter()
+            .filter_map(|path| match path.try_into() {
+                Ok(Path::ClientConsensusState(path)) => Some(path),
+                _ => None,
+            })
+            .filter_map(|path| {
+                let consensus_state = ibc_store
+                    .consensus_state_store
+                    .get(StoreHeight::Pending, &path)?;
+                let height = Height::new(path.revision_number, path.revision_height).ok()?;
+                Some(ConsensusStateSnapshot {
+                    height,
+                    consensus_state: consensus_state.into(),
+                    update_meta: ibc_store.client_update_meta(client_id, &height).ok(),
+                })
+            })
+            .collect();
+
+        consensus_states.sort_by_key(|consensus| consensus.height);
+
+        Some(ClientSnapshot {
+            client_id: client_id.clone(),
+            client_state: client_state.into(),
+            consensus_states,
+        })
+    }
+
+    /// Restores a client from a [`ClientSnapshot`] obtained through
+    /// [`Self::snapshot_client`], possibly of another context.
+    ///
+    /// The client is written on top of the existing one with the same
+    /// identifier, if any: consensus states missing from the snapshot are
+    /// kept.
+    pub fn restore_client(&mut self, snapshot: ClientSnapshot) -> Result<(), ContextError> {
+        Self::restore_client_into(&mut self.ibc_store, snapshot)
+    }
+
+    fn restore_client_into(
+        ibc_store: &mut MockIbcStore<S>,
+        snapshot: ClientSnapshot,
+    ) -> Result<(), ContextError> {
+        let client_state = AnyClientState::try_from(snapshot.client_state)?;
+        ibc_store.store_client_state(
+            ClientStatePath::new(snapshot.client_id.clone()),
+            client_state,
+        )?;
+
+        for consensus in snapshot.consensus_states {
+            let consensus_state = AnyConsensusState::try_from(consensus.consensus_state)?;
+            ibc_store.store_consensus_state(
+                ClientConsensusStatePath::new(
+                    snapshot.client_id.clone(),
+                    consensus.height.revision_number(),
+                    consensus.height.revision_height(),
+                ),
+                consensus_state,
+            )?;
+
+            if let Some((host_timestamp, host_height)) = consensus.update_meta {
+                ibc_store.store_update_meta(
+                    snapshot.client_id.clone(),
+                    consensus.height,
+                    host_timestamp,
+                    host_height,
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Exports the IBC state of the context as a [`SerializableIbcState`].
+    ///
+    /// See [`SerializableIbcState`] for the data that is left out.
+    pub fn export_state(&self) -> SerializableIbcState {
+        let ibc_store = &self.ibc_store;
+
+        let clients = ibc_store
+            .client_state_store
+            .get_keys(&"clients".to_owned().into())
+            .into_iter()
+            .filter_map(|path| match path.try_into() {
+                Ok(Path::ClientState(path)) => Some(path),
+                _ => None,
+            })
+            .filter_map(|path| self.snapshot_client(&path.0))
+            .collect();
+
+        let connections = ibc_store
+            .connection_end_store
+            .get_keys(&"connections".to_owned().into())
+            .into_iter()
+            .filter_map(|path| match path.try_into() {
+                Ok(Path::Connection(path)) => Some(path),
+                _ => None,
+            })
+            .filter_map(|path| {
+                let connection_end = ibc_store
+                    .connection_end_store
+                    .get(StoreHeight::Pending, &path)?;
+                Some(IdentifiedConnectionEnd {
+                    connection_id: path.0,
+                    connection_end,
+                })
+            })
+            .collect();
+
+        let client_connections = ibc_store
+            .connection_ids_store
+            .get_keys(&"clients".to_owned().into())
+            .into_iter()
+            .filter_map(|path| match path.try_into() {
+                Ok(Path::ClientConnection(path)) => Some(path),
+                _ => None,
+            })
+            .filter_map(|path| {
+                let connection_ids = ibc_store
+                    .connection_ids_store
+                    .get(StoreHeight::Pending, &path)?;
+                Some(ClientConnectionsSnapshot {
+                    client_id: path.0,
+                    connection_ids,
+                })
+            })
+            .collect();
+
+        let channels = ibc_store
+            .channel_end_store
+            .get_keys(&"channelEnds".to_owned().into())
+            .into_iter()
+            .filter_map(|path| match path.try_into() {
+                Ok(Path::ChannelEnd(path)) => Some(path),
+                _ => None,
+            })
+            .filter_map(|path| {
+                let channel_end = ibc_store
+                    .channel_end_store
+                    .get(StoreHeight::Pending, &path)?;
+                Some(IdentifiedChannelEnd {
+                    port_id: path.0,
+                    channel_id: path.1,
+                    channel_end,
+                })
+            })
+            .collect();
+
+        let send_sequences = ibc_store
+            .send_sequence_store
+            .get_keys(&"nextSequenceSend".to_owned().into())
+            .into_iter()
+            .filter_map(|path| match path.try_into() {
+                Ok(Path::SeqSend(path)) => Some(path),
+                _ => None,
+            })
+            .filter_map(|path| {
+                let sequence = ibc_store
+                    .send_sequence_store
+                    .get(StoreHeight::Pending, &path)?;
+                Some(SequenceSnapshot {
+                    port_id: path.0,
+                    channel_id: path.1,
+                    sequence,
+                })
+            })
+            .collect();
+
+        let recv_sequences = ibc_store
+            .recv_sequence_store
+            .get_keys(&"nextSequenceRecv".to_owned().into())
+            .into_iter()
+            .filter_map(|path| match path.try_into() {
+                Ok(Path::SeqRecv(path)) => Some(path),
+                _ => None,
+            })
+            .filter_map(|path| {
+                let sequence = ibc_store
+                    .recv_sequence_store
+                    .get(StoreHeight::Pending, &path)?;
+                Some(SequenceSnapshot {
+                    port_id: path.0,
+                    channel_id: path.1,
+                    sequence,
+                })
+            })
+            .collect();
+
+        let ack_sequences = ibc_store
+            .ack_sequence_store
+            .get_keys(&"nextSequenceAck".to_owned().into())
+            .into_iter()
+            .filter_map(|path| match path.try_into() {
+                Ok(Path::SeqAck(path)) => Some(path),
+                _ => None,
+            })
+            .filter_map(|path| {
+                let sequence = ibc_store
+                    .ack_sequence_store
+                    .get(StoreHeight::Pending, &path)?;
+                Some(SequenceSnapshot {
+                    port_id: path.0,
+                    channel_id: path.1,
+                    sequence,
+                })
+            })
+            .collect();
+
+        let packet_commitments = ibc_store
+            .packet_commitment_store
+            .get_keys(&"commitments".to_owned().into())
+            .into_iter()
+            .filter_map(|path| match path.try_into() {
+                Ok(Path::Commitment(path)) => Some(path),
+                _ => None,
+            })
+            .filter_map(|path| {
+                let commitment = ibc_store
+                    .packet_commitment_store
+                    .get(StoreHeight::Pending, &path)?;
+                Some(PacketCommitmentSnapshot {
+                    port_id: path.port_id,
+                    channel_id: path.channel_id,
+                    sequence: path.sequence,
+                    commitment,
+                })
+            })
+            .collect();
+
+        let packet_receipts = ibc_store
+            .typed_keys::<ReceiptPath>()
+            .into_iter()
+            .map(|path| PacketReceiptSnapshot {
+                port_id: path.port_id,
+                channel_id: path.channel_id,
+                sequence: path.sequence,
+            })
+            .collect();
+
+        let packet_acknowledgements = ibc_store
+            .typed_keys::<AckPath>()
+            .into_iter()
+            .filter_map(|path| {
+                let ack_commitment = ibc_store
+                    .packet_ack_store
+                    .get(StoreHeight::Pending, &path)?;
+                Some(PacketAcknowledgementSnapshot {
+                    port_id: path.port_id,
+                    channel_id: path.channel_id,
+                    sequence: path.sequence,
+                    ack_commitment,
+                })
+            })
+            .collect();
+
+        SerializableIbcState {
+            client_counter: ibc_store.client_counter().expect("no error"),
+            connection_counter: ibc_store.connection_counter().expect("no error"),
+            channel_counter: ibc_store.channel_counter().expect("no error"),
+            clients,
+            connections,
+            client_connections,
+            channels,
+            send_sequences,
+            recv_sequences,
+            ack_sequences,
+            packet_commitments,
+            packet_receipts,
+            packet_acknowledgements,
+        }
+    }
+
+    /// Imports a [`SerializableIbcState`] previously obtained through
+    /// [`Self::export_state`] into the context.
+    ///
+    /// The snapshot is written on top of the existing IBC state, so it is
+    /// meant to be imported into a freshly created context.
+    pub fn import_state(&mut self, state: SerializableIbcState) {
+        let ibc_store = &mut self.ibc_store;
+
+        ibc_store
+            .client_counter
+            .set(NextClientSequencePath, state.client_counter)
+            .expect("error writing to store");
+        ibc_store
+            .conn_counter
+            .set(NextConnectionSequencePath, state.connection_counter)
+            .expect("error writing to store");
+        ibc_store
+            .channel_counter
+            .set(NextChannelSequencePath, state.channel_counter)
+            .expect("error writing to store");
+
+        for client in state.clients {
+            Self::restore_client_into(ibc_store, client).expect("valid client snapshot");
+        }
+
+        for connection in state.connections {
+            ibc_store
+                .store_connection(
+                    &ConnectionPath::new(&connection.connection_id),
+                    connection.connection_end,
+                )
+                .expect("error writing to store");
+        }
+
+        for client_connections in state.client_connections {
+            ibc_store
+                .connection_ids_store
+                .set(
+                    ClientConnectionPath::new(client_connections.client_id),
+                    client_connections.connection_ids,
+                )
+                .expect("error writing to store");
+        }
+
+        for channel in state.channels {
+            ibc_store
+                .store_channel(
+                    &ChannelEndPath::new(&channel.port_id, &channel.channel_id),
+                    channel.channel_end,
+                )
+                .expect("error writing to store");
+        }
+
+        for seq in state.send_sequences {
+            ibc_store
+                .store_next_sequence_send(
+                    &SeqSendPath::new(&seq.port_id, &seq.channel_id),
+                    seq.sequence,
+                )
+                .expect("error writing to store");
+        }
+
+        for seq in state.recv_sequences {
+            ibc_store
+                .store_next_sequence_recv(
+                    &SeqRecvPath::new(&seq.port_id, &seq.channel_id),
+                    seq.sequence,
+                )
+                .expect("error writing to store");
+        }
+
+        for seq in state.ack_sequences {
+            ibc_store
+                .store_next_sequence_ack(
+                    &SeqAckPath::new(&seq.port_id, &seq.channel_id),
+                    seq.sequence,
+                )
+                .expect("error writing to store");
+        }
+
+        for packet_commitment in state.packet_commitments {
+            ibc_store
+                .store_packet_commitment(
+                    &CommitmentPath::new(
+                        &packet_commitment.port_id,
+                        &packet_commitment.channel_id,
+                        packet_commitment.sequence,
+                    ),
+                    packet_commitment.commitment,
+                )
+                .expect("error writing to store");
+        }
+
+        for receipt in state.packet_receipts {
+            ibc_store
+                .store_packet_receipt(
+                    &ReceiptPath::new(&receipt.port_id, &receipt.channel_id, receipt.sequence),
+                    Receipt::Ok,
+                )
+                .expect("error writing to store");
+        }
+
+        for ack in state.packet_acknowledgements {
+            ibc_store
+                .store_packet_acknowledgement(
+                    &AckPath::new(&ack.port_id, &ack.channel_id, ack.sequence),
+                    ack.ack_commitment,
+                )
+                .expect("error writing to store");
+        }
+    }
+
+    /// Compares the IBC state of the context with the one of `other` and
+    /// returns a human-readable description of every differing entry, e.g. a
+    /// client missing from one side or a consensus state that differs.
+    ///
+    /// The comparison covers the data of [`SerializableIbcState`], hence the
+    /// events, logs and host chain are ignored. An empty result means both
+    /// states are identical.
+    pub fn diff(&self, other: &Self) -> Vec<String> {
+        let this = self.export_state();
+        let that = other.export_state();
+
+        let mut diffs = Vec::new();
+
+        for (name, this_counter, that_counter) in [
+            ("client", this.client_counter, that.client_counter),
+            (
+                "connection",
+                this.connection_counter,
+                that.connection_counter,
+            ),
+            ("channel", this.channel_counter, that.channel_counter),
+        ] {
+            if this_counter != that_counter {
+                diffs.push(format!(
+                    "{name} counter differs: {this_counter} != {that_counter}"
+                ));
+            }
+        }
+
+        let client_states = |state: &SerializableIbcState| {
+            state
+                .clients
+                .iter()
+                .map(|client| {
+                    (
+                        format!("client `{}`", client.client_id),
+                        client.client_state.clone(),
+                    )
+                })
+                .collect()
+        };
+        diff_entries(&mut diffs, client_states(&this), client_states(&that));
+
+        let consensus_states = |state: &SerializableIbcState| {
+            state
+                .clients
+                .iter()
+                .flat_map(|client| {
+                    client.consensus_states.iter().map(|consensus| {
+                        (
+                            format!(
+                                "consensus state of client `{}` at height {}",
+                                client.client_id, consensus.height
+                            ),
+                            (consensus.consensus_state.clone(), consensus.update_meta),
+                        )
+                    })
+                })
+                .collect()
+        };
+        diff_entries(&mut diffs, consensus_states(&this), consensus_states(&that));
+
+        let connections = |state: &SerializableIbcState| {
+            state
+                .connections
+                .iter()
+                .map(|connection| {
+                    (
+                        format!("connection `{}`", connection.connection_id),
+                        connection.connection_end.clone(),
+                    )
+                })
+                .collect()
+        };
+        diff_entries(&mut diffs, connections(&this), connections(&that));
+
+        let client_connections = |state: &SerializableIbcState| {
+            state
+                .client_connections
+                .iter()
+                .map(|client_connections| {
+                    (
+                        format!("connections of client `{}`", client_connections.client_id),
+                        client_connections.connection_ids.clone(),
+                    )
+                })
+                .collect()
+        };
+        diff_entries(
+            &mut diffs,
+            client_connections(&this),
+            client_connections(&that),
+        );
+
+        let channels = |state: &SerializableIbcState| {
+            state
+                .channels
+                .iter()
+                .map(|channel| {
+                    (
+                        format!("channel `{}/{}`", channel.port_id, channel.channel_id),
+                        channel.channel_end.clone(),
+                    )
+                })
+                .collect()
+        };
+        diff_entries(&mut diffs, channels(&this), channels(&that));
+
+        let sequences = |kind: &str, sequences: &[SequenceSnapshot]| {
+            sequences
+                .iter()
+                .map(|seq| {
+                    (
+                        format!(
+                            "next {kind} sequence of channel `{}/{}`",
+                            seq.port_id, seq.channel_id
+                        ),
+                        seq.sequence,
+                    )
+                })
+                .collect()
+        };
+        diff_entries(
+            &mut diffs,
+            sequences("send", &this.send_sequences),
+            sequences("send", &that.send_sequences),
+        );
+        diff_entries(
+            &mut diffs,
+            sequences("receive", &this.recv_sequences),
+            sequences("receive", &that.recv_sequences),
+        );
+        diff_entries(
+            &mut diffs,
+            sequences("ack", &this.ack_sequences),
+            sequences("ack", &that.ack_sequences),
+        );
+
+        let packet_commitments = |state: &SerializableIbcState| {
+            state
+                .packet_commitments
+                .iter()
+                .map(|commitment| {
+                    (
+                        format!(
+                            "packet commitment of channel `{}/{}` at sequence {}",
+                            commitment.port_id, commitment.channel_id, commitment.sequence
+                        ),
+                        commitment.commitment.clone(),
+                    )
+                })
+                .collect()
+        };
+        diff_entries(
+            &mut diffs,
+            packet_commitments(&this),
+            packet_commitments(&that),
+        );
+
+        let packet_receipts = |state: &SerializableIbcState| {
+            state
+                .packet_receipts
+                .iter()
+                .map(|receipt| {
+                    (
+                        format!(
+                            "packet receipt of channel `{}/{}` at sequence {}",
+                            receipt.port_id, receipt.channel_id, receipt.sequence
+                        ),
+                        (),
+                    )
+                })
+                .collect()
+        };
+        diff_entries(&mut diffs, packet_receipts(&this), packet_receipts(&that));
+
+        let packet_acknowledgements = |state: &SerializableIbcState| {
+            state
+                .packet_acknowledgements
+                .iter()
+                .map(|ack| {
+                    (
+                        format!(
+                            "packet acknowledgement of channel `{}/{}` at sequence {}",
+                            ack.port_id, ack.channel_id, ack.sequence
+                        ),
+                        ack.ack_commitment.clone(),
+                    )
+                })
+                .collect()
+        };
+        diff_entries(
+            &mut diffs,
+            packet_acknowledgements(&this),
+            packet_acknowledgements(&that),
+        );
+
+        diffs
+    }
+
+    /// Calls [`validate`] function on [`MsgEnvelope`] using the context's IBC store and router.
     pub fn validate(&mut self, msg: MsgEnvelope) -> Result<(), ContextError> {
+        self.record_call("validate", || envelope_args(&msg));
         validate(&self.ibc_store, &self.ibc_router, msg)
     }
 
-    /// Calls [`execute`] function on [`MsgEnvelope`] using the context's IBC store and router.
-    pub fn execute(&mut self, msg: MsgEnvelope) -> Result<(), ContextError> {
-        execute(&mut self.ibc_store, &mut self.ibc_router, msg)
+    /// Calls [`execute`] function on [`MsgEnvelope`] using the context's IBC store and router.
+    pub fn execute(&mut self, msg: MsgEnvelope) -> Result<(), ContextError> {
+        self.record_call("execute", || envelope_args(&msg));
+        execute(&mut self.ibc_store, &mut self.ibc_router, msg)
+    }
+
+    /// Calls [`dispatch`] function on [`MsgEnvelope`] using the context's IBC store and router.
+    pub fn dispatch(&mut self, msg: MsgEnvelope) -> Result<(), ContextError> {
+        self.record_call("dispatch", || envelope_args(&msg));
+        dispatch(&mut self.ibc_store, &mut self.ibc_router, msg)
+    }
+
+    /// Calls [`send_packet`] function on [`Packet`] using the context's IBC store.
+    pub fn send_packet(&mut self, packet: Packet) -> Result<SendPacketResult, ContextError> {
+        self.record_call("send_packet", || {
+            vec![
+                ("port_id".to_string(), packet.port_id_on_a.to_string()),
+                ("channel_id".to_string(), packet.chan_id_on_a.to_string()),
+                ("sequence".to_string(), packet.seq_on_a.to_string()),
+            ]
+        });
+        send_packet(&mut self.ibc_store, packet)
+    }
+
+    /// Replays the scenario described by the given JSON, e.g. to reproduce a
+    /// reported bug, and returns the events emitted along the way.
+    ///
+    /// The scenario is a JSON array of [`ScenarioStep`]s, run in order:
+    /// messages go through [`dispatch`](Self::dispatch) and packets through
+    /// [`send_packet`](Self::send_packet). The replay stops at the first
+    /// failing step, leaving the effects of the previous ones in place. A
+    /// scenario which cannot be parsed, or a step which cannot be decoded,
+    /// fails with `RouterError::MalformedMessageBytes`.
+    #[cfg(feature = "serde")]
+    pub fn replay_scenario(&mut self, json: &str) -> Result<Vec<IbcEvent>, ContextError> {
+        use ibc::core::router::types::error::RouterError;
+
+        let steps: Vec<ScenarioStep> =
+            serde_json::from_str(json).map_err(|e| RouterError::MalformedMessageBytes {
+                reason: e.to_string(),
+            })?;
+
+        let first_event = self.ibc_store.events.lock().len();
+
+        for step in steps {
+            match step {
+                ScenarioStep::Message { type_url, value } => {
+                    let value = decode_scenario_hex(&value)?;
+                    self.dispatch(MsgEnvelope::try_from(Any { type_url, value })?)?;
+                }
+                ScenarioStep::SendPacket { packet } => {
+                    self.send_packet(packet.try_into()?)?;
+                }
+                ScenarioStep::AdvanceBlock => self.advance_block_height(),
+            }
+        }
+
+        Ok(self.ibc_store.events.lock()[first_event..].to_vec())
+    }
+
+    /// Runs the given closure with a [`SharedIbcStore`] wrapping the IBC store
+    /// of the context, e.g. to call handlers from several threads spawned with
+    /// `std::thread::scope`. The store is only borrowed for the duration of
+    /// the closure, hence it stays in the context even if the closure panics.
+    ///
+    /// Only the IBC store is shared: the router is left out, as IBC modules
+    /// are not required to be `Send` or `Sync`.
+    pub fn with_shared_ibc_store<R>(&mut self, f: impl FnOnce(&SharedIbcStore<'_, S>) -> R) -> R {
+        let shared_ibc_store = SharedIbcStore {
+            ibc_store: Mutex::new(&mut self.ibc_store),
+        };
+
+        f(&shared_ibc_store)
+    }
+
+    /// Enables or disables the recording of the handler calls made through the
+    /// context. Disabling it discards the recorded calls.
+    pub fn record_calls(&mut self, enabled: bool) {
+        match (enabled, &self.handler_calls) {
+            (true, None) => self.handler_calls = Some(Vec::new()),
+            (false, _) => self.handler_calls = None,
+            (true, Some(_)) => {}
+        }
+    }
+
+    /// Returns the handler calls recorded since recording was enabled.
+    pub fn call_log(&self) -> &[HandlerCall] {
+        self.handler_calls.as_deref().unwrap_or_default()
+    }
+
+    fn record_call(&mut self, handler: &str, args: impl FnOnce() -> Vec<(String, String)>) {
+        if let Some(handler_calls) = &mut self.handler_calls {
+            handler_calls.push(HandlerCall {
+                handler: handler.to_string(),
+                args: args(),
+            });
+        }
+    }
+
+    /// A datagram passes from the relayer to the IBC module (on host chain).
+    /// Alternative method to `Ics18Context::send` that does not exercise any serialization.
+    /// Used in testing the Ics18 algorithms, hence this may return a Ics18Error.
+    pub fn deliver(&mut self, msg: MsgEnvelope) -> Result<(), RelayerError> {
+        self.dispatch(msg)
+            .map_err(RelayerError::TransactionFailed)?;
+        // Create a new block.
+        self.advance_block_height();
+        Ok(())
+    }
+
+    /// Returns all the events that have been emitted by the context's IBC store.
+    pub fn get_events(&self) -> Vec<IbcEvent> {
+        self.ibc_store.events.lock().clone()
+    }
+
+    /// Returns the first event emitted by the context's IBC store which
+    /// satisfies the given predicate.
+    ///
+    /// The events are kept behind a lock, hence the lookup helpers return
+    /// copies of them.
+    pub fn find_event(&self, pred: impl Fn(&IbcEvent) -> bool) -> Option<IbcEvent> {
+        self.ibc_store
+            .events
+            .lock()
+            .iter()
+            .find(|event| pred(event))
+            .cloned()
+    }
+
+    /// Returns the events emitted by the context's IBC store of the given
+    /// type, as per [`IbcEvent::event_type`], e.g. `send_packet`.
+    pub fn events_of_kind(&self, event_type: &str) -> Vec<IbcEvent> {
+        self.ibc_store
+            .events
+            .lock()
+            .iter()
+            .filter(|event| event.event_type() == event_type)
+            .cloned()
+            .collect()
+    }
+
+    /// Returns the last send packet event emitted by the context's IBC store.
+    pub fn last_send_packet(&self) -> Option<SendPacket> {
+        self.ibc_store
+            .events
+            .lock()
+            .iter()
+            .rev()
+            .find_map(|event| match event {
+                IbcEvent::SendPacket(event) => Some(event.clone()),
+                _ => None,
+            })
+    }
+
+    /// Asserts that the context's IBC store has emitted exactly `n` events.
+    ///
+    /// # Panics
+    ///
+    /// Panics listing the emitted events otherwise.
+    pub fn assert_event_count(&self, n: usize) {
+        let events = self.get_events();
+
+        assert_eq!(
+            events.len(),
+            n,
+            "expected {n} events, but {} were emitted: {events:#?}",
+            events.len()
+        );
+    }
+
+    /// Asserts that the context's IBC store has not emitted any event.
+    ///
+    /// # Panics
+    ///
+    /// Panics listing the emitted events, if any.
+    pub fn assert_no_events(&self) {
+        let events = self.get_events();
+
+        assert!(
+            events.is_empty(),
+            "expected no events, but {} were emitted: {events:#?}",
+            events.len()
+        );
+    }
+
+    /// Checks that every event emitted by the context's IBC store is
+    /// well-formed, returning a description of each violation otherwise.
+    ///
+    /// Identifiers and heights are valid by construction, hence only the
+    /// invariants the event types do not enforce are checked:
+    /// - update client events list their consensus height among their
+    ///   consensus heights,
+    /// - packet events carry a non-zero sequence and at least one timeout,
+    /// - module events have a kind and no attribute with an empty key.
+    pub fn validate_events(&self) -> Result<(), Vec<String>> {
+        let violations: Vec<String> = self
+            .get_events()
+            .iter()
+            .enumerate()
+            .flat_map(|(index, event)| {
+                event_violations(event)
+                    .into_iter()
+                    .map(move |violation| format!("event #{index}: {violation}"))
+            })
+            .collect();
+
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(violations)
+        }
+    }
+
+    /// Forwards each event emitted by the context's IBC store to the given
+    /// sink as it is produced, replacing any previous sink. The events are
+    /// still recorded, see [`Self::get_events`].
+    pub fn set_event_sink(&mut self, sink: impl Fn(&IbcEvent) + Send + Sync + 'static) {
+        *self.ibc_store.event_sink.lock() = Some(EventSink::new(sink));
+    }
+
+    /// Returns all the logs that have been emitted by the context's IBC store.
+    pub fn get_logs(&self) -> Vec<String> {
+        self.ibc_store.logs.lock().clone()
+    }
+}
+
+/// Returns the invariants of the given event that do not hold, see
+/// [`StoreGenericTestContext::validate_events`].
+fn event_violations(event: &IbcEvent) -> Vec<String> {
+    let mut violations = Vec::new();
+
+    let mut check_packet = |kind: &str,
+                            sequence: &Sequence,
+                            timeout_height: &TimeoutHeight,
+                            timeout_timestamp: &Timestamp| {
+        if sequence.is_zero() {
+            violations.push(format!("{kind} event has a zero sequence"));
+        }
+        if !timeout_height.is_set() && !timeout_timestamp.is_set() {
+            violations.push(format!(
+                "{kind} event of sequence {sequence} has neither a timeout height nor a timeout timestamp"
+            ));
+        }
+    };
+
+    match event {
+        IbcEvent::SendPacket(e) => check_packet(
+            "send packet",
+            e.seq_on_a(),
+            e.timeout_height_on_b(),
+            e.timeout_timestamp_on_b(),
+        ),
+        IbcEvent::ReceivePacket(e) => check_packet(
+            "receive packet",
+            e.seq_on_b(),
+            e.timeout_height_on_b(),
+            e.timeout_timestamp_on_b(),
+        ),
+        IbcEvent::WriteAcknowledgement(e) => check_packet(
+            "write acknowledgement",
+            e.seq_on_a(),
+            e.timeout_height_on_b(),
+            e.timeout_timestamp_on_b(),
+        ),
+        IbcEvent::AcknowledgePacket(e) => check_packet(
+            "acknowledge packet",
+            e.seq_on_a(),
+            e.timeout_height_on_b(),
+            e.timeout_timestamp_on_b(),
+        ),
+        IbcEvent::TimeoutPacket(e) => check_packet(
+            "timeout packet",
+            e.seq_on_a(),
+            e.timeout_height_on_b(),
+            e.timeout_timestamp_on_b(),
+        ),
+        IbcEvent::UpdateClient(e) => {
+            if !e.consensus_heights().contains(e.consensus_height()) {
+                violations.push(format!(
+                    "update client event of `{}` does not list its consensus height {} among its consensus heights",
+                    e.client_id(),
+                    e.consensus_height()
+                ));
+            }
+        }
+        IbcEvent::Module(e) => {
+            if e.kind.is_empty() {
+                violations.push("module event has an empty kind".to_string());
+            }
+            if e.attributes
+                .iter()
+                .any(|attribute| attribute.key.is_empty())
+            {
+                violations.push(format!(
+                    "module event `{}` has an attribute with an empty key",
+                    e.kind
+                ));
+            }
+        }
+        _ => {}
+    }
+
+    violations
+}
+
+/// Returns the key arguments of a [`MsgEnvelope`] for the handler call log.
+fn envelope_args(msg: &MsgEnvelope) -> Vec<(String, String)> {
+    let kind = match msg {
+        MsgEnvelope::Client(_) => "client",
+        MsgEnvelope::Connection(_) => "connection",
+        MsgEnvelope::Channel(_) => "channel",
+        MsgEnvelope::Packet(_) => "packet",
+    };
+
+    vec![("message".to_string(), kind.to_string())]
+}
+
+/// A handler call run against a [`MockContext`] by [`assert_frozen_client_rejected`].
+pub type ClientOperation<'a> = Box<dyn FnOnce(&mut MockContext) -> Result<(), ContextError> + 'a>;
+
+/// Freezes the given client, then runs each of the named operations against
+/// the context and asserts that every one of them is rejected with
+/// [`ClientError::ClientNotActive`], e.g. to check that all the handlers
+/// relying on a client refuse to operate on it once frozen.
+///
+/// The client is frozen through [`MockContext::set_client_status`], hence it
+/// must be of the mock type. The operations run one after the other on the
+/// same context.
+///
+/// # Panics
+///
+/// Panics naming the first operation which is not rejected as expected.
+pub fn assert_frozen_client_rejected<'a>(
+    ctx: &mut MockContext,
+    client_id: &ClientId,
+    operations: impl IntoIterator<Item = (&'a str, ClientOperation<'a>)>,
+) {
+    ctx.set_client_status(client_id, Status::Frozen);
+
+    for (name, operation) in operations {
+        let res = operation(ctx);
+
+        assert!(
+            matches!(
+                res,
+                Err(ContextError::ClientError(ClientError::ClientNotActive {
+                    status: Status::Frozen
+                }))
+            ),
+            "{name} must reject the frozen client `{client_id}`, got: {res:?}"
+        );
+    }
+}
+
+/// Runs `run` against two contexts built by `build_ctx` and asserts that both
+/// end up with the same events and byte-identical IBC stores, e.g. to catch a
+/// handler depending on the iteration order of a hash map.
+///
+/// The stores are compared through their root hashes, hence `build_ctx` must
+/// itself be deterministic: two calls must return contexts with the same state.
+///
+/// # Panics
+///
+/// Panics if the events or the store root hashes of the two runs differ.
+pub fn assert_deterministic(build_ctx: impl Fn() -> MockContext, run: impl Fn(&mut MockContext)) {
+    let run_once = || {
+        let mut ctx = build_ctx();
+        run(&mut ctx);
+
+        let events = ctx.get_events();
+        let root_hash = ctx.ibc_store.end_block().expect("no error");
+
+        (events, root_hash)
+    };
+
+    let (events_1, root_hash_1) = run_once();
+    let (events_2, root_hash_2) = run_once();
+
+    assert_eq!(events_1, events_2, "the two runs emitted different events");
+    assert_eq!(
+        root_hash_1, root_hash_2,
+        "the two runs left the IBC store in different states"
+    );
+}
+
+/// Appends to `diffs` a line for every entry that is missing from one of the
+/// two sides or whose values differ.
+fn diff_entries<V: PartialEq>(
+    diffs: &mut Vec<String>,
+    this: BTreeMap<String, V>,
+    mut that: BTreeMap<String, V>,
+) {
+    for (entry, value) in this {
+        match that.remove(&entry) {
+            Some(other_value) if other_value == value => {}
+            Some(_) => diffs.push(format!("{entry} differs")),
+            None => diffs.push(format!("{entry} is missing from the other context")),
+        }
+    }
+
+    for entry in that.into_keys() {
+        diffs.push(format!("{entry} is missing from this context"));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::ops::Bound;
+
+    use ibc::clients::tendermint::types::client_type as tm_client_type;
+    use ibc::core::channel::types::channel::{Counterparty, Order, State};
+
+    use super::*;
+    use crate::fixtures::core::connection::ConnectionEndConfig;
+    use crate::hosts::{HostConsensusState, MockHost, TendermintHost};
+    use crate::testapp::ibc::clients::mock::client_state::client_type as mock_client_type;
+    #[cfg(feature = "serde")]
+    use crate::testapp::ibc::core::client_ctx::MockClientRecord;
+    use crate::testapp::ibc::core::types::{DefaultIbcStore, LightClientBuilder};
+
+    /// Returns an open unordered channel end on `connection-0`, whose
+    /// counterparty is `transfer/channel-0`.
+    fn open_channel_end() -> ChannelEnd {
+        ChannelEnd::new(
+            State::Open,
+            Order::Unordered,
+            Counterparty::new(PortId::transfer(), Some(ChannelId::zero())),
+            vec![ConnectionId::zero()],
+            ChannelVersion::new("ics20-1".to_string()),
+        )
+        .expect("no error")
+    }
+
+    /// Bootstraps the open connection `connection-0` on the given client, with
+    /// the open channel `transfer/channel-0` on top of it, sending from
+    /// sequence 1.
+    fn with_open_channel<H>(ctx: TestContext<H>, client_id: &ClientId) -> TestContext<H>
+    where
+        H: TestHost,
+        HostClientState<H>: ClientStateValidation<DefaultIbcStore>,
+    {
+        let conn_end = ConnectionEndConfig::builder()
+            .client_id(client_id.clone())
+            .build();
+
+        ctx.with_connection(ConnectionId::zero(), conn_end)
+            .with_channel(PortId::transfer(), ChannelId::zero(), open_channel_end())
+            .with_send_sequence(PortId::transfer(), ChannelId::zero(), 1.into())
+    }
+
+    #[test]
+    #[should_panic(expected = "at least one height must be given")]
+    fn test_with_client_at_heights_rejects_no_height() {
+        let client_id = mock_client_type().build_client_id(0);
+
+        let _ = MockContext::default().with_client_at_heights(&client_id, &[]);
+    }
+
+    #[test]
+    fn test_with_client_at_heights() {
+        let client_id = mock_client_type().build_client_id(0);
+        let heights = [1, 5, 10].map(|h| Height::new(0, h).expect("Never fails"));
+
+        let ctx = MockContext::default().with_client_at_heights(&client_id, &heights);
+
+        assert_eq!(ctx.light_client_latest_height(&client_id), heights[2]);
+
+        let next_consensus_state = ctx
+            .ibc_store
+            .next_consensus_state(&client_id, &heights[0])
+            .expect("no error")
+            .expect("consensus state exists");
+
+        assert_eq!(
+            next_consensus_state,
+            MockConsensusState::new(
+                MockHeader::new(heights[1]).with_timestamp(ctx.latest_timestamp())
+            )
+            .into()
+        );
+    }
+
+    #[test]
+    fn test_next_and_prev_consensus_state() {
+        let client_id = mock_client_type().build_client_id(0);
+        // The store orders the keys lexicographically, e.g. height 10 before 9.
+        let heights = [1, 2, 9, 10, 11, 20, 100].map(|h| Height::new(0, h).expect("Never fails"));
+
+        let ctx = MockContext::default().with_client_at_heights(&client_id, &heights);
+
+        let consensus_states = ctx.consensus_states(&client_id).expect("client exists");
+
+        for query in 1..=101 {
+            let height = Height::new(0, query).expect("Never fails");
+
+            let expected_next = consensus_states
+                .range((Bound::Excluded(height), Bound::Unbounded))
+                .next()
+                .map(|(_, consensus_state)| consensus_state.clone());
+            let expected_prev = consensus_states
+                .range(..height)
+                .next_back()
+                .map(|(_, consensus_state)| consensus_state.clone());
+
+            assert_eq!(
+                ctx.ibc_store
+                    .next_consensus_state(&client_id, &height)
+                    .expect("no error"),
+                expected_next,
+                "next consensus state after {height}"
+            );
+            assert_eq!(
+                ctx.ibc_store
+                    .prev_consensus_state(&client_id, &height)
+                    .expect("no error"),
+                expected_prev,
+                "previous consensus state before {height}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_with_consensus_states() {
+        let client_id = mock_client_type().build_client_id(0);
+        let genesis_timestamp = MockContext::default().latest_timestamp();
+
+        let states: BTreeMap<_, _> = [3, 1, 12, 5, 10, 2, 9, 7, 11, 4, 8, 6]
+            .into_iter()
+            .map(|h| {
+                let height = Height::new(0, h).expect("Never fails");
+                let timestamp = (genesis_timestamp + Duration::from_secs(h)).expect("no overflow");
+                let consensus_state: AnyConsensusState =
+                    MockConsensusState::new(MockHeader::new(height).with_timestamp(timestamp))
+                        .into();
+                (height, consensus_state)
+            })
+            .collect();
+
+        let ctx = MockContext::default().with_consensus_states(&client_id, states.clone());
+
+        let latest_height = Height::new(0, 12).expect("Never fails");
+        let client_state = ctx
+            .ibc_store
+            .client_state(&client_id)
+            .expect("client exists");
+        assert_eq!(client_state.latest_height(), latest_height);
+        assert_eq!(ctx.consensus_states(&client_id), Some(states.clone()));
+
+        for query in 1..=13 {
+            let height = Height::new(0, query).expect("Never fails");
+
+            assert_eq!(
+                ctx.ibc_store
+                    .next_consensus_state(&client_id, &height)
+                    .expect("no error"),
+                states
+                    .range((Bound::Excluded(height), Bound::Unbounded))
+                    .next()
+                    .map(|(_, consensus_state)| consensus_state.clone()),
+                "next consensus state after {height}"
+            );
+            assert_eq!(
+                ctx.ibc_store
+                    .prev_consensus_state(&client_id, &height)
+                    .expect("no error"),
+                states
+                    .range(..height)
+                    .next_back()
+                    .map(|(_, consensus_state)| consensus_state.clone()),
+                "previous consensus state before {height}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_consensus_state_heights_paginated() {
+        let client_id = mock_client_type().build_client_id(0);
+        let heights = [1, 2, 9, 10, 11].map(|h| Height::new(0, h).expect("Never fails"));
+
+        let ctx = MockContext::default().with_client_at_heights(&client_id, &heights);
+
+        let mut pages = Vec::new();
+        let mut start_after = None;
+        loop {
+            let (page, next) = ctx
+                .ibc_store
+                .consensus_state_heights_paginated(&client_id, start_after, 2)
+                .expect("no error");
+            pages.push(page);
+            match next {
+                Some(cursor) => start_after = Some(cursor),
+                None => break,
+            }
+        }
+
+        assert_eq!(
+            pages,
+            vec![
+                heights[0..2].to_vec(),
+                heights[2..4].to_vec(),
+                heights[4..].to_vec()
+            ]
+        );
+
+        let (page, next) = ctx
+            .ibc_store
+            .consensus_state_heights_paginated(&client_id, Some(heights[4]), 2)
+            .expect("no error");
+        assert!(page.is_empty());
+        assert_eq!(next, None);
+    }
+
+    #[test]
+    fn test_diff() {
+        let client_id = mock_client_type().build_client_id(0);
+        let heights = |revision_heights: &[u64]| {
+            revision_heights
+                .iter()
+                .map(|&h| Height::new(0, h).expect("Never fails"))
+                .collect::<Vec<_>>()
+        };
+
+        let ctx_a =
+            MockContext::default().with_client_at_heights(&client_id, &heights(&[1, 5, 10]));
+        let ctx_b = MockContext::default().with_client_at_heights(&client_id, &heights(&[1, 10]));
+
+        assert!(ctx_a.diff(&ctx_a).is_empty());
+        assert_eq!(
+            ctx_a.diff(&ctx_b),
+            vec![format!(
+                "consensus state of client `{client_id}` at height 0-5 is missing from the other context"
+            )]
+        );
+        assert_eq!(ctx_b.diff(&ctx_a).len(), 1);
+    }
+
+    #[test]
+    fn test_diff_packet_receipts_and_acknowledgements() {
+        let mut ctx_a = MockContext::default();
+        let ctx_b = MockContext::default();
+
+        ctx_a
+            .ibc_store
+            .store_packet_receipt(
+                &ReceiptPath::new(&PortId::transfer(), &ChannelId::zero(), 1.into()),
+                Receipt::Ok,
+            )
+            .expect("no error");
+        ctx_a
+            .ibc_store
+            .store_packet_acknowledgement(
+                &AckPath::new(&PortId::transfer(), &ChannelId::zero(), 2.into()),
+                vec![1, 2, 3].into(),
+            )
+            .expect("no error");
+
+        assert_eq!(
+            ctx_a.diff(&ctx_b),
+            vec![
+                "packet receipt of channel `transfer/channel-0` at sequence 1 is missing from the other context".to_string(),
+                "packet acknowledgement of channel `transfer/channel-0` at sequence 2 is missing from the other context".to_string(),
+            ]
+        );
+
+        let mut ctx_c = MockContext::default();
+        ctx_c.import_state(ctx_a.export_state());
+        assert!(ctx_a.diff(&ctx_c).is_empty());
+    }
+
+    #[test]
+    fn test_rewind_to() {
+        let client_id = mock_client_type().build_client_id(0);
+        let client_heights = [1, 5, 10].map(|h| Height::new(0, h).expect("Never fails"));
+
+        let mut ctx = MockContext::default().with_client_at_heights(&client_id, &client_heights);
+
+        let target_height = Height::new(0, 5).expect("Never fails");
+        assert_eq!(ctx.latest_height(), target_height);
+        let target_timestamp = ctx.latest_timestamp();
+
+        ctx = ctx.advance_block_up_to_height(Height::new(0, 10).expect("Never fails"));
+
+        let receipt_path = ReceiptPath::new(&PortId::transfer(), &ChannelId::zero(), 1.into());
+        ctx.ibc_store
+            .store_packet_receipt(&receipt_path, Receipt::Ok)
+            .expect("no error");
+        ctx.halt(Duration::from_secs(60));
+
+        ctx.rewind_to(target_height);
+
+        assert_eq!(ctx.latest_height(), target_height);
+        assert_eq!(ctx.latest_timestamp(), target_timestamp);
+        assert_eq!(
+            ctx.ibc_store.host_timestamp().expect("no error"),
+            target_timestamp
+        );
+        assert!(ctx.host.get_block(&target_height.increment()).is_none());
+        assert!(ctx
+            .ibc_store
+            .host_consensus_state(&target_height.increment())
+            .is_err());
+        assert!(ctx.ibc_store.host_consensus_state(&target_height).is_ok());
+
+        let consensus_heights: Vec<Height> = ctx
+            .consensus_states(&client_id)
+            .expect("client exists")
+            .into_keys()
+            .collect();
+        assert_eq!(consensus_heights, client_heights[..2]);
+
+        // the state not indexed by height is kept
+        assert!(matches!(
+            ctx.ibc_store.get_packet_receipt(&receipt_path),
+            Ok(Receipt::Ok)
+        ));
+
+        // the chain keeps going from the rewound height
+        ctx.advance_block_height();
+        assert_eq!(ctx.latest_height(), target_height.increment());
+    }
+
+    #[test]
+    fn test_client_update_meta_heights() {
+        let client_id = mock_client_type().build_client_id(0);
+        let other_client_id = mock_client_type().build_client_id(1);
+        let heights = [1, 3, 10].map(|h| Height::new(0, h).expect("Never fails"));
+
+        let mut ctx = MockContext::default();
+
+        let host_timestamp = ctx.latest_timestamp();
+        let host_height = ctx.latest_height();
+
+        for height in heights {
+            ctx.ibc_store
+                .store_update_meta(client_id.clone(), height, host_timestamp, host_height)
+                .expect("no error");
+        }
+        ctx.ibc_store
+            .store_update_meta(
+                other_client_id.clone(),
+                heights[1],
+                host_timestamp,
+                host_height,
+            )
+            .expect("no error");
+
+        // sorted by height, unlike the keys of the store
+        assert_eq!(
+            ctx.ibc_store
+                .client_update_meta_heights(&client_id)
+                .expect("no error"),
+            heights.to_vec()
+        );
+
+        ctx.ibc_store
+            .delete_update_meta(client_id.clone(), heights[1])
+            .expect("no error");
+
+        assert_eq!(
+            ctx.ibc_store
+                .client_update_meta_heights(&client_id)
+                .expect("no error"),
+            vec![heights[0], heights[2]]
+        );
+        assert_eq!(
+            ctx.ibc_store
+                .client_update_meta_heights(&other_client_id)
+                .expect("no error"),
+            vec![heights[1]]
+        );
     }
 
-    /// Calls [`dispatch`] function on [`MsgEnvelope`] using the context's IBC store and router.
-    pub fn dispatch(&mut self, msg: MsgEnvelope) -> Result<(), ContextError> {
-        dispatch(&mut self.ibc_store, &mut self.ibc_router, msg)
+    #[test]
+    fn test_consensus_state_path_with_zero_height() {
+        let client_id = mock_client_type().build_client_id(0);
+        let mut ctx = MockContext::default()
+            .with_client_at_heights(&client_id, &[Height::new(0, 1).expect("Never fails")]);
+
+        // revision height 0 maps to no valid height
+        let path = ClientConsensusStatePath::new(client_id.clone(), 0, 0);
+        let consensus_state: AnyConsensusState =
+            MockConsensusState::new(MockHeader::new(Height::min(0))).into();
+
+        assert!(matches!(
+            ctx.ibc_store
+                .store_consensus_state(path.clone(), consensus_state),
+            Err(ContextError::ClientError(ClientError::InvalidHeight))
+        ));
+        assert!(matches!(
+            ctx.ibc_store.delete_consensus_state(path.clone()),
+            Err(ContextError::ClientError(ClientError::InvalidHeight))
+        ));
+        assert!(matches!(
+            ctx.ibc_store.consensus_state(&path),
+            Err(ContextError::ClientError(ClientError::InvalidHeight))
+        ));
     }
 
-    /// A datagram passes from the relayer to the IBC module (on host chain).
-    /// Alternative method to `Ics18Context::send` that does not exercise any serialization.
-    /// Used in testing the Ics18 algorithms, hence this may return a Ics18Error.
-    pub fn deliver(&mut self, msg: MsgEnvelope) -> Result<(), RelayerError> {
-        self.dispatch(msg)
-            .map_err(RelayerError::TransactionFailed)?;
-        // Create a new block.
-        self.advance_block_height();
-        Ok(())
+    #[test]
+    fn test_strict_update_meta_rejects_time_travel() {
+        let client_id = mock_client_type().build_client_id(0);
+        let heights = [1, 5].map(|h| Height::new(0, h).expect("Never fails"));
+
+        let mut ctx = MockContext::default()
+            .with_client_at_heights(&client_id, &heights)
+            .with_strict_update_meta(true);
+
+        ctx.advance_block_height();
+
+        let host_timestamp = ctx.latest_timestamp();
+        let host_height = ctx.latest_height();
+
+        ctx.ibc_store
+            .store_update_meta(client_id.clone(), heights[1], host_timestamp, host_height)
+            .expect("no error");
+
+        let earlier_timestamp = (host_timestamp - Duration::from_secs(1)).expect("no error");
+
+        assert!(ctx
+            .ibc_store
+            .store_update_meta(
+                client_id.clone(),
+                heights[1],
+                earlier_timestamp,
+                host_height
+            )
+            .is_err());
+        assert!(ctx
+            .ibc_store
+            .store_update_meta(
+                client_id.clone(),
+                heights[1],
+                host_timestamp,
+                host_height.decrement().expect("no error"),
+            )
+            .is_err());
+
+        // going back in time is allowed outside of the strict mode
+        let mut ctx = ctx.with_strict_update_meta(false);
+
+        ctx.ibc_store
+            .store_update_meta(client_id, heights[1], earlier_timestamp, host_height)
+            .expect("no error");
     }
 
-    /// Returns all the events that have been emitted by the context's IBC store.
-    pub fn get_events(&self) -> Vec<IbcEvent> {
-        self.ibc_store.events.lock().clone()
+    #[test]
+    fn test_advance_client() {
+        let client_id = tm_client_type().build_client_id(0);
+        let ctx_b = TendermintContext::default();
+
+        let mut ctx = MockContext::default().with_light_client(
+            &client_id,
+            LightClientBuilder::init()
+                .context(&ctx_b)
+                .build::<LightClientState<TendermintHost>>(),
+        );
+
+        let heights = [5, 6, 7, 8].map(|h| Height::new(0, h).expect("Never fails"));
+
+        assert_eq!(ctx.light_client_latest_height(&client_id), heights[0]);
+
+        ctx.advance_client(&client_id, heights[3])
+            .expect("client updates succeed");
+
+        assert_eq!(ctx.light_client_latest_height(&client_id), heights[3]);
+        assert_eq!(
+            ctx.consensus_states(&client_id)
+                .expect("client exists")
+                .keys()
+                .copied()
+                .collect::<Vec<_>>(),
+            heights.to_vec()
+        );
+
+        // advancing to a lower height is a no-op, which leaves the host alone
+        let host_height = ctx.latest_height();
+        ctx.advance_client(&client_id, heights[1])
+            .expect("no error");
+        assert_eq!(ctx.light_client_latest_height(&client_id), heights[3]);
+        assert_eq!(ctx.latest_height(), host_height);
     }
 
-    /// Returns all the logs that have been emitted by the context's IBC store.
-    pub fn get_logs(&self) -> Vec<String> {
-        self.ibc_store.logs.lock().clone()
+    #[test]
+    #[should_panic(expected = "not a mock client")]
+    fn test_set_client_status_rejects_tendermint_client() {
+        let client_id = tm_client_type().build_client_id(0);
+        let ctx_b = TendermintContext::default();
+
+        let mut ctx = MockContext::default().with_light_client(
+            &client_id,
+            LightClientBuilder::init()
+                .context(&ctx_b)
+                .build::<LightClientState<TendermintHost>>(),
+        );
+
+        ctx.set_client_status(&client_id, Status::Frozen);
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use ibc::core::client::context::consensus_state::ConsensusState;
+    #[test]
+    fn test_halt() {
+        let client_id = tm_client_type().build_client_id(0);
+        let ctx_b = TendermintContext::default();
+
+        let mut ctx = MockContext::default().with_light_client(
+            &client_id,
+            LightClientBuilder::init()
+                .context(&ctx_b)
+                .build::<LightClientState<TendermintHost>>(),
+        );
 
-    use super::*;
-    use crate::hosts::{HostConsensusState, MockHost, TendermintHost};
-    use crate::testapp::ibc::core::types::DefaultIbcStore;
+        let status = |ctx: &MockContext| {
+            ctx.ibc_store
+                .client_state(&client_id)
+                .expect("client exists")
+                .status(&ctx.ibc_store, &client_id)
+                .expect("no error")
+        };
+
+        let height = ctx.latest_height();
+        let timestamp = ctx.latest_timestamp();
+        assert_eq!(status(&ctx), Status::Active);
+
+        // ten years, well beyond the trusting period of the client
+        let halt = Duration::from_secs(10 * 365 * 24 * 60 * 60);
+        ctx.halt(halt);
+
+        assert_eq!(ctx.latest_height(), height);
+        assert_eq!(ctx.latest_timestamp(), timestamp);
+        assert_eq!(
+            ValidationContext::host_timestamp(&ctx.ibc_store).expect("no error"),
+            (timestamp + halt).expect("no error")
+        );
+        assert_eq!(status(&ctx), Status::Expired);
+
+        ctx.advance_block_height();
+
+        assert_eq!(ctx.latest_height(), height.increment());
+        assert_eq!(
+            ctx.latest_timestamp(),
+            (timestamp + (halt + ctx.block_time)).expect("no error")
+        );
+        assert_eq!(
+            ValidationContext::host_timestamp(&ctx.ibc_store).expect("no error"),
+            ctx.latest_timestamp()
+        );
+        assert_eq!(status(&ctx), Status::Expired);
+    }
+
+    #[test]
+    fn test_with_updated_client() {
+        let client_id = tm_client_type().build_client_id(0);
+        let ctx_b = TendermintContext::default();
+
+        let mut ctx = MockContext::default().with_light_client(
+            &client_id,
+            LightClientBuilder::init()
+                .context(&ctx_b)
+                .build::<LightClientState<TendermintHost>>(),
+        );
+
+        let old_height = Height::new(0, 5).expect("Never fails");
+        let new_height = Height::new(0, 10).expect("Never fails");
+
+        assert_eq!(ctx.light_client_latest_height(&client_id), old_height);
+
+        // the header must not be ahead of the host
+        for _ in 0..5 {
+            ctx.advance_block_height();
+        }
+        let timestamp = ctx.latest_timestamp();
+
+        let ctx = ctx.with_updated_client(&client_id, new_height, timestamp);
+
+        assert_eq!(ctx.light_client_latest_height(&client_id), new_height);
+
+        let expected_consensus_state: AnyConsensusState = TendermintHost::default()
+            .generate_block(
+                Vec::new(),
+                new_height.revision_height(),
+                timestamp,
+                &Default::default(),
+            )
+            .into_header()
+            .into_consensus_state()
+            .into();
+
+        let consensus_states = ctx.consensus_states(&client_id).expect("client exists");
+        assert_eq!(
+            consensus_states.keys().copied().collect::<Vec<_>>(),
+            vec![old_height, new_height]
+        );
+        assert_eq!(
+            consensus_states.get(&new_height),
+            Some(&expected_consensus_state)
+        );
+
+        assert_eq!(
+            ctx.ibc_store
+                .client_update_meta(&client_id, &new_height)
+                .expect("update meta exists"),
+            (ctx.latest_timestamp(), ctx.latest_height())
+        );
+    }
+
+    #[test]
+    fn test_prove_packet_commitment() {
+        use ibc::core::client::context::client_state::ClientStateCommon;
+
+        use crate::fixtures::core::channel::dummy_raw_packet;
+
+        let client_id = mock_client_type().build_client_id(0);
+
+        let mut ctx = with_open_channel(
+            TendermintContext::default().with_light_client(
+                &client_id,
+                LightClientState::<MockHost>::with_latest_height(
+                    Height::new(0, 5).expect("no error"),
+                ),
+            ),
+            &client_id,
+        );
+
+        let packet: Packet = dummy_raw_packet(10, 0).try_into().expect("no error");
+        ctx.send_packet(packet.clone())
+            .expect("send_packet succeeds");
+        ctx.advance_block_height();
+
+        let (commitment, proof) = ctx.prove_packet_commitment(
+            &packet.port_id_on_a,
+            &packet.chan_id_on_a,
+            packet.seq_on_a,
+        );
+
+        let tm_light_client: LightClientState<TendermintHost> =
+            LightClientBuilder::init().context(&ctx).build();
+        let root = ctx
+            .ibc_store
+            .host_consensus_state(&ctx.latest_height())
+            .expect("host consensus state exists")
+            .root()
+            .clone();
+
+        let commitment_path =
+            CommitmentPath::new(&packet.port_id_on_a, &packet.chan_id_on_a, packet.seq_on_a);
+
+        tm_light_client
+            .client_state
+            .verify_membership(
+                &ctx.ibc_store.commitment_prefix(),
+                &proof,
+                &root,
+                commitment_path.clone().into(),
+                commitment.clone(),
+            )
+            .expect("successful proof verification");
+
+        assert!(tm_light_client
+            .client_state
+            .verify_membership(
+                &ctx.ibc_store.commitment_prefix(),
+                &proof,
+                &root,
+                commitment_path.into(),
+                vec![0; commitment.len()],
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn test_get_consensus_proof() {
+        use ibc::core::client::context::client_state::ClientStateCommon;
+
+        let client_id = mock_client_type().build_client_id(0);
+        let client_height = Height::new(0, 5).expect("no error");
+
+        let mut ctx = TendermintContext::default().with_light_client(
+            &client_id,
+            LightClientState::<MockHost>::with_latest_height(client_height),
+        );
+
+        assert!(ctx.get_consensus_proof(&client_id, client_height).is_none());
+
+        ctx.advance_block_height();
+
+        let proof = ctx
+            .get_consensus_proof(&client_id, client_height)
+            .expect("consensus state is part of the latest block");
+        assert!(ctx
+            .get_consensus_proof(&client_id, Height::new(0, 6).expect("no error"))
+            .is_none());
+
+        let tm_light_client: LightClientState<TendermintHost> =
+            LightClientBuilder::init().context(&ctx).build();
+        let root = ctx
+            .ibc_store
+            .host_consensus_state(&ctx.latest_height())
+            .expect("host consensus state exists")
+            .root()
+            .clone();
+
+        let consensus_state_path = ClientConsensusStatePath::new(
+            client_id.clone(),
+            client_height.revision_number(),
+            client_height.revision_height(),
+        );
+        let consensus_state = ctx
+            .ibc_store
+            .consensus_state(&consensus_state_path)
+            .expect("consensus state exists");
+
+        tm_light_client
+            .client_state
+            .verify_membership(
+                &ctx.ibc_store.commitment_prefix(),
+                &proof,
+                &root,
+                consensus_state_path.into(),
+                consensus_state.encode_vec(),
+            )
+            .expect("successful proof verification");
+    }
+
+    #[test]
+    fn test_clear_channels_and_connections() {
+        let client_id = mock_client_type().build_client_id(0);
+        let heights = [3, 7].map(|h| Height::new(0, h).expect("Never fails"));
+
+        let mut ctx = with_open_channel(
+            MockContext::default().with_client_at_heights(&client_id, &heights),
+            &client_id,
+        )
+        .with_channel(PortId::transfer(), ChannelId::new(1), open_channel_end())
+        .with_send_sequence(PortId::transfer(), ChannelId::zero(), 5.into());
+
+        let consensus_states = ctx.consensus_states(&client_id);
+
+        ctx.clear_channels();
+        ctx.clear_connections();
+        ctx.reset_sequences();
+
+        for chan_id in [ChannelId::zero(), ChannelId::new(1)] {
+            assert!(ctx
+                .ibc_store
+                .channel_end(&ChannelEndPath::new(&PortId::transfer(), &chan_id))
+                .is_err());
+        }
+        assert!(ctx.ibc_store.connection_end(&ConnectionId::zero()).is_err());
+        assert!(ctx
+            .ibc_store
+            .get_next_sequence_send(&SeqSendPath::new(&PortId::transfer(), &ChannelId::zero()))
+            .is_err());
+
+        // the client and its consensus states remain
+        assert!(ctx.ibc_store.client_state(&client_id).is_ok());
+        assert_eq!(ctx.consensus_states(&client_id), consensus_states);
+    }
+
+    #[test]
+    #[should_panic(expected = "exceeds the budget of 10 bytes (clients: ")]
+    fn test_assert_store_under() {
+        let client_id = mock_client_type().build_client_id(0);
+        let heights = [3, 7].map(|h| Height::new(0, h).expect("Never fails"));
+
+        let ctx = MockContext::default().with_client_at_heights(&client_id, &heights);
+
+        ctx.assert_store_under(usize::MAX);
+        ctx.assert_store_under(10);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_snapshot_and_restore_client() {
+        let client_id = mock_client_type().build_client_id(0);
+        // The store orders the keys lexicographically, e.g. height 10 before 9.
+        let heights = [1, 2, 9, 10, 11, 20].map(|h| Height::new(0, h).expect("Never fails"));
+
+        let ctx = MockContext::default().with_client_at_heights(&client_id, &heights);
+
+        assert!(ctx
+            .snapshot_client(&mock_client_type().build_client_id(1))
+            .is_none());
+
+        let snapshot = ctx.snapshot_client(&client_id).expect("client exists");
+        let json = serde_json::to_string(&snapshot).expect("no error");
+        let restored_snapshot: ClientSnapshot = serde_json::from_str(&json).expect("no error");
+
+        // the serialized form is stable
+        assert_eq!(
+            serde_json::to_string(&restored_snapshot).expect("no error"),
+            json
+        );
+
+        let mut restored_ctx = MockContext::default();
+        restored_ctx
+            .restore_client(restored_snapshot)
+            .expect("no error");
+
+        assert_eq!(
+            restored_ctx.consensus_states(&client_id),
+            ctx.consensus_states(&client_id)
+        );
+
+        for query in 1..=21 {
+            let height = Height::new(0, query).expect("Never fails");
+
+            assert_eq!(
+                restored_ctx
+                    .ibc_store
+                    .next_consensus_state(&client_id, &height)
+                    .expect("no error"),
+                ctx.ibc_store
+                    .next_consensus_state(&client_id, &height)
+                    .expect("no error"),
+                "next consensus state after {height}"
+            );
+        }
+
+        let record = MockClientRecord {
+            client_state: ctx.ibc_store.client_state(&client_id).ok(),
+            consensus_states: ctx.consensus_states(&client_id).expect("client exists"),
+        };
+        let restored_record: MockClientRecord =
+            serde_json::from_str(&serde_json::to_string(&record).expect("no error"))
+                .expect("no error");
+
+        assert_eq!(restored_record.client_state, record.client_state);
+        assert_eq!(restored_record.consensus_states, record.consensus_states);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_replay_scenario() {
+        let client_id = mock_client_type().build_client_id(0);
+
+        let mut ctx = with_open_channel(
+            MockContext::default().with_light_client(
+                &client_id,
+                LightClientState::<MockHost>::with_latest_height(
+                    Height::new(0, 42).expect("no error"),
+                ),
+            ),
+            &client_id,
+        );
+
+        let packet_json = |sequence: u64, data: &str| {
+            format!(
+                r#"{{
+                    "sequence": {sequence},
+                    "port_id_on_a": "transfer",
+                    "chan_id_on_a": "channel-0",
+                    "port_id_on_b": "transfer",
+                    "chan_id_on_b": "channel-0",
+                    "data": "{data}",
+                    "timeout_height": {{ "revision_number": 0, "revision_height": 100 }},
+                    "timeout_timestamp": 0
+                }}"#
+            )
+        };
+
+        let scenario = format!(
+            r#"[
+                {{ "type": "send_packet", "packet": {} }},
+                {{ "type": "advance_block" }},
+                {{ "type": "send_packet", "packet": {} }}
+            ]"#,
+            packet_json(1, "0102"),
+            packet_json(2, "ff"),
+        );
+
+        let height_before = ctx.latest_height();
+
+        let events = ctx.replay_scenario(&scenario).expect("scenario replays");
+
+        let sent_packets: Vec<_> = events
+            .iter()
+            .filter_map(|event| match event {
+                IbcEvent::SendPacket(e) => Some((*e.seq_on_a(), e.packet_data().to_vec())),
+                _ => None,
+            })
+            .collect();
+
+        assert_eq!(
+            sent_packets,
+            vec![(1.into(), vec![0x01, 0x02]), (2.into(), vec![0xff])]
+        );
+        assert_eq!(ctx.latest_height(), height_before.increment());
+
+        let res = ctx.replay_scenario(r#"[{ "type": "unknown" }]"#);
+        assert!(
+            matches!(
+                res,
+                Err(ContextError::RouterError(
+                    ibc::core::router::types::error::RouterError::MalformedMessageBytes { .. }
+                ))
+            ),
+            "unexpected result: {res:?}"
+        );
+    }
+
+    #[test]
+    fn test_assert_frozen_client_rejected() {
+        use ibc::core::client::types::msgs::MsgUpgradeClient;
+
+        use crate::fixtures::core::channel::dummy_raw_packet;
+        use crate::fixtures::core::client::dummy_msg_upgrade_client;
+
+        let client_id = mock_client_type().build_client_id(0);
+        let client_height = Height::new(0, 42).expect("no error");
+
+        let mut ctx = with_open_channel(
+            MockContext::default().with_light_client(
+                &client_id,
+                LightClientState::<MockHost>::with_latest_height(client_height),
+            ),
+            &client_id,
+        );
+
+        let msg_upgrade: MsgUpgradeClient =
+            dummy_msg_upgrade_client(client_id.clone(), Height::new(1, 26).expect("no error"));
+
+        let msg_update = MsgUpdateClient {
+            client_id: client_id.clone(),
+            client_message: MockHeader::new(client_height.increment()).into(),
+            signer: dummy_account_id(),
+        };
+
+        let packet: Packet = dummy_raw_packet(100, 0).try_into().expect("valid packet");
+
+        assert_frozen_client_rejected(
+            &mut ctx,
+            &client_id,
+            [
+                (
+                    "upgrade_client",
+                    Box::new(move |ctx: &mut MockContext| {
+                        ctx.validate(MsgEnvelope::from(ClientMsg::from(msg_upgrade)))
+                    }) as ClientOperation<'_>,
+                ),
+                (
+                    "update_client",
+                    Box::new(move |ctx: &mut MockContext| {
+                        ctx.validate(MsgEnvelope::from(ClientMsg::from(msg_update)))
+                    }) as ClientOperation<'_>,
+                ),
+                (
+                    "send_packet",
+                    Box::new(move |ctx: &mut MockContext| ctx.send_packet(packet).map(|_| ()))
+                        as ClientOperation<'_>,
+                ),
+            ],
+        );
+
+        ctx.assert_no_events();
+    }
+
+    #[test]
+    fn test_consensus_states() {
+        let client_id = mock_client_type().build_client_id(0);
+        let heights = [3, 7].map(|h| Height::new(0, h).expect("Never fails"));
+
+        let ctx = MockContext::default();
+
+        assert_eq!(ctx.consensus_states(&client_id), None);
+
+        let ctx = ctx.with_client_at_heights(&client_id, &heights);
+        let timestamp = ctx.latest_timestamp();
+
+        let expected: BTreeMap<Height, AnyConsensusState> = heights
+            .iter()
+            .map(|&height| {
+                (
+                    height,
+                    MockConsensusState::new(MockHeader::new(height).with_timestamp(timestamp))
+                        .into(),
+                )
+            })
+            .collect();
+
+        assert_eq!(ctx.consensus_states(&client_id), Some(expected));
+    }
+
+    #[test]
+    fn test_consensus_state_exists() {
+        let client_id = mock_client_type().build_client_id(0);
+        let heights = [3, 7].map(|h| Height::new(0, h).expect("Never fails"));
+
+        let ctx = MockContext::default();
+
+        assert!(matches!(
+            ctx.consensus_state_exists(&client_id, &heights[0]),
+            Err(ContextError::ClientError(
+                ClientError::ClientStateNotFound { .. }
+            ))
+        ));
+
+        let ctx = ctx.with_client_at_heights(&client_id, &heights);
+
+        for h in 1..=8 {
+            let height = Height::new(0, h).expect("Never fails");
+            assert_eq!(
+                ctx.consensus_state_exists(&client_id, &height)
+                    .expect("no error"),
+                heights.contains(&height),
+                "consensus state at {height}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_prune_expired_consensus_states() {
+        let client_id = mock_client_type().build_client_id(0);
+        let trusting_period = Duration::from_secs(100);
+
+        let ctx = MockContext::default();
+        let now = ctx.latest_timestamp();
+
+        let heights = [1, 2, 3].map(|h| Height::new(0, h).expect("Never fails"));
+        let ages = [500, 200, 10].map(Duration::from_secs);
+
+        let light_client = LightClientState::<MockHost> {
+            client_state: MockClientState::new(MockHeader::new(heights[2]).with_timestamp(now))
+                .with_trusting_period(trusting_period),
+            consensus_states: heights
+                .iter()
+                .zip(ages)
+                .map(|(&height, age)| {
+                    let timestamp = (now - age).expect("no underflow");
+                    (
+                        height,
+                        MockConsensusState::new(MockHeader::new(height).with_timestamp(timestamp)),
+                    )
+                })
+                .collect(),
+        };
+
+        let mut ctx = ctx.with_light_client(&client_id, light_client);
+
+        assert_eq!(ctx.prune_expired_consensus_states(&client_id, now), 2);
+        assert_eq!(
+            ctx.ibc_store
+                .consensus_state_heights(&client_id)
+                .expect("no error"),
+            vec![heights[2]]
+        );
+
+        assert_eq!(ctx.prune_expired_consensus_states(&client_id, now), 0);
+    }
+
+    #[test]
+    fn test_block_time_advances_timestamp() {
+        let block_time = Duration::from_secs(7);
+
+        let mut ctx = MockContext::default().with_block_time(block_time);
+
+        let height = ctx.latest_height();
+        let timestamp = ctx.latest_timestamp();
+
+        for _ in 0..10 {
+            ctx.advance_block_height();
+        }
+
+        assert_eq!(ctx.latest_height(), height.add(10));
+        assert_eq!(
+            ctx.latest_timestamp(),
+            (timestamp + block_time * 10).expect("no overflow")
+        );
+    }
 
     #[test]
     fn test_mock_history_validation() {
@@ -78,10 +78,15 @@ mod tests {
             },
             Test {
                 event_kind: UPGRADE_CLIENT_EVENT,
-                event: UpgradeClient::new(client_id.clone(), client_type.clone(), consensus_height)
-                    .into(),
-                expected_keys: expected_keys[0..3].to_vec(),
-                expected_values: expected_values[0..3].to_vec(),
+                event: UpgradeClient::new(
+                    client_id.clone(),
+                    client_type.clone(),
+                    consensus_height,
+                    Height::new(0, 3).unwrap(),
+                )
+                .into(),
+                expected_keys: [&expected_keys[0..3], &["previous_height"]].concat(),
+                expected_values: [&expected_values[0..3], &["0-3"]].concat(),
             },
             Test {
                 event_kind: CLIENT_MISBEHAVIOUR_EVENT,
@@ -3,9 +3,14 @@ mod conn_open_confirm;
 mod conn_open_init;
 mod conn_open_try;
 
+use core::time::Duration;
+
+use ibc::core::commitment_types::commitment::CommitmentPrefix;
 use ibc::core::commitment_types::proto::v1::MerklePrefix;
 use ibc::core::connection::types::proto::v1::Counterparty as RawCounterparty;
-use ibc::core::host::types::identifiers::ConnectionId;
+use ibc::core::connection::types::version::Version;
+use ibc::core::connection::types::{ConnectionEnd, Counterparty, State};
+use ibc::core::host::types::identifiers::{ClientId, ConnectionId};
 use ibc::core::primitives::prelude::*;
 use typed_builder::TypedBuilder;
 
@@ -37,6 +42,40 @@ impl From<CounterpartyConfig> for RawCounterparty {
     }
 }
 
+/// Configuration for building dummy connection ends, open on
+/// `07-tendermint-0` by default.
+#[derive(TypedBuilder, Debug)]
+#[builder(build_method(into = ConnectionEnd))]
+pub struct ConnectionEndConfig {
+    #[builder(default = State::Open)]
+    state: State,
+    #[builder(default = ClientId::new("07-tendermint", 0).expect("no error"))]
+    client_id: ClientId,
+    #[builder(default = Counterparty::new(
+        ClientId::new("07-tendermint", 0).expect("no error"),
+        Some(ConnectionId::zero()),
+        CommitmentPrefix::try_from(vec![0]).expect("no error"),
+    ))]
+    counterparty: Counterparty,
+    #[builder(default = Version::compatibles())]
+    versions: Vec<Version>,
+    #[builder(default = Duration::ZERO)]
+    delay_period: Duration,
+}
+
+impl From<ConnectionEndConfig> for ConnectionEnd {
+    fn from(config: ConnectionEndConfig) -> Self {
+        Self::new(
+            config.state,
+            config.client_id,
+            config.counterparty,
+            config.versions,
+            config.delay_period,
+        )
+        .expect("valid connection end")
+    }
+}
+
 pub fn dummy_raw_counterparty_conn(conn_id: Option<u64>) -> RawCounterparty {
     let connection_id = match conn_id {
         Some(id) => ConnectionId::new(id).to_string(),
@@ -64,6 +64,8 @@ where
                 Default::default(),
             ),
             ibc_router: MockRouter::new_with_transfer(),
+            block_time: params.block_time,
+            handler_calls: None,
         };
 
         // store is at height 0; no block
@@ -40,6 +40,11 @@ impl TestHost for MockHost {
         self.history.push(block);
     }
 
+    fn truncate_history(&mut self, revision_height: u64) {
+        // blocks are indexed from height 1
+        self.history.truncate(revision_height as usize);
+    }
+
     fn generate_block(
         &self,
         _commitment_root: Vec<u8>,
@@ -64,6 +64,14 @@ pub trait TestHost: Default + Debug + Sized {
     /// Add a block to the host chain.
     fn push_block(&mut self, block: Self::Block);
 
+    /// Drop the blocks above the given revision height from the host chain.
+    ///
+    /// Only needed to rewind the host chain, hence the default
+    /// implementation, which panics.
+    fn truncate_history(&mut self, revision_height: u64) {
+        panic!("host does not support dropping the blocks above {revision_height}")
+    }
+
     /// Commit a block with commitment root to the blockchain, by extending the history of blocks.
     fn commit_block(
         &mut self,
@@ -53,6 +53,11 @@ impl TestHost for TendermintHost {
         self.history.push(block);
     }
 
+    fn truncate_history(&mut self, revision_height: u64) {
+        // blocks are indexed from height 1
+        self.history.truncate(revision_height as usize);
+    }
+
     fn generate_block(
         &self,
         commitment_root: Vec<u8>,
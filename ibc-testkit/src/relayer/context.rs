@@ -1,3 +1,4 @@
+use ibc::core::channel::types::channel::Order;
 use ibc::core::channel::types::packet::Packet;
 use ibc::core::client::context::client_state::ClientStateValidation;
 use ibc::core::host::types::identifiers::{ChannelId, ClientId, ConnectionId, PortId};
@@ -54,6 +55,11 @@ where
         &mut self.ctx_b
     }
 
+    /// Returns mutable references to both contexts at once.
+    pub fn get_ctxs_mut(&mut self) -> (&mut TestContext<A>, &mut TestContext<B>) {
+        (&mut self.ctx_a, &mut self.ctx_b)
+    }
+
     /// Creates a light client of second context on the first context.
     /// Returns the client identifier of the created client.
     pub fn create_client_on_a(&mut self, signer: Signer) -> ClientId {
@@ -120,7 +126,7 @@ where
         )
     }
 
-    /// Creates a channel between the two contexts starting from the first context.
+    /// Creates an unordered channel between the two contexts starting from the first context.
     /// Returns the channel identifiers of the created channel ends.
     pub fn create_channel_on_a(
         &mut self,
@@ -129,6 +135,28 @@ where
         conn_id_on_b: ConnectionId,
         port_id_on_b: PortId,
         signer: Signer,
+    ) -> (ChannelId, ChannelId) {
+        self.create_channel_with_order_on_a(
+            conn_id_on_a,
+            port_id_on_a,
+            conn_id_on_b,
+            port_id_on_b,
+            Order::Unordered,
+            signer,
+        )
+    }
+
+    /// Creates a channel of the given ordering between the two contexts starting from the
+    /// first context.
+    /// Returns the channel identifiers of the created channel ends.
+    pub fn create_channel_with_order_on_a(
+        &mut self,
+        conn_id_on_a: ConnectionId,
+        port_id_on_a: PortId,
+        conn_id_on_b: ConnectionId,
+        port_id_on_b: PortId,
+        ordering: Order,
+        signer: Signer,
     ) -> (ChannelId, ChannelId) {
         let client_id_on_a = self
             .ctx_a
@@ -146,7 +174,7 @@ where
             .client_id()
             .clone();
 
-        TypedRelayerOps::<A, B>::create_channel_on_a(
+        TypedRelayerOps::<A, B>::create_channel_with_order_on_a(
             &mut self.ctx_a,
             &mut self.ctx_b,
             client_id_on_a,
@@ -155,6 +183,7 @@ where
             client_id_on_b,
             conn_id_on_b,
             port_id_on_b,
+            ordering,
             signer,
         )
     }
@@ -0,0 +1,382 @@
+use alloc::vec::Vec;
+
+use ibc::core::channel::types::acknowledgement::Acknowledgement;
+use ibc::core::channel::types::channel::Order;
+use ibc::core::channel::types::packet::Packet;
+use ibc::core::host::types::identifiers::{ChannelId, ClientId, PortId, Sequence};
+use ibc::core::host::types::path::ChannelEndPath;
+use ibc::core::host::ValidationContext;
+use ibc::primitives::Signer;
+
+use crate::context::MockContext;
+use crate::fixtures::core::signer::dummy_account_id;
+use crate::hosts::MockHost;
+use crate::relayer::context::RelayerContext;
+use crate::relayer::error::RelayerError;
+use crate::relayer::utils::TypedRelayerOps;
+
+type MockRelayerOps = TypedRelayerOps<MockHost, MockHost>;
+
+/// Identifies one of the two chains held by a [`RelayerHarness`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Chain {
+    A,
+    B,
+}
+
+impl Chain {
+    /// Returns the other chain of the pair.
+    pub fn counterparty(self) -> Self {
+        match self {
+            Self::A => Self::B,
+            Self::B => Self::A,
+        }
+    }
+}
+
+/// An acknowledgement written on the destination chain which is not yet
+/// relayed back to the source chain.
+#[derive(Clone, Debug)]
+pub struct PendingAck {
+    /// The chain the packet was sent from.
+    pub source: Chain,
+    pub packet: Packet,
+    pub acknowledgement: Acknowledgement,
+}
+
+/// Runs two [`MockContext`]s side by side as chain `A` and chain `B` and
+/// relays packets between them.
+///
+/// The harness takes a few shortcuts compared to a real relayer:
+/// - Both chains are [`MockHost`]s, so each side tracks its counterparty with
+///   a mock light client. Headers are not signed and the client updates
+///   involve no real cryptography.
+/// - Proofs are taken from the source store at its latest height, right after
+///   the client on the destination is synced to that height.
+/// - Messages are delivered directly into the contexts. Nothing is queued,
+///   reordered or lost in transit.
+pub struct RelayerHarness {
+    relayer: RelayerContext<MockHost, MockHost>,
+    signer: Signer,
+    pending_acks: Vec<PendingAck>,
+}
+
+impl RelayerHarness {
+    /// Creates a harness with the given contexts as chain `A` and chain `B`.
+    pub fn new(ctx_a: MockContext, ctx_b: MockContext) -> Self {
+        Self {
+            relayer: RelayerContext::new(ctx_a, ctx_b),
+            signer: dummy_account_id(),
+            pending_acks: Vec::new(),
+        }
+    }
+
+    /// Sets the signer of the messages submitted by the harness.
+    pub fn with_signer(mut self, signer: Signer) -> Self {
+        self.signer = signer;
+        self
+    }
+
+    /// Returns immutable reference to the underlying relayer context.
+    pub fn relayer(&self) -> &RelayerContext<MockHost, MockHost> {
+        &self.relayer
+    }
+
+    /// Returns mutable reference to the underlying relayer context.
+    pub fn relayer_mut(&mut self) -> &mut RelayerContext<MockHost, MockHost> {
+        &mut self.relayer
+    }
+
+    /// Returns immutable reference to the context of the given chain.
+    pub fn ctx(&self, chain: Chain) -> &MockContext {
+        match chain {
+            Chain::A => self.relayer.get_ctx_a(),
+            Chain::B => self.relayer.get_ctx_b(),
+        }
+    }
+
+    /// Returns mutable reference to the context of the given chain.
+    pub fn ctx_mut(&mut self, chain: Chain) -> &mut MockContext {
+        match chain {
+            Chain::A => self.relayer.get_ctx_a_mut(),
+            Chain::B => self.relayer.get_ctx_b_mut(),
+        }
+    }
+
+    /// Returns the acknowledgements that are not yet relayed back to their source.
+    pub fn pending_acks(&self) -> &[PendingAck] {
+        &self.pending_acks
+    }
+
+    /// Creates the clients, a connection and an unordered channel on the given
+    /// port between the two chains, starting from chain `A`.
+    /// Returns the channel identifiers on `A` and `B`.
+    pub fn open_channel(&mut self, port_id: PortId) -> (ChannelId, ChannelId) {
+        self.open_channel_with_order(port_id, Order::Unordered)
+    }
+
+    /// Creates the clients, a connection and a channel of the given ordering on
+    /// the given port between the two chains, starting from chain `A`.
+    /// Returns the channel identifiers on `A` and `B`.
+    pub fn open_channel_with_order(
+        &mut self,
+        port_id: PortId,
+        ordering: Order,
+    ) -> (ChannelId, ChannelId) {
+        let signer = self.signer.clone();
+
+        let client_id_on_a = self.relayer.create_client_on_a(signer.clone());
+        let client_id_on_b = self.relayer.create_client_on_b(signer.clone());
+
+        let (conn_id_on_a, conn_id_on_b) =
+            self.relayer
+                .create_connection_on_a(client_id_on_a, client_id_on_b, signer.clone());
+
+        self.relayer.create_channel_with_order_on_a(
+            conn_id_on_a,
+            port_id.clone(),
+            conn_id_on_b,
+            port_id,
+            ordering,
+            signer,
+        )
+    }
+
+    /// Relays a packet committed on `from` to `to`.
+    ///
+    /// The client of `from` on `to` is first updated to the latest height of
+    /// `from`, then the packet is submitted on `to` along with the proof of its
+    /// commitment. The written acknowledgement is returned and kept as pending
+    /// until [`relay_acks`](Self::relay_acks) is called.
+    pub fn relay_packet(&mut self, from: Chain, to: Chain, packet: Packet) -> Acknowledgement {
+        self.try_relay_packet(from, to, packet)
+            .expect("packet is received")
+    }
+
+    /// Relays a packet committed on `from` to `to` like
+    /// [`relay_packet`](Self::relay_packet), but returns the error of `to`
+    /// rejecting the packet instead of panicking. A rejected packet leaves no
+    /// pending acknowledgement.
+    pub fn try_relay_packet(
+        &mut self,
+        from: Chain,
+        to: Chain,
+        packet: Packet,
+    ) -> Result<Acknowledgement, RelayerError> {
+        assert_eq!(
+            to,
+            from.counterparty(),
+            "packet must be relayed across chains"
+        );
+
+        let signer = self.signer.clone();
+        let (ctx_from, ctx_to) = self.ctxs_mut(from);
+
+        let client_id_on_to =
+            client_id_of_channel(ctx_to, &packet.port_id_on_b, &packet.chan_id_on_b);
+
+        MockRelayerOps::update_client_on_a_with_sync(
+            ctx_to,
+            ctx_from,
+            client_id_on_to,
+            signer.clone(),
+        );
+
+        let acknowledgement =
+            MockRelayerOps::try_packet_recv_on_b(ctx_to, ctx_from, packet.clone(), signer)?;
+
+        self.pending_acks.push(PendingAck {
+            source: from,
+            packet,
+            acknowledgement: acknowledgement.clone(),
+        });
+
+        Ok(acknowledgement)
+    }
+
+    /// Relays the given packets committed on `from` to `to`, in the order of
+    /// their sequences in `order` rather than the order they were sent in,
+    /// e.g. to check that an unordered channel accepts sequence gaps.
+    ///
+    /// Each packet is relayed with [`try_relay_packet`](Self::try_relay_packet),
+    /// and its outcome is returned in the same order as `order`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a sequence of `order` matches none of the given packets.
+    pub fn relay_packets_in_order(
+        &mut self,
+        from: Chain,
+        to: Chain,
+        packets: &[Packet],
+        order: &[Sequence],
+    ) -> Vec<Result<Acknowledgement, RelayerError>> {
+        order
+            .iter()
+            .map(|seq| {
+                let packet = packets
+                    .iter()
+                    .find(|packet| packet.seq_on_a == *seq)
+                    .unwrap_or_else(|| panic!("no packet with sequence {seq}"))
+                    .clone();
+
+                self.try_relay_packet(from, to, packet)
+            })
+            .collect()
+    }
+
+    /// Relays every pending acknowledgement back to the chain its packet was sent from.
+    /// Returns the number of relayed acknowledgements.
+    pub fn relay_acks(&mut self) -> usize {
+        let pending_acks = core::mem::take(&mut self.pending_acks);
+        let count = pending_acks.len();
+
+        for PendingAck {
+            source,
+            packet,
+            acknowledgement,
+        } in pending_acks
+        {
+            let signer = self.signer.clone();
+            let (ctx_source, ctx_dest) = self.ctxs_mut(source);
+
+            let client_id_on_source =
+                client_id_of_channel(ctx_source, &packet.port_id_on_a, &packet.chan_id_on_a);
+
+            MockRelayerOps::update_client_on_a_with_sync(
+                ctx_source,
+                ctx_dest,
+                client_id_on_source,
+                signer.clone(),
+            );
+
+            MockRelayerOps::packet_ack_on_a(ctx_source, ctx_dest, packet, acknowledgement, signer);
+        }
+
+        count
+    }
+
+    /// Returns the context of `chain` followed by the context of its counterparty.
+    fn ctxs_mut(&mut self, chain: Chain) -> (&mut MockContext, &mut MockContext) {
+        let (ctx_a, ctx_b) = self.relayer.get_ctxs_mut();
+
+        match chain {
+            Chain::A => (ctx_a, ctx_b),
+            Chain::B => (ctx_b, ctx_a),
+        }
+    }
+}
+
+/// Returns the client identifier underlying the given channel end on `ctx`.
+fn client_id_of_channel(ctx: &MockContext, port_id: &PortId, chan_id: &ChannelId) -> ClientId {
+    let conn_id = ctx
+        .ibc_store()
+        .channel_end(&ChannelEndPath::new(port_id, chan_id))
+        .expect("channel exists")
+        .connection_hops()[0]
+        .clone();
+
+    ctx.ibc_store()
+        .connection_end(&conn_id)
+        .expect("connection exists")
+        .client_id()
+        .clone()
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use ibc::core::handler::types::events::IbcEvent;
+
+    use super::*;
+
+    #[test]
+    fn test_relay_transfer_packet_from_a_to_b() {
+        let mut harness = RelayerHarness::new(MockContext::default(), MockContext::default());
+
+        let (chan_id_on_a, _) = harness.open_channel(PortId::transfer());
+
+        let signer = dummy_account_id();
+        let packet = harness
+            .relayer_mut()
+            .send_dummy_transfer_packet_on_a(chan_id_on_a, signer);
+
+        harness.relay_packet(Chain::A, Chain::B, packet.clone());
+
+        let recv_event = harness
+            .ctx(Chain::B)
+            .ibc_store()
+            .events
+            .lock()
+            .iter()
+            .rev()
+            .find_map(|event| match event {
+                IbcEvent::ReceivePacket(e) => Some(e.clone()),
+                _ => None,
+            })
+            .expect("receive packet event is emitted");
+
+        assert_eq!(recv_event.seq_on_b(), &packet.seq_on_a);
+        assert_eq!(recv_event.chan_id_on_b(), &packet.chan_id_on_b);
+        assert_eq!(harness.pending_acks().len(), 1);
+
+        assert_eq!(harness.relay_acks(), 1);
+        assert!(harness.pending_acks().is_empty());
+
+        let Some(IbcEvent::AcknowledgePacket(_)) = harness
+            .ctx(Chain::A)
+            .ibc_store()
+            .events
+            .lock()
+            .last()
+            .cloned()
+        else {
+            panic!("unexpected event")
+        };
+    }
+
+    #[test]
+    fn test_relay_packets_out_of_order() {
+        use ibc::core::channel::types::error::PacketError;
+        use ibc::core::handler::types::error::ContextError;
+
+        let order: Vec<Sequence> = vec![3.into(), 1.into(), 2.into()];
+
+        for ordering in [Order::Unordered, Order::Ordered] {
+            let mut harness = RelayerHarness::new(MockContext::default(), MockContext::default());
+
+            let (chan_id_on_a, _) = harness.open_channel_with_order(PortId::transfer(), ordering);
+
+            let signer = dummy_account_id();
+            let packets: Vec<Packet> = (0..3)
+                .map(|_| {
+                    harness
+                        .relayer_mut()
+                        .send_dummy_transfer_packet_on_a(chan_id_on_a.clone(), signer.clone())
+                })
+                .collect();
+
+            let results = harness.relay_packets_in_order(Chain::A, Chain::B, &packets, &order);
+
+            match ordering {
+                Order::Ordered => {
+                    assert!(
+                        matches!(
+                            results[0],
+                            Err(RelayerError::TransactionFailed(ContextError::PacketError(
+                                PacketError::InvalidPacketSequence { .. }
+                            )))
+                        ),
+                        "ordered channel must reject a sequence gap, got: {:?}",
+                        results[0]
+                    );
+                    assert!(results[1..].iter().all(Result::is_ok));
+                    assert_eq!(harness.pending_acks().len(), 2);
+                }
+                _ => {
+                    assert!(results.iter().all(Result::is_ok));
+                    assert_eq!(harness.pending_acks().len(), 3);
+                }
+            }
+        }
+    }
+}
@@ -1,4 +1,5 @@
 pub mod context;
 pub mod error;
+pub mod harness;
 pub mod integration;
 pub mod utils;
@@ -34,6 +34,7 @@ use ibc_query::core::context::ProvableContext;
 
 use crate::context::TestContext;
 use crate::hosts::{HostClientState, TestBlock, TestHost};
+use crate::relayer::error::RelayerError;
 use crate::testapp::ibc::core::types::{DefaultIbcStore, LightClientBuilder, LightClientState};
 
 /// Implements IBC relayer functions for a pair of [`TestHost`] implementations: `A` and `B`.
@@ -465,7 +466,7 @@ where
         (conn_id_on_a, conn_id_on_b)
     }
 
-    /// `A` initiates a channel with port identifier with the other end on `B`.
+    /// `A` initiates an unordered channel with port identifier with the other end on `B`.
     /// Returns the channel identifier of `A`.
     pub fn channel_open_init_on_a(
         ctx_a: &mut TestContext<A>,
@@ -473,12 +474,32 @@ where
         port_id_on_a: PortId,
         port_id_on_b: PortId,
         signer: Signer,
+    ) -> ChannelId {
+        Self::channel_open_init_with_order_on_a(
+            ctx_a,
+            conn_id_on_a,
+            port_id_on_a,
+            port_id_on_b,
+            Order::Unordered,
+            signer,
+        )
+    }
+
+    /// `A` initiates a channel of the given ordering with port identifier with the other end on `B`.
+    /// Returns the channel identifier of `A`.
+    pub fn channel_open_init_with_order_on_a(
+        ctx_a: &mut TestContext<A>,
+        conn_id_on_a: ConnectionId,
+        port_id_on_a: PortId,
+        port_id_on_b: PortId,
+        ordering: Order,
+        signer: Signer,
     ) -> ChannelId {
         let msg_for_a = MsgEnvelope::Channel(ChannelMsg::OpenInit(MsgChannelOpenInit {
             port_id_on_a,
             connection_hops_on_a: [conn_id_on_a].to_vec(),
             port_id_on_b,
-            ordering: Order::Unordered,
+            ordering,
             signer,
             version_proposal: ChannelVersion::empty(),
         }));
@@ -494,7 +515,8 @@ where
         open_init_channel_event.chan_id_on_a().clone()
     }
 
-    /// `B` receives the channel opening attempt by `A` after `A` initiates the channel.
+    /// `B` receives the channel opening attempt by `A` after `A` initiates the channel,
+    /// with the same ordering as the channel end on `A`.
     /// Returns the channel identifier of `B`.
     pub fn channel_open_try_on_b(
         ctx_b: &mut TestContext<B>,
@@ -506,12 +528,17 @@ where
     ) -> ChannelId {
         let proof_height_on_a = ctx_a.latest_height();
 
+        let chan_end_path_on_a = ChannelEndPath::new(&port_id_on_a, &chan_id_on_a);
+
+        let ordering = *ctx_a
+            .ibc_store()
+            .channel_end(&chan_end_path_on_a)
+            .expect("channel end exists")
+            .ordering();
+
         let proof_chan_end_on_a = ctx_a
             .ibc_store()
-            .get_proof(
-                proof_height_on_a,
-                &ChannelEndPath::new(&port_id_on_a, &chan_id_on_a).into(),
-            )
+            .get_proof(proof_height_on_a, &chan_end_path_on_a.into())
             .expect("connection end exists")
             .try_into()
             .expect("value merkle proof");
@@ -525,7 +552,7 @@ where
             version_supported_on_a: ChannelVersion::empty(),
             proof_chan_end_on_a,
             proof_height_on_a,
-            ordering: Order::Unordered,
+            ordering,
             signer,
 
             version_proposal: ChannelVersion::empty(),
@@ -681,7 +708,7 @@ where
         };
     }
 
-    /// A channel is created by `A` towards `B` using the IBC channel handshake protocol.
+    /// An unordered channel is created by `A` towards `B` using the IBC channel handshake protocol.
     /// Returns the channel identifiers of `A` and `B`.
     #[allow(clippy::too_many_arguments)]
     pub fn create_channel_on_a(
@@ -695,11 +722,42 @@ where
         port_id_on_b: PortId,
         signer: Signer,
     ) -> (ChannelId, ChannelId) {
-        let chan_id_on_a = TypedRelayerOps::<A, B>::channel_open_init_on_a(
+        Self::create_channel_with_order_on_a(
+            ctx_a,
+            ctx_b,
+            client_id_on_a,
+            conn_id_on_a,
+            port_id_on_a,
+            client_id_on_b,
+            conn_id_on_b,
+            port_id_on_b,
+            Order::Unordered,
+            signer,
+        )
+    }
+
+    /// A channel of the given ordering is created by `A` towards `B` using the IBC channel
+    /// handshake protocol.
+    /// Returns the channel identifiers of `A` and `B`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_channel_with_order_on_a(
+        ctx_a: &mut TestContext<A>,
+        ctx_b: &mut TestContext<B>,
+        client_id_on_a: ClientId,
+        conn_id_on_a: ConnectionId,
+        port_id_on_a: PortId,
+        client_id_on_b: ClientId,
+        conn_id_on_b: ConnectionId,
+        port_id_on_b: PortId,
+        ordering: Order,
+        signer: Signer,
+    ) -> (ChannelId, ChannelId) {
+        let chan_id_on_a = TypedRelayerOps::<A, B>::channel_open_init_with_order_on_a(
             ctx_a,
             conn_id_on_a.clone(),
             port_id_on_a.clone(),
             port_id_on_b.clone(),
+            ordering,
             signer.clone(),
         );
 
@@ -803,6 +861,18 @@ where
         packet: Packet,
         signer: Signer,
     ) -> Acknowledgement {
+        Self::try_packet_recv_on_b(ctx_b, ctx_a, packet, signer).expect("success")
+    }
+
+    /// `B` attempts to receive a packet from an IBC module on `A`, e.g. one
+    /// delivered out of order on an ordered channel.
+    /// Returns `B`'s acknowledgement of receipt, or the error of `B` rejecting the packet.
+    pub fn try_packet_recv_on_b(
+        ctx_b: &mut TestContext<B>,
+        ctx_a: &TestContext<A>,
+        packet: Packet,
+        signer: Signer,
+    ) -> Result<Acknowledgement, RelayerError> {
         let proof_height_on_a = ctx_a.latest_height();
 
         let proof_commitment_on_a = ctx_a
@@ -823,7 +893,7 @@ where
             signer,
         }));
 
-        ctx_b.deliver(msg_for_b).expect("success");
+        ctx_b.deliver(msg_for_b)?;
 
         let Some(IbcEvent::WriteAcknowledgement(write_ack_event)) =
             ctx_b.ibc_store().events.lock().last().cloned()
@@ -831,7 +901,7 @@ where
             panic!("unexpected event")
         };
 
-        write_ack_event.acknowledgement().clone()
+        Ok(write_ack_event.acknowledgement().clone())
     }
 
     /// `A` receives the acknowledgement from `B` that `B` received the packet from `A`.
@@ -0,0 +1,223 @@
+//! Declarative macros generating the protobuf [`Any`] dispatch of enums over
+//! client or consensus states, like [`AnyClientState`] and
+//! [`AnyConsensusState`].
+//!
+//! [`AnyClientState`]: super::AnyClientState
+//! [`AnyConsensusState`]: super::AnyConsensusState
+
+#[doc(hidden)]
+pub use ibc::core::client::types::error::ClientError;
+#[doc(hidden)]
+pub use ibc::primitives::proto::{Any, Protobuf};
+
+/// Defines an enum over client states, along with its `TryFrom<Any>`,
+/// `From<_> for Any` and `Protobuf<Any>` implementations.
+///
+/// Each variant is given the type URL its client state is decoded from. An
+/// optional `fallback` variant, whose type must convert from and into
+/// [`Any`], catches the client states of any other type URL. Without it,
+/// decoding them fails with [`ClientError::UnknownClientStateType`].
+///
+/// The attributes of the enum, e.g. its derives, are kept as is.
+///
+/// ```ignore
+/// define_any_client! {
+///     #[derive(Debug, Clone, From, PartialEq, ClientState)]
+///     #[validation(MyContext)]
+///     #[execution(MyContext)]
+///     pub enum MyClientState {
+///         Tendermint(TmClientState) = TENDERMINT_CLIENT_STATE_TYPE_URL,
+///         Solomachine(SmClientState) = SOLOMACHINE_CLIENT_STATE_TYPE_URL,
+///     }
+///     fallback {
+///         Unknown(UnknownClientState),
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! define_any_client {
+    (
+        $(#[$meta:meta])*
+        $vis:vis enum $name:ident {
+            $( $(#[$vmeta:meta])* $variant:ident($ty:ty) = $type_url:path ),+ $(,)?
+        }
+        $( fallback {
+            $(#[$fmeta:meta])* $fallback:ident($fty:ty) $(,)?
+        } )?
+    ) => {
+        $crate::__define_any_state! {
+            raw => $crate::testapp::ibc::clients::macros::ClientError::UnknownClientStateType {
+                client_state_type: raw.type_url,
+            };
+            $(#[$meta])*
+            $vis enum $name {
+                $( $(#[$vmeta])* $variant($ty) = $type_url ),+
+            }
+            $( fallback { $(#[$fmeta])* $fallback($fty) } )?
+        }
+    };
+}
+
+/// Defines an enum over consensus states, along with its `TryFrom<Any>`,
+/// `From<_> for Any` and `Protobuf<Any>` implementations.
+///
+/// Follows the same syntax as [`define_any_client!`]. Without a `fallback`
+/// variant, decoding a consensus state of an unlisted type URL fails with
+/// [`ClientError::UnknownConsensusStateType`].
+#[macro_export]
+macro_rules! define_any_consensus {
+    (
+        $(#[$meta:meta])*
+        $vis:vis enum $name:ident {
+            $( $(#[$vmeta:meta])* $variant:ident($ty:ty) = $type_url:path ),+ $(,)?
+        }
+        $( fallback {
+            $(#[$fmeta:meta])* $fallback:ident($fty:ty) $(,)?
+        } )?
+    ) => {
+        $crate::__define_any_state! {
+            raw => $crate::testapp::ibc::clients::macros::ClientError::UnknownConsensusStateType {
+                consensus_state_type: raw.type_url,
+            };
+            $(#[$meta])*
+            $vis enum $name {
+                $( $(#[$vmeta])* $variant($ty) = $type_url ),+
+            }
+            $( fallback { $(#[$fmeta])* $fallback($fty) } )?
+        }
+    };
+}
+
+/// Shared implementation of [`define_any_client!`] and
+/// [`define_any_consensus!`], given the error returned for unlisted type URLs
+/// in terms of the raw [`Any`].
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __define_any_state {
+    (
+        $raw:ident => $unknown:expr;
+        $(#[$meta:meta])*
+        $vis:vis enum $name:ident {
+            $( $(#[$vmeta:meta])* $variant:ident($ty:ty) = $type_url:path ),+
+        }
+        $( fallback { $(#[$fmeta:meta])* $fallback:ident($fty:ty) } )?
+    ) => {
+        $(#[$meta])*
+        $vis enum $name {
+            $( $(#[$vmeta])* $variant($ty), )+
+            $( $(#[$fmeta])* $fallback($fty), )?
+        }
+
+        impl $crate::testapp::ibc::clients::macros::Protobuf<$crate::testapp::ibc::clients::macros::Any>
+            for $name
+        {
+        }
+
+        impl ::core::convert::TryFrom<$crate::testapp::ibc::clients::macros::Any> for $name {
+            type Error = $crate::testapp::ibc::clients::macros::ClientError;
+
+            fn try_from(
+                $raw: $crate::testapp::ibc::clients::macros::Any,
+            ) -> ::core::result::Result<Self, Self::Error> {
+                $(
+                    if $raw.type_url == $type_url {
+                        return ::core::result::Result::Ok(Self::$variant(
+                            <$ty as ::core::convert::TryFrom<
+                                $crate::testapp::ibc::clients::macros::Any,
+                            >>::try_from($raw)?,
+                        ));
+                    }
+                )+
+
+                $crate::__define_any_state!(@unknown $raw, $unknown $(, $fallback)?)
+            }
+        }
+
+        impl ::core::convert::From<$name> for $crate::testapp::ibc::clients::macros::Any {
+            fn from(state: $name) -> Self {
+                match state {
+                    $( $name::$variant(state) => state.into(), )+
+                    $( $name::$fallback(state) => state.into(), )?
+                }
+            }
+        }
+    };
+    (@unknown $raw:ident, $unknown:expr) => {
+        ::core::result::Result::Err($unknown)
+    };
+    (@unknown $raw:ident, $unknown:expr, $fallback:ident) => {
+        ::core::result::Result::Ok(Self::$fallback($raw.into()))
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use ibc::core::client::types::Height;
+    use ibc::core::primitives::prelude::*;
+
+    use super::*;
+    use crate::testapp::ibc::clients::mock::client_state::{
+        MockClientState, MOCK_CLIENT_STATE_TYPE_URL,
+    };
+    use crate::testapp::ibc::clients::mock::consensus_state::{
+        MockConsensusState, MOCK_CONSENSUS_STATE_TYPE_URL,
+    };
+    use crate::testapp::ibc::clients::mock::header::MockHeader;
+    use crate::testapp::ibc::clients::unknown::UnknownClientState;
+
+    crate::define_any_client! {
+        #[derive(Clone, Debug, PartialEq)]
+        enum MockOnlyClientState {
+            Mock(MockClientState) = MOCK_CLIENT_STATE_TYPE_URL,
+        }
+        fallback {
+            Unknown(UnknownClientState),
+        }
+    }
+
+    crate::define_any_consensus! {
+        #[derive(Clone, Debug, PartialEq)]
+        enum MockOnlyConsensusState {
+            Mock(MockConsensusState) = MOCK_CONSENSUS_STATE_TYPE_URL,
+        }
+    }
+
+    #[test]
+    fn test_define_any_client() {
+        let client_state =
+            MockOnlyClientState::Mock(MockClientState::new(MockHeader::new(Height::min(0))));
+
+        let decoded =
+            MockOnlyClientState::try_from(Any::from(client_state.clone())).expect("no error");
+        assert_eq!(decoded, client_state);
+
+        let raw = Any {
+            type_url: "/ibc.lightclients.future.v1.ClientState".to_string(),
+            value: vec![1, 2, 3],
+        };
+        let decoded = MockOnlyClientState::try_from(raw.clone()).expect("no error");
+        assert_eq!(
+            decoded,
+            MockOnlyClientState::Unknown(UnknownClientState(raw))
+        );
+    }
+
+    #[test]
+    fn test_define_any_consensus() {
+        let consensus_state =
+            MockOnlyConsensusState::Mock(MockConsensusState::new(MockHeader::new(Height::min(0))));
+
+        let decoded =
+            MockOnlyConsensusState::try_from(Any::from(consensus_state.clone())).expect("no error");
+        assert_eq!(decoded, consensus_state);
+
+        let decoded = MockOnlyConsensusState::try_from(Any {
+            type_url: "/ibc.lightclients.future.v1.ConsensusState".to_string(),
+            value: vec![1, 2, 3],
+        });
+        assert!(matches!(
+            decoded,
+            Err(ClientError::UnknownConsensusStateType { .. })
+        ));
+    }
+}
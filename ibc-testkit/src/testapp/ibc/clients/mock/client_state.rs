@@ -1,4 +1,6 @@
+use alloc::sync::Arc;
 use core::str::FromStr;
+use core::sync::atomic::{AtomicBool, Ordering};
 use core::time::Duration;
 
 use ibc::core::client::context::prelude::*;
@@ -7,6 +9,7 @@ use ibc::core::client::types::{Height, Status};
 use ibc::core::commitment_types::commitment::{
     CommitmentPrefix, CommitmentProofBytes, CommitmentRoot,
 };
+use ibc::core::commitment_types::error::CommitmentError;
 use ibc::core::handler::types::error::ContextError;
 use ibc::core::host::types::identifiers::{ClientId, ClientType};
 use ibc::core::host::types::path::{ClientConsensusStatePath, ClientStatePath, Path};
@@ -30,11 +33,19 @@ pub fn client_type() -> ClientType {
 /// A mock of a client state. For an example of a real structure that this mocks, you can see
 /// `ClientState` of ics07_tendermint/client_state.rs.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct MockClientState {
     pub header: MockHeader,
     pub trusting_period: Duration,
-    pub frozen: bool,
+    /// The height at which the client is frozen, if it is. Only whether the
+    /// client is frozen is part of its encoding, hence a decoded frozen
+    /// client is frozen at height `0-1`, see [`MockClientState::frozen`].
+    pub frozen_height: Option<Height>,
+    /// The pending proof verification failure of the store the client state
+    /// was read from. Not part of its encoding, see
+    /// [`MockContext::fail_next_proof_verification`](crate::context::MockContext::fail_next_proof_verification).
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) proof_verification_failure: ProofVerificationFailure,
 }
 
 impl MockClientState {
@@ -45,7 +56,8 @@ impl MockClientState {
         Self {
             header,
             trusting_period: Duration::from_secs(64000),
-            frozen: false,
+            frozen_height: None,
+            proof_verification_failure: ProofVerificationFailure::default(),
         }
     }
 
@@ -64,29 +76,80 @@ impl MockClientState {
         }
     }
 
+    /// Freezes the client at height `0-1`, which stands for a client frozen
+    /// regardless of the height of the misbehaviour, as in ibc-go.
     pub fn frozen(self) -> Self {
+        self.with_frozen_height(Height::min(0))
+    }
+
+    /// Freezes the client at the given height, making it report
+    /// [`Status::Frozen`].
+    pub fn with_frozen_height(self, frozen_height: Height) -> Self {
         Self {
-            frozen: true,
+            frozen_height: Some(frozen_height),
             ..self
         }
     }
 
     pub fn unfrozen(self) -> Self {
         Self {
-            frozen: false,
+            frozen_height: None,
             ..self
         }
     }
 
+    pub fn frozen_height(&self) -> Option<Height> {
+        self.frozen_height
+    }
+
     pub fn is_frozen(&self) -> bool {
-        self.frozen
+        self.frozen_height.is_some()
     }
 
     fn expired(&self, elapsed: Duration) -> bool {
         elapsed > self.trusting_period
     }
+
+    /// Any proof is valid, unless a verification failure is pending on the
+    /// store the client state was read from, which is then cleared.
+    fn verify_proof(&self) -> Result<(), ClientError> {
+        if self.proof_verification_failure.take() {
+            return Err(ClientError::Ics23Verification(
+                CommitmentError::VerificationFailure,
+            ));
+        }
+
+        Ok(())
+    }
 }
 
+/// A handle to the flag of a store telling whether the next proof verification
+/// fails. It is not part of the client state, hence ignored when comparing
+/// client states.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct ProofVerificationFailure(Option<Arc<AtomicBool>>);
+
+impl ProofVerificationFailure {
+    pub(crate) fn new(pending: Arc<AtomicBool>) -> Self {
+        Self(Some(pending))
+    }
+
+    /// Returns whether a failure is pending, clearing it.
+    fn take(&self) -> bool {
+        self.0
+            .as_ref()
+            .is_some_and(|pending| pending.swap(false, Ordering::SeqCst))
+    }
+}
+
+impl PartialEq for ProofVerificationFailure {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for ProofVerificationFailure {}
+
 impl Protobuf<RawMockClientState> for MockClientState {}
 
 impl TryFrom<RawMockClientState> for MockClientState {
@@ -101,7 +164,8 @@ impl TryFrom<RawMockClientState> for MockClientState {
                 })?
                 .try_into()?,
             trusting_period: Duration::from_nanos(raw.trusting_period),
-            frozen: raw.frozen,
+            frozen_height: raw.frozen.then(|| Height::min(0)),
+            proof_verification_failure: ProofVerificationFailure::default(),
         })
     }
 }
@@ -115,7 +179,7 @@ impl From<MockClientState> for RawMockClientState {
                 .as_nanos()
                 .try_into()
                 .expect("no overflow"),
-            frozen: value.frozen,
+            frozen: value.is_frozen(),
         }
     }
 }
@@ -157,6 +221,18 @@ pub trait MockClientContext {
 
     /// Returns the current height of the local chain.
     fn host_height(&self) -> Result<Height, ContextError>;
+
+    /// Returns the status the given client is forced to report, if any,
+    /// regardless of its actual state. For testing purposes only.
+    fn client_status_override(&self, _client_id: &ClientId) -> Option<Status> {
+        None
+    }
+
+    /// Returns the statuses the given client reports from the paired host
+    /// heights on, sorted by height. For testing purposes only.
+    fn client_status_schedule(&self, _client_id: &ClientId) -> Vec<(Height, Status)> {
+        Vec::new()
+    }
 }
 
 impl ClientStateCommon for MockClientState {
@@ -211,7 +287,7 @@ impl ClientStateCommon for MockClientState {
         _path: Path,
         _value: Vec<u8>,
     ) -> Result<(), ClientError> {
-        Ok(())
+        self.verify_proof()
     }
 
     fn verify_non_membership(
@@ -221,7 +297,7 @@ impl ClientStateCommon for MockClientState {
         _root: &CommitmentRoot,
         _path: Path,
     ) -> Result<(), ClientError> {
-        Ok(())
+        self.verify_proof()
     }
 }
 
@@ -275,10 +351,25 @@ where
     }
 
     fn status(&self, ctx: &V, client_id: &ClientId) -> Result<Status, ClientError> {
+        if let Some(status) = ctx.client_status_override(client_id) {
+            return Ok(status);
+        }
+
         if self.is_frozen() {
             return Ok(Status::Frozen);
         }
 
+        let host_height = ctx.host_height()?;
+
+        if let Some((_, status)) = ctx
+            .client_status_schedule(client_id)
+            .into_iter()
+            .rev()
+            .find(|(height, _)| *height <= host_height)
+        {
+            return Ok(status);
+        }
+
         let latest_consensus_state: MockConsensusState = {
             match ctx.consensus_state(&ClientConsensusStatePath::new(
                 client_id.clone(),
@@ -326,7 +417,7 @@ where
     ) -> Result<(), ClientError> {
         let mock_consensus_state: MockConsensusState = consensus_state.try_into()?;
 
-        ctx.store_client_state(ClientStatePath::new(client_id.clone()), (*self).into())?;
+        ctx.store_client_state(ClientStatePath::new(client_id.clone()), self.clone().into())?;
         ctx.store_consensus_state(
             ClientConsensusStatePath::new(
                 client_id.clone(),
@@ -385,7 +476,7 @@ where
         client_id: &ClientId,
         _client_message: Any,
     ) -> Result<(), ClientError> {
-        let frozen_client_state = self.frozen();
+        let frozen_client_state = self.clone().frozen();
 
         ctx.store_client_state(
             ClientStatePath::new(client_id.clone()),
@@ -444,10 +535,7 @@ where
 
         let latest_height = substitute_client_state.latest_height();
 
-        let new_mock_client_state = MockClientState {
-            frozen: false,
-            ..substitute_client_state
-        };
+        let new_mock_client_state = substitute_client_state.unfrozen();
 
         let host_timestamp = ctx.host_timestamp()?;
         let host_height = ctx.host_height()?;
@@ -496,10 +584,95 @@ mod test {
 
         let client_state = MockClientState::new(MockHeader::default());
         let expected = r#"{"typeUrl":"/ibc.mock.ClientState","value":"CgQKAhABEICAkMrSxg4="}"#;
-        let json = serde_json::to_string(&Any::from(client_state)).unwrap();
+        let json = serde_json::to_string(&Any::from(client_state.clone())).unwrap();
         assert_eq!(json, expected);
 
         let proto_any = serde_json::from_str::<Any>(expected).unwrap();
         assert_eq!(proto_any, Any::from(client_state));
     }
+
+    #[test]
+    fn test_status_schedule() {
+        use ibc::core::client::context::client_state::ClientStateValidation;
+        use ibc::core::client::context::{ClientExecutionContext, ClientValidationContext};
+        use ibc::core::client::types::Status;
+        use ibc::core::host::types::path::ClientStatePath;
+
+        use super::client_type;
+        use crate::context::MockContext;
+
+        let client_id = client_type().build_client_id(0);
+
+        let ctx = MockContext::default();
+        let client_height = ctx.latest_height();
+        let expiry_height = client_height.add(2);
+
+        let mut ctx = ctx
+            .with_client_at_heights(&client_id, &[client_height])
+            .with_client_status_schedule(&client_id, vec![(expiry_height, Status::Expired)]);
+
+        let status = |ctx: &MockContext| {
+            ctx.ibc_store
+                .client_state(&client_id)
+                .expect("client exists")
+                .status(&ctx.ibc_store, &client_id)
+                .expect("no error")
+        };
+
+        assert_eq!(status(&ctx), Status::Active);
+
+        ctx.advance_block_height();
+        assert_eq!(status(&ctx), Status::Active);
+
+        ctx.advance_block_height();
+        assert_eq!(ctx.latest_height(), expiry_height);
+        assert_eq!(status(&ctx), Status::Expired);
+
+        // a frozen client reports so, whatever its schedule
+        let frozen_client_state = ctx
+            .ibc_store
+            .client_state(&client_id)
+            .expect("client exists")
+            .into_mock()
+            .expect("mock client")
+            .frozen();
+        ctx.ibc_store
+            .store_client_state(
+                ClientStatePath::new(client_id.clone()),
+                frozen_client_state.into(),
+            )
+            .expect("no error");
+        assert_eq!(status(&ctx), Status::Frozen);
+
+        // an empty schedule leaves no entry behind
+        let ctx = ctx.with_client_status_schedule(&client_id, Vec::new());
+        assert!(!ctx
+            .ibc_store
+            .client_status_schedules
+            .lock()
+            .contains_key(&client_id));
+    }
+
+    #[test]
+    fn test_frozen_height() {
+        use ibc::core::client::types::Height;
+        use ibc::primitives::proto::Any;
+
+        use super::{MockClientState, MockHeader};
+
+        let frozen_height = Height::new(0, 5).expect("no error");
+        let client_state = MockClientState::new(MockHeader::default());
+
+        assert_eq!(client_state.frozen_height(), None);
+
+        let frozen_client_state = client_state.with_frozen_height(frozen_height);
+
+        assert!(frozen_client_state.is_frozen());
+        assert_eq!(frozen_client_state.frozen_height(), Some(frozen_height));
+
+        // Only the frozen flag survives the encoding.
+        let decoded = MockClientState::try_from(Any::from(frozen_client_state)).expect("no error");
+        assert_eq!(decoded.frozen_height(), Some(Height::min(0)));
+        assert!(!decoded.unfrozen().is_frozen());
+    }
 }
@@ -1,20 +1,33 @@
+pub mod macros;
 pub mod mock;
+pub mod unknown;
 
 use alloc::fmt::Debug;
+use core::fmt::{Display, Error as FmtError, Formatter};
+use core::time::Duration;
 
 use basecoin_store::context::ProvableStore;
 use derive_more::From;
 use ibc::clients::tendermint::client_state::ClientState as TmClientState;
 use ibc::clients::tendermint::consensus_state::ConsensusState as TmConsensusState;
 use ibc::clients::tendermint::types::{
-    ClientState as ClientStateType, ConsensusState as ConsensusStateType,
+    ClientState as ClientStateType, ConsensusState as ConsensusStateType, TrustThreshold,
     TENDERMINT_CLIENT_STATE_TYPE_URL, TENDERMINT_CONSENSUS_STATE_TYPE_URL,
 };
+use ibc::core::client::context::client_state::{ClientStateCommon, ClientStateValidation};
+use ibc::core::client::context::consensus_state::ConsensusState as _;
+use ibc::core::client::context::{ClientValidationContext, ExtClientValidationContext};
 use ibc::core::client::types::error::ClientError;
-use ibc::core::client::types::Height;
+use ibc::core::client::types::proto::v1::Height as RawHeight;
+use ibc::core::client::types::{Height, Status};
+use ibc::core::handler::types::error::ContextError;
+use ibc::core::host::types::identifiers::ClientId;
+use ibc::core::host::types::path::ClientConsensusStatePath;
 use ibc::core::primitives::prelude::*;
 use ibc::derive::{ClientState, ConsensusState};
 use ibc::primitives::proto::{Any, Protobuf};
+use ibc::primitives::Timestamp;
+use tendermint::Hash;
 
 use super::core::types::MockIbcStore;
 use crate::testapp::ibc::clients::mock::client_state::{
@@ -23,20 +36,335 @@ use crate::testapp::ibc::clients::mock::client_state::{
 use crate::testapp::ibc::clients::mock::consensus_state::{
     MockConsensusState, MOCK_CONSENSUS_STATE_TYPE_URL,
 };
+use crate::testapp::ibc::clients::mock::header::MockHeader;
+use crate::testapp::ibc::clients::unknown::UnknownClientState;
 
-#[derive(Debug, Clone, From, PartialEq, ClientState)]
-#[validation(MockIbcStore<S: ProvableStore + Debug>)]
-#[execution(MockIbcStore<S: ProvableStore + Debug>)]
-pub enum AnyClientState {
-    Tendermint(TmClientState),
-    Mock(MockClientState),
+crate::define_any_client! {
+    /// The client states supported by the mock IBC store.
+    ///
+    /// The canonical encoding of a client state is the protobuf [`Any`] of its
+    /// variant, as given by `From<AnyClientState> for Any`. Decoding it back with
+    /// [`TryFrom<Any>`] yields a client state equal to the original one, so that a
+    /// stored and reloaded client state is unchanged.
+    ///
+    /// Both conversions are generated by [`define_any_client!`](crate::define_any_client),
+    /// which host enums over other client types can follow.
+    #[derive(Debug, Clone, From, PartialEq, ClientState)]
+    #[validation(MockIbcStore<S: ProvableStore + Debug>)]
+    #[execution(MockIbcStore<S: ProvableStore + Debug>)]
+    pub enum AnyClientState {
+        Tendermint(TmClientState) = TENDERMINT_CLIENT_STATE_TYPE_URL,
+        Mock(MockClientState) = MOCK_CLIENT_STATE_TYPE_URL,
+    }
+    fallback {
+        /// Fallback for client states of unrecognized types, preserving their
+        /// raw encoding. Such clients cannot be validated or updated.
+        Unknown(UnknownClientState),
+    }
+}
+
+/// The client types known to [`AnyClientState`] and [`AnyConsensusState`], as
+/// given by the type URL of their protobuf [`Any`].
+///
+/// Parsing the type URL once, e.g. for a batch of states read from the same
+/// store, lets them be decoded with
+/// [`AnyClientState::try_from_any_with_type_url`] and
+/// [`AnyConsensusState::try_from_any_with_type_url`] without comparing it
+/// again for each of them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ClientTypeUrl {
+    Tendermint,
+    Mock,
+    Unknown,
+}
+
+impl ClientTypeUrl {
+    /// Parses the type URL of either a client state or a consensus state.
+    pub fn parse(type_url: &str) -> Self {
+        match type_url {
+            TENDERMINT_CLIENT_STATE_TYPE_URL | TENDERMINT_CONSENSUS_STATE_TYPE_URL => {
+                Self::Tendermint
+            }
+            MOCK_CLIENT_STATE_TYPE_URL | MOCK_CONSENSUS_STATE_TYPE_URL => Self::Mock,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+fn decode_error(e: impl Display) -> ClientError {
+    ClientError::Other {
+        description: e.to_string(),
+    }
+}
+
+/// The parameters a light client checks headers against.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LightClientParams {
+    /// Fraction of the trusted validator set that must sign a new header.
+    pub trust_level: TrustThreshold,
+    /// Duration for which a trusted consensus state remains valid.
+    pub trusting_period: Duration,
+    /// Unbonding period of the counterparty chain.
+    pub unbonding_period: Duration,
+    /// Tolerated drift between the local and the counterparty clocks.
+    pub max_clock_drift: Duration,
 }
 
 impl AnyClientState {
+    /// Returns the type of the client state, as given by its type URL.
+    pub fn type_url_enum(&self) -> ClientTypeUrl {
+        match self {
+            Self::Tendermint(_) => ClientTypeUrl::Tendermint,
+            Self::Mock(_) => ClientTypeUrl::Mock,
+            Self::Unknown(_) => ClientTypeUrl::Unknown,
+        }
+    }
+
+    /// Decodes a client state of the given type, skipping the comparison of
+    /// the type URL of the raw [`Any`]. Yields the same client state as
+    /// [`TryFrom<Any>`] as long as `type_url` was parsed from `raw.type_url`.
+    pub fn try_from_any_with_type_url(
+        type_url: ClientTypeUrl,
+        raw: Any,
+    ) -> Result<Self, ClientError> {
+        use ibc::clients::tendermint::types::proto::v1::ClientState as RawTmClientState;
+
+        use crate::testapp::ibc::clients::mock::proto::ClientState as RawMockClientState;
+
+        match type_url {
+            ClientTypeUrl::Tendermint => Protobuf::<RawTmClientState>::decode(raw.value.as_slice())
+                .map(Self::Tendermint)
+                .map_err(decode_error),
+            ClientTypeUrl::Mock => Protobuf::<RawMockClientState>::decode(raw.value.as_slice())
+                .map(Self::Mock)
+                .map_err(decode_error),
+            ClientTypeUrl::Unknown => Ok(Self::Unknown(raw.into())),
+        }
+    }
+
+    /// Decodes a client state from its raw protobuf bytes and type URL, e.g.
+    /// as read back from a database. Follows [`TryFrom<Any>`], so unrecognized
+    /// type URLs yield [`AnyClientState::Unknown`].
+    pub fn from_proto_bytes(type_url: &str, bytes: &[u8]) -> Result<Self, ClientError> {
+        Self::try_from(Any {
+            type_url: type_url.to_string(),
+            value: bytes.to_vec(),
+        })
+    }
+
+    /// Returns the Tendermint client state, if the client is of that type.
+    pub fn as_tendermint(&self) -> Option<&TmClientState> {
+        match self {
+            Self::Tendermint(cs) => Some(cs),
+            _ => None,
+        }
+    }
+
+    /// Returns the mock client state, if the client is of that type.
+    pub fn as_mock(&self) -> Option<&MockClientState> {
+        match self {
+            Self::Mock(cs) => Some(cs),
+            _ => None,
+        }
+    }
+
+    /// Converts into the Tendermint client state, if the client is of that type.
+    pub fn into_tendermint(self) -> Option<TmClientState> {
+        match self {
+            Self::Tendermint(cs) => Some(cs),
+            _ => None,
+        }
+    }
+
+    /// Converts into the mock client state, if the client is of that type.
+    pub fn into_mock(self) -> Option<MockClientState> {
+        match self {
+            Self::Mock(cs) => Some(cs),
+            _ => None,
+        }
+    }
+
     pub fn latest_height(&self) -> Height {
         match self {
             Self::Tendermint(cs) => cs.inner().latest_height,
             Self::Mock(cs) => cs.latest_height(),
+            Self::Unknown(_) => Height::min(0),
+        }
+    }
+
+    /// Returns the latest height in its protobuf representation, which agrees
+    /// with the JSON one of [`Self::latest_height`] field by field.
+    pub fn latest_height_proto(&self) -> RawHeight {
+        self.latest_height().into()
+    }
+
+    /// Returns the trust level of the client, if its type has one.
+    pub fn trust_threshold(&self) -> Option<TrustThreshold> {
+        match self {
+            Self::Tendermint(cs) => Some(cs.trust_threshold()),
+            Self::Mock(_) | Self::Unknown(_) => None,
+        }
+    }
+
+    /// Returns the parameters the client verifies headers with, if its type has any.
+    pub fn verification_params(&self) -> Option<LightClientParams> {
+        match self {
+            Self::Tendermint(cs) => {
+                let inner = cs.inner();
+                Some(LightClientParams {
+                    trust_level: inner.trust_level,
+                    trusting_period: inner.trusting_period,
+                    unbonding_period: inner.unbonding_period,
+                    max_clock_drift: inner.max_clock_drift,
+                })
+            }
+            Self::Mock(_) | Self::Unknown(_) => None,
+        }
+    }
+
+    /// Returns the upgrade path the client expects upgrade proofs against,
+    /// i.e. the store keys under which the upgraded client and consensus
+    /// states are committed. Empty if its type does not support upgrades.
+    pub fn upgrade_path(&self) -> Vec<String> {
+        match self {
+            Self::Tendermint(cs) => cs.inner().upgrade_path.clone(),
+            Self::Mock(_) | Self::Unknown(_) => Vec::new(),
+        }
+    }
+
+    /// Returns true if a client of this type may act as the substitute of a
+    /// client recovery, e.g. for recovery tooling to filter candidates.
+    ///
+    /// Mock clients accept any substitute and recover from it, hence are
+    /// eligible too. Clients of an unknown type cannot be verified and never
+    /// are.
+    pub fn supports_substitution(&self) -> bool {
+        match self {
+            Self::Tendermint(_) | Self::Mock(_) => true,
+            Self::Unknown(_) => false,
+        }
+    }
+
+    /// Returns a minimal consensus state to create the client with, to be
+    /// stored at its latest height.
+    ///
+    /// A Tendermint consensus state gets a placeholder commitment root, no
+    /// next validator set hash and the Unix epoch as timestamp, hence the
+    /// client is expired until it is updated. `None` is returned for clients
+    /// of an unknown type.
+    pub fn genesis_consensus_state(&self) -> Option<AnyConsensusState> {
+        match self {
+            Self::Tendermint(_) => Some(
+                ConsensusStateType::new(vec![0].into(), tendermint::Time::unix_epoch(), Hash::None)
+                    .into(),
+            ),
+            Self::Mock(_) => {
+                Some(MockConsensusState::new(MockHeader::new(self.latest_height())).into())
+            }
+            Self::Unknown(_) => None,
+        }
+    }
+
+    /// Returns true if the client is active and can thus accept updates,
+    /// i.e. it is neither frozen nor expired, according to its status on
+    /// the given context. Errors computing the status count as not updatable.
+    pub fn is_updatable<S>(&self, ctx: &MockIbcStore<S>, client_id: &ClientId) -> bool
+    where
+        S: ProvableStore + Debug,
+    {
+        matches!(self.status(ctx, client_id), Ok(Status::Active))
+    }
+
+    /// Returns the heights at which a consensus state of the client is
+    /// stored on the given context, whatever the type of the client.
+    pub fn consensus_heights<V>(
+        &self,
+        ctx: &V,
+        client_id: &ClientId,
+    ) -> Result<Vec<Height>, ContextError>
+    where
+        V: ExtClientValidationContext,
+    {
+        ctx.consensus_state_heights(client_id)
+    }
+
+    /// Returns the time at which the client expires, i.e. the timestamp of
+    /// its latest consensus state on the given context plus its trusting
+    /// period.
+    ///
+    /// Only Tendermint clients expire: `None` is returned for other client
+    /// types, as well as if the consensus state cannot be read or carries no
+    /// timestamp.
+    pub fn expiry_time<S>(&self, ctx: &MockIbcStore<S>, client_id: &ClientId) -> Option<Timestamp>
+    where
+        S: ProvableStore + Debug,
+    {
+        let Self::Tendermint(cs) = self else {
+            return None;
+        };
+
+        let latest_height = self.latest_height();
+        let consensus_state = ctx
+            .consensus_state(&ClientConsensusStatePath::new(
+                client_id.clone(),
+                latest_height.revision_number(),
+                latest_height.revision_height(),
+            ))
+            .ok()?;
+
+        let timestamp = consensus_state.timestamp();
+        if !timestamp.is_set() {
+            return None;
+        }
+
+        (timestamp + cs.inner().trusting_period).ok()
+    }
+
+    /// Checks that the given client state may substitute this one during a
+    /// client recovery, i.e. that both share the same security parameters.
+    ///
+    /// Both client states must be of the same type. Tendermint clients must
+    /// also track the same chain with the same trust level. Note that this is
+    /// stricter than the ICS-07 substitution check, which lets the chain ID
+    /// change. Unknown client states are never compatible.
+    pub fn compatible_for_substitution(&self, other: &AnyClientState) -> Result<(), ClientError> {
+        match (self, other) {
+            (Self::Tendermint(subject), Self::Tendermint(substitute)) => {
+                let (subject, substitute) = (subject.inner(), substitute.inner());
+
+                if subject.chain_id != substitute.chain_id {
+                    return Err(ClientError::ClientSpecific {
+                        description: format!(
+                            "substitute tracks chain `{}` while subject tracks chain `{}`",
+                            substitute.chain_id, subject.chain_id
+                        ),
+                    });
+                }
+
+                if subject.trust_level != substitute.trust_level {
+                    return Err(ClientError::ClientSpecific {
+                        description: format!(
+                            "substitute trust level `{}` differs from subject trust level `{}`",
+                            substitute.trust_level, subject.trust_level
+                        ),
+                    });
+                }
+
+                Ok(())
+            }
+            (Self::Mock(_), Self::Mock(_)) => Ok(()),
+            (Self::Unknown(cs), _) | (_, Self::Unknown(cs)) => {
+                Err(ClientError::UnknownClientStateType {
+                    client_state_type: cs.type_url().to_string(),
+                })
+            }
+            _ => Err(ClientError::ClientSpecific {
+                description: format!(
+                    "substitute of type `{}` cannot replace subject of type `{}`",
+                    other.client_type(),
+                    self.client_type()
+                ),
+            }),
         }
     }
 
@@ -44,37 +372,105 @@ impl AnyClientState {
         match self {
             Self::Tendermint(cs) => cs.inner().is_frozen(),
             Self::Mock(cs) => cs.is_frozen(),
+            Self::Unknown(_) => false,
         }
     }
-}
 
-impl Protobuf<Any> for AnyClientState {}
+    /// Returns a one-line human readable summary of the client state, e.g.
+    /// for CLI tools, made of its type, chain ID if any, latest height and
+    /// status.
+    ///
+    /// The status is derived from the client state alone: without a host
+    /// context, an expired client is still reported as active.
+    pub fn summary(&self) -> String {
+        let status = match self {
+            Self::Unknown(_) => Status::Unauthorized,
+            _ if self.is_frozen() => Status::Frozen,
+            _ => Status::Active,
+        };
 
-impl TryFrom<Any> for AnyClientState {
-    type Error = ClientError;
+        match self {
+            Self::Tendermint(cs) => format!(
+                "{} chain_id={} latest_height={} status={status}",
+                self.client_type(),
+                cs.inner().chain_id(),
+                self.latest_height(),
+            ),
+            Self::Mock(_) => format!(
+                "{} latest_height={} status={status}",
+                self.client_type(),
+                self.latest_height(),
+            ),
+            Self::Unknown(cs) => format!(
+                "{} type_url={} status={status}",
+                self.client_type(),
+                cs.type_url(),
+            ),
+        }
+    }
 
-    fn try_from(raw: Any) -> Result<Self, Self::Error> {
-        if raw.type_url == TENDERMINT_CLIENT_STATE_TYPE_URL {
-            Ok(TmClientState::try_from(raw)?.into())
-        } else if raw.type_url == MOCK_CLIENT_STATE_TYPE_URL {
-            MockClientState::try_from(raw).map(Into::into)
-        } else {
-            Err(ClientError::Other {
-                description: "failed to deserialize message".to_string(),
-            })
+    /// Returns a deterministic JSON of the client state, e.g. for snapshot
+    /// tests, as an object holding its `type_url` and its `value`.
+    ///
+    /// The value is the JSON of the raw protobuf client state, hence only
+    /// holds the persisted fields. The keys of every object are sorted, and
+    /// the raw bytes of client states of an unknown type are kept as is.
+    #[cfg(feature = "serde")]
+    pub fn to_debug_json(&self) -> String {
+        use ibc::clients::tendermint::types::proto::v1::ClientState as RawTmClientState;
+        use serde_json::{json, Value};
+
+        use crate::testapp::ibc::clients::mock::proto::ClientState as RawMockClientState;
+
+        let value = match self {
+            Self::Tendermint(cs) => serde_json::to_value(RawTmClientState::from(cs.clone())),
+            Self::Mock(cs) => serde_json::to_value(RawMockClientState::from(cs.clone())),
+            Self::Unknown(cs) => serde_json::to_value(&cs.0.value),
         }
+        .expect("raw client states always serialize to JSON");
+
+        let type_url = Any::from(self.clone()).type_url;
+
+        sort_json_keys(json!({ "type_url": type_url, "value": value })).to_string()
     }
 }
 
-impl From<AnyClientState> for Any {
-    fn from(host_client_state: AnyClientState) -> Self {
-        match host_client_state {
-            AnyClientState::Tendermint(cs) => cs.into(),
-            AnyClientState::Mock(cs) => cs.into(),
+/// A concise summary naming the variant along with its identifying fields,
+/// e.g. `Tendermint(chain=ibc-0, height=0-10)`, unlike the verbose [`Debug`].
+impl Display for AnyClientState {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match self {
+            Self::Tendermint(cs) => write!(
+                f,
+                "Tendermint(chain={}, height={})",
+                cs.inner().chain_id(),
+                self.latest_height()
+            ),
+            Self::Mock(cs) => write!(f, "Mock(height={})", cs.latest_height()),
+            Self::Unknown(cs) => write!(f, "Unknown(type_url={})", cs.type_url()),
         }
     }
 }
 
+/// Rebuilds the objects of the given JSON value with their keys in order,
+/// whether or not `serde_json` preserves insertion order.
+#[cfg(feature = "serde")]
+fn sort_json_keys(value: serde_json::Value) -> serde_json::Value {
+    use serde_json::Value;
+
+    match value {
+        Value::Object(map) => Value::Object(
+            map.into_iter()
+                .map(|(key, value)| (key, sort_json_keys(value)))
+                .collect::<BTreeMap<_, _>>()
+                .into_iter()
+                .collect(),
+        ),
+        Value::Array(values) => Value::Array(values.into_iter().map(sort_json_keys).collect()),
+        value => value,
+    }
+}
+
 impl From<ClientStateType> for AnyClientState {
     fn from(client_state: ClientStateType) -> Self {
         Self::Tendermint(client_state.into())
@@ -87,33 +483,112 @@ impl From<ConsensusStateType> for AnyConsensusState {
     }
 }
 
-#[derive(Debug, Clone, From, PartialEq, Eq, ConsensusState)]
-pub enum AnyConsensusState {
-    Tendermint(TmConsensusState),
-    Mock(MockConsensusState),
+crate::define_any_consensus! {
+    /// The consensus states supported by the mock IBC store.
+    ///
+    /// Decoding a consensus state of any other type fails with
+    /// [`ClientError::UnknownConsensusStateType`].
+    #[derive(Debug, Clone, From, PartialEq, Eq, ConsensusState)]
+    pub enum AnyConsensusState {
+        Tendermint(TmConsensusState) = TENDERMINT_CONSENSUS_STATE_TYPE_URL,
+        Mock(MockConsensusState) = MOCK_CONSENSUS_STATE_TYPE_URL,
+    }
 }
 
-impl TryFrom<Any> for AnyConsensusState {
-    type Error = ClientError;
+impl AnyConsensusState {
+    /// Returns the type of the consensus state, as given by its type URL.
+    pub fn type_url_enum(&self) -> ClientTypeUrl {
+        match self {
+            Self::Tendermint(_) => ClientTypeUrl::Tendermint,
+            Self::Mock(_) => ClientTypeUrl::Mock,
+        }
+    }
 
-    fn try_from(raw: Any) -> Result<Self, Self::Error> {
-        if raw.type_url == TENDERMINT_CONSENSUS_STATE_TYPE_URL {
-            Ok(TmConsensusState::try_from(raw)?.into())
-        } else if raw.type_url == MOCK_CONSENSUS_STATE_TYPE_URL {
-            MockConsensusState::try_from(raw).map(Into::into)
-        } else {
-            Err(ClientError::Other {
-                description: "failed to deserialize message".to_string(),
-            })
+    /// Decodes a consensus state of the given type, skipping the comparison
+    /// of the type URL of the raw [`Any`]. Yields the same consensus state as
+    /// [`TryFrom<Any>`] as long as `type_url` was parsed from `raw.type_url`.
+    pub fn try_from_any_with_type_url(
+        type_url: ClientTypeUrl,
+        raw: Any,
+    ) -> Result<Self, ClientError> {
+        use ibc::clients::tendermint::types::proto::v1::ConsensusState as RawTmConsensusState;
+
+        use crate::testapp::ibc::clients::mock::proto::ConsensusState as RawMockConsensusState;
+
+        match type_url {
+            ClientTypeUrl::Tendermint => {
+                Protobuf::<RawTmConsensusState>::decode(raw.value.as_slice())
+                    .map(Self::Tendermint)
+                    .map_err(decode_error)
+            }
+            ClientTypeUrl::Mock => Protobuf::<RawMockConsensusState>::decode(raw.value.as_slice())
+                .map(Self::Mock)
+                .map_err(decode_error),
+            ClientTypeUrl::Unknown => Err(ClientError::UnknownConsensusStateType {
+                consensus_state_type: raw.type_url,
+            }),
+        }
+    }
+
+    /// Returns a mock consensus state at the given timestamp, e.g. to seed a
+    /// time-ordered consensus state history. Its height is the minimal one of
+    /// revision 0, hence callers storing several states should key them by
+    /// height themselves.
+    pub fn mock_at(timestamp: Timestamp) -> Self {
+        MockConsensusState::new(MockHeader::new(Height::min(0)).with_timestamp(timestamp)).into()
+    }
+
+    /// Returns the hash of the next validator set, if the consensus state
+    /// tracks one.
+    pub fn next_validators_hash(&self) -> Option<Hash> {
+        match self {
+            Self::Tendermint(cs) => Some(cs.inner().next_validators_hash),
+            Self::Mock(_) => None,
+        }
+    }
+
+    /// Returns true if less than `trusting_period` has elapsed between the
+    /// timestamp of the consensus state and `now`.
+    ///
+    /// A consensus state ahead of `now` is fresh, while one without timestamp
+    /// never is.
+    pub fn is_fresh(&self, now: Timestamp, trusting_period: Duration) -> bool {
+        let timestamp = self.timestamp();
+
+        match now.duration_since(&timestamp) {
+            Some(elapsed) => elapsed < trusting_period,
+            None => now.is_set() && timestamp.is_set(),
         }
     }
+
+    /// Returns the commitment root of the consensus state as a lowercase hex
+    /// string, e.g. to cross-reference it with a block explorer.
+    pub fn root_hex(&self) -> String {
+        self.root()
+            .as_bytes()
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect()
+    }
 }
 
-impl From<AnyConsensusState> for Any {
-    fn from(host_consensus_state: AnyConsensusState) -> Self {
-        match host_consensus_state {
-            AnyConsensusState::Tendermint(cs) => cs.into(),
-            AnyConsensusState::Mock(cs) => cs.into(),
+/// A concise summary naming the variant along with its identifying fields,
+/// unlike the verbose [`Debug`]. Tendermint consensus states carry no height,
+/// see [`AnyConsensusState`].
+impl Display for AnyConsensusState {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match self {
+            Self::Tendermint(cs) => write!(
+                f,
+                "Tendermint(timestamp={}, next_validators_hash={})",
+                self.timestamp(),
+                cs.inner().next_validators_hash
+            ),
+            Self::Mock(cs) => write!(
+                f,
+                "Mock(height={}, timestamp={})",
+                cs.header.height, cs.header.timestamp
+            ),
         }
     }
 }
@@ -144,3 +619,646 @@ impl TryFrom<AnyConsensusState> for MockConsensusState {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::clients::tendermint::dummy_tm_client_state_from_raw;
+
+    #[test]
+    fn test_from_proto_bytes_round_trip() {
+        let client_state = AnyClientState::from(
+            dummy_tm_client_state_from_raw(RawHeight {
+                revision_number: 0,
+                revision_height: 0,
+            })
+            .expect("no error"),
+        );
+
+        let raw = Any::from(client_state.clone());
+        assert_eq!(raw.type_url, TENDERMINT_CLIENT_STATE_TYPE_URL);
+
+        let decoded =
+            AnyClientState::from_proto_bytes(&raw.type_url, &raw.value).expect("no error");
+
+        assert_eq!(decoded, client_state);
+    }
+
+    #[test]
+    fn test_client_state_any_round_trip() {
+        use ibc::core::host::types::identifiers::ChainId;
+
+        use crate::fixtures::clients::tendermint::ClientStateConfig;
+        use crate::testapp::ibc::clients::mock::header::MockHeader;
+
+        let height = Height::new(1, 10).expect("no error");
+        let timestamp = Timestamp::from_nanoseconds(1_000_000_000_123).expect("no error");
+        let mock_client_state =
+            MockClientState::new(MockHeader::new(height).with_timestamp(timestamp))
+                .with_trusting_period(Duration::from_nanos(1_234_567_891));
+
+        let client_states: Vec<AnyClientState> = vec![
+            ClientStateConfig::builder()
+                .upgrade_path(vec!["upgrade".to_string(), "upgradedIBCState".to_string()])
+                .build()
+                .into_client_state(ChainId::new("ibc-1").expect("no error"), height)
+                .expect("no error")
+                .into(),
+            dummy_tm_client_state_from_raw(RawHeight {
+                revision_number: 0,
+                revision_height: 0,
+            })
+            .expect("no error")
+            .into(),
+            dummy_tm_client_state_from_raw(RawHeight {
+                revision_number: 0,
+                revision_height: 5,
+            })
+            .expect("no error")
+            .into(),
+            MockClientState::new(MockHeader::new(Height::min(0))).into(),
+            mock_client_state.clone().into(),
+            mock_client_state.frozen().into(),
+        ];
+
+        for client_state in client_states {
+            let decoded =
+                AnyClientState::try_from(Any::from(client_state.clone())).expect("no error");
+
+            assert_eq!(decoded, client_state);
+        }
+    }
+
+    #[test]
+    fn test_try_from_any_with_type_url() {
+        let client_states: Vec<AnyClientState> = vec![
+            dummy_tm_client_state_from_raw(RawHeight {
+                revision_number: 0,
+                revision_height: 5,
+            })
+            .expect("no error")
+            .into(),
+            MockClientState::new(MockHeader::new(Height::min(0))).into(),
+            UnknownClientState(Any {
+                type_url: "/ibc.lightclients.future.v1.ClientState".to_string(),
+                value: vec![1, 2, 3],
+            })
+            .into(),
+        ];
+
+        for client_state in client_states {
+            let raw = Any::from(client_state.clone());
+            let type_url = ClientTypeUrl::parse(&raw.type_url);
+            assert_eq!(type_url, client_state.type_url_enum());
+
+            let decoded = AnyClientState::try_from_any_with_type_url(type_url, raw.clone())
+                .expect("no error");
+            assert_eq!(decoded, AnyClientState::try_from(raw).expect("no error"));
+            assert_eq!(decoded, client_state);
+        }
+
+        let consensus_states: Vec<AnyConsensusState> = vec![
+            ConsensusStateType::new(
+                vec![0x00, 0x1f, 0xab, 0xff].into(),
+                tendermint::Time::unix_epoch(),
+                Hash::Sha256([1; 32]),
+            )
+            .into(),
+            AnyConsensusState::mock_at(Timestamp::from_nanoseconds(5).expect("no error")),
+        ];
+
+        for consensus_state in consensus_states {
+            let raw = Any::from(consensus_state.clone());
+            let type_url = ClientTypeUrl::parse(&raw.type_url);
+            assert_eq!(type_url, consensus_state.type_url_enum());
+
+            let decoded = AnyConsensusState::try_from_any_with_type_url(type_url, raw.clone())
+                .expect("no error");
+            assert_eq!(decoded, AnyConsensusState::try_from(raw).expect("no error"));
+            assert_eq!(decoded, consensus_state);
+        }
+
+        assert!(matches!(
+            AnyConsensusState::try_from_any_with_type_url(
+                ClientTypeUrl::Unknown,
+                Any {
+                    type_url: "/ibc.lightclients.future.v1.ConsensusState".to_string(),
+                    value: vec![1, 2, 3],
+                }
+            ),
+            Err(ClientError::UnknownConsensusStateType { .. })
+        ));
+    }
+
+    #[test]
+    fn test_downcast() {
+        use crate::testapp::ibc::clients::mock::header::MockHeader;
+
+        let tm_client_state = dummy_tm_client_state_from_raw(RawHeight {
+            revision_number: 0,
+            revision_height: 0,
+        })
+        .expect("no error");
+        let mock_client_state =
+            MockClientState::new(MockHeader::new(Height::new(0, 10).expect("no error")));
+
+        let client_state = AnyClientState::from(tm_client_state.clone());
+        assert_eq!(client_state.as_tendermint(), Some(&tm_client_state));
+        assert_eq!(client_state.as_mock(), None);
+        assert_eq!(client_state.clone().into_mock(), None);
+        assert_eq!(client_state.into_tendermint(), Some(tm_client_state));
+
+        let client_state = AnyClientState::from(mock_client_state.clone());
+        assert_eq!(client_state.as_mock(), Some(&mock_client_state));
+        assert_eq!(client_state.as_tendermint(), None);
+        assert_eq!(client_state.clone().into_tendermint(), None);
+        assert_eq!(client_state.into_mock(), Some(mock_client_state));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_to_debug_json_is_stable() {
+        use crate::testapp::ibc::clients::mock::header::MockHeader;
+
+        let tm_client_state = || -> AnyClientState {
+            dummy_tm_client_state_from_raw(RawHeight {
+                revision_number: 0,
+                revision_height: 5,
+            })
+            .expect("no error")
+            .into()
+        };
+        let mock_client_state = || -> AnyClientState {
+            MockClientState::new(MockHeader::new(Height::new(0, 5).expect("no error"))).into()
+        };
+
+        for (first, second) in [
+            (tm_client_state(), tm_client_state()),
+            (mock_client_state(), mock_client_state()),
+        ] {
+            let json = first.to_debug_json();
+
+            assert_eq!(json, second.to_debug_json());
+            assert!(json.starts_with(&format!(
+                r#"{{"type_url":"{}","value":{{"#,
+                Any::from(first).type_url
+            )));
+        }
+    }
+
+    #[test]
+    fn test_display() {
+        use ibc::core::host::types::identifiers::ChainId;
+
+        use crate::fixtures::clients::tendermint::ClientStateConfig;
+        use crate::testapp::ibc::clients::mock::header::MockHeader;
+
+        let height = Height::new(0, 10).expect("no error");
+
+        let tm_client_state = AnyClientState::from(
+            ClientStateConfig::builder()
+                .build()
+                .into_client_state(ChainId::new("ibc-0").expect("no error"), height)
+                .expect("no error"),
+        );
+        assert_eq!(
+            tm_client_state.to_string(),
+            "Tendermint(chain=ibc-0, height=0-10)"
+        );
+        assert_ne!(tm_client_state.to_string(), format!("{tm_client_state:?}"));
+
+        let mock_header = MockHeader::new(height);
+
+        let mock_client_state = AnyClientState::from(MockClientState::new(mock_header));
+        assert_eq!(mock_client_state.to_string(), "Mock(height=0-10)");
+
+        let unknown_client_state = AnyClientState::from(UnknownClientState(Any {
+            type_url: "/ibc.lightclients.future.v1.ClientState".to_string(),
+            value: vec![],
+        }));
+        assert_eq!(
+            unknown_client_state.to_string(),
+            "Unknown(type_url=/ibc.lightclients.future.v1.ClientState)"
+        );
+
+        let mock_consensus_state = AnyConsensusState::from(MockConsensusState::new(mock_header));
+        let display = mock_consensus_state.to_string();
+        assert!(display.starts_with("Mock("), "display: {display}");
+        assert!(display.contains("height=0-10"), "display: {display}");
+
+        let tm_consensus_state = AnyConsensusState::from(ConsensusStateType::new(
+            vec![0].into(),
+            tendermint::Time::unix_epoch(),
+            Hash::None,
+        ));
+        let display = tm_consensus_state.to_string();
+        assert!(display.starts_with("Tendermint("), "display: {display}");
+        assert!(display.contains("timestamp="), "display: {display}");
+        assert!(
+            display.contains("next_validators_hash="),
+            "display: {display}"
+        );
+    }
+
+    #[test]
+    fn test_summary() {
+        use ibc::core::host::types::identifiers::ChainId;
+
+        use crate::fixtures::clients::tendermint::ClientStateConfig;
+
+        let client_state = AnyClientState::from(
+            ClientStateConfig::builder()
+                .build()
+                .into_client_state(
+                    ChainId::new("ibc-0").expect("no error"),
+                    Height::new(0, 10).expect("no error"),
+                )
+                .expect("no error"),
+        );
+
+        let summary = client_state.summary();
+
+        assert!(summary.contains("chain_id=ibc-0"), "summary: {summary}");
+        assert!(summary.contains("latest_height=0-10"), "summary: {summary}");
+        assert!(summary.contains("status=Active"), "summary: {summary}");
+    }
+
+    #[test]
+    fn test_is_fresh() {
+        use crate::testapp::ibc::clients::mock::header::MockHeader;
+
+        let now = Timestamp::from_nanoseconds(1_000_000_000_000).expect("no error");
+        let trusting_period = Duration::from_secs(60);
+
+        let consensus_state_at = |elapsed: Duration| {
+            AnyConsensusState::from(MockConsensusState::new(
+                MockHeader::new(Height::min(0)).with_timestamp((now - elapsed).expect("no error")),
+            ))
+        };
+
+        assert!(consensus_state_at(Duration::from_secs(30)).is_fresh(now, trusting_period));
+        assert!(!consensus_state_at(Duration::from_secs(90)).is_fresh(now, trusting_period));
+    }
+
+    #[test]
+    fn test_is_updatable() {
+        use crate::context::MockContext;
+        use crate::hosts::MockHost;
+        use crate::testapp::ibc::clients::mock::client_state::client_type as mock_client_type;
+        use crate::testapp::ibc::core::types::LightClientState;
+
+        let client_id = mock_client_type().build_client_id(0);
+
+        let ctx = MockContext::default().with_light_client(
+            &client_id,
+            LightClientState::<MockHost>::with_latest_height(Height::new(0, 5).expect("no error")),
+        );
+
+        let client_state = ctx
+            .ibc_store
+            .client_state(&client_id)
+            .expect("client exists");
+        assert!(client_state.is_updatable(&ctx.ibc_store, &client_id));
+
+        let AnyClientState::Mock(mock_client_state) = client_state else {
+            panic!("mock client state is expected")
+        };
+        let frozen_client_state = AnyClientState::from(mock_client_state.frozen());
+        assert!(!frozen_client_state.is_updatable(&ctx.ibc_store, &client_id));
+    }
+
+    #[test]
+    fn test_consensus_heights() {
+        use crate::context::MockContext;
+        use crate::testapp::ibc::clients::mock::client_state::client_type as mock_client_type;
+
+        let client_id = mock_client_type().build_client_id(0);
+        let heights = [1, 5, 10].map(|h| Height::new(0, h).expect("no error"));
+
+        let ctx = MockContext::default().with_client_at_heights(&client_id, &heights);
+
+        let client_state = ctx
+            .ibc_store
+            .client_state(&client_id)
+            .expect("client exists");
+
+        let mut consensus_heights = client_state
+            .consensus_heights(&ctx.ibc_store, &client_id)
+            .expect("no error");
+        let mut expected =
+            ExtClientValidationContext::consensus_state_heights(&ctx.ibc_store, &client_id)
+                .expect("no error");
+
+        consensus_heights.sort();
+        expected.sort();
+
+        assert_eq!(consensus_heights, expected);
+        assert_eq!(consensus_heights, heights.to_vec());
+    }
+
+    #[test]
+    fn test_genesis_consensus_state() {
+        use ibc::core::client::types::msgs::{ClientMsg, MsgCreateClient};
+        use ibc::core::handler::types::msgs::MsgEnvelope;
+        use ibc::core::host::types::identifiers::ChainId;
+
+        use crate::context::MockContext;
+        use crate::fixtures::clients::tendermint::ClientStateConfig;
+        use crate::fixtures::core::signer::dummy_account_id;
+        use crate::testapp::ibc::clients::mock::header::MockHeader;
+
+        let latest_height = Height::new(0, 10).expect("no error");
+
+        let tm_client_state = AnyClientState::from(
+            ClientStateConfig::builder()
+                .build()
+                .into_client_state(ChainId::new("ibc-0").expect("no error"), latest_height)
+                .expect("no error"),
+        );
+        let mock_client_state =
+            AnyClientState::from(MockClientState::new(MockHeader::new(latest_height)));
+
+        let mut ctx = MockContext::default();
+
+        for client_state in [tm_client_state, mock_client_state] {
+            let consensus_state = client_state
+                .genesis_consensus_state()
+                .expect("consensus state is built");
+
+            let client_id = ctx.next_client_id(&client_state.client_type());
+            let msg = MsgCreateClient::new(
+                client_state.clone().into(),
+                consensus_state.clone().into(),
+                dummy_account_id(),
+            );
+            ctx.dispatch(MsgEnvelope::from(ClientMsg::from(msg)))
+                .expect("client is created");
+
+            let stored_heights = client_state
+                .consensus_heights(&ctx.ibc_store, &client_id)
+                .expect("no error");
+            assert_eq!(stored_heights, vec![client_state.latest_height()]);
+
+            let stored = ctx
+                .ibc_store
+                .consensus_state(&ClientConsensusStatePath::new(
+                    client_id,
+                    latest_height.revision_number(),
+                    latest_height.revision_height(),
+                ))
+                .expect("consensus state is stored");
+            assert_eq!(stored, consensus_state);
+        }
+
+        let unknown_client_state = AnyClientState::Unknown(UnknownClientState(Any {
+            type_url: "/ibc.lightclients.future.v1.ClientState".to_string(),
+            value: vec![],
+        }));
+        assert_eq!(unknown_client_state.genesis_consensus_state(), None);
+    }
+
+    #[test]
+    fn test_supports_substitution() {
+        let tm_client_state = AnyClientState::from(
+            dummy_tm_client_state_from_raw(RawHeight {
+                revision_number: 0,
+                revision_height: 0,
+            })
+            .expect("no error"),
+        );
+        assert!(tm_client_state.supports_substitution());
+
+        let mock_client_state = AnyClientState::from(MockClientState::new(MockHeader::new(
+            Height::new(0, 1).expect("no error"),
+        )));
+        assert!(mock_client_state.supports_substitution());
+
+        let unknown_client_state = AnyClientState::Unknown(UnknownClientState(Any {
+            type_url: "/ibc.lightclients.future.v1.ClientState".to_string(),
+            value: vec![],
+        }));
+        assert!(!unknown_client_state.supports_substitution());
+    }
+
+    #[test]
+    fn test_mock_at() {
+        let earlier = Timestamp::from_nanoseconds(1_000).expect("no error");
+        let later = Timestamp::from_nanoseconds(2_000).expect("no error");
+
+        let earlier_cs = AnyConsensusState::mock_at(earlier);
+        let later_cs = AnyConsensusState::mock_at(later);
+
+        assert_eq!(earlier_cs.timestamp(), earlier);
+        assert_eq!(later_cs.timestamp(), later);
+        assert!(earlier_cs.timestamp() < later_cs.timestamp());
+    }
+
+    #[test]
+    fn test_expiry_time() {
+        use ibc::clients::tendermint::types::client_type as tm_client_type;
+
+        use crate::context::{MockContext, TendermintContext};
+        use crate::hosts::TendermintHost;
+        use crate::testapp::ibc::clients::mock::header::MockHeader;
+        use crate::testapp::ibc::core::types::{LightClientBuilder, LightClientState};
+
+        let ctx_tm = TendermintContext::default();
+        let light_client: LightClientState<TendermintHost> =
+            LightClientBuilder::init().context(&ctx_tm).build();
+
+        let client_id = tm_client_type().build_client_id(0);
+        let ctx = MockContext::default().with_light_client(&client_id, light_client);
+
+        let client_state = ctx
+            .ibc_store
+            .client_state(&client_id)
+            .expect("client exists");
+        let AnyClientState::Tendermint(tm_client_state) = &client_state else {
+            panic!("tendermint client state is expected")
+        };
+
+        let latest_height = client_state.latest_height();
+        let consensus_state = ctx
+            .ibc_store
+            .consensus_state(&ClientConsensusStatePath::new(
+                client_id.clone(),
+                latest_height.revision_number(),
+                latest_height.revision_height(),
+            ))
+            .expect("consensus state exists");
+
+        let expected = (consensus_state.timestamp() + tm_client_state.inner().trusting_period)
+            .expect("no error");
+
+        assert_eq!(
+            client_state.expiry_time(&ctx.ibc_store, &client_id),
+            Some(expected)
+        );
+
+        let mock_client_state = AnyClientState::from(MockClientState::new(MockHeader::new(
+            Height::new(0, 5).expect("no error"),
+        )));
+        assert_eq!(
+            mock_client_state.expiry_time(&ctx.ibc_store, &client_id),
+            None
+        );
+    }
+
+    #[test]
+    fn test_upgrade_path() {
+        use ibc::core::host::types::identifiers::ChainId;
+
+        use crate::fixtures::clients::tendermint::ClientStateConfig;
+        use crate::testapp::ibc::clients::mock::header::MockHeader;
+
+        let upgrade_path = vec!["upgrade".to_string(), "upgradedIBCState".to_string()];
+
+        let client_state = AnyClientState::from(
+            ClientStateConfig::builder()
+                .upgrade_path(upgrade_path.clone())
+                .build()
+                .into_client_state(
+                    ChainId::new("ibc-0").expect("no error"),
+                    Height::new(0, 10).expect("no error"),
+                )
+                .expect("no error"),
+        );
+
+        assert_eq!(client_state.upgrade_path(), upgrade_path);
+
+        let mock_client_state =
+            AnyClientState::from(MockClientState::new(MockHeader::new(Height::min(0))));
+        assert!(mock_client_state.upgrade_path().is_empty());
+    }
+
+    #[test]
+    fn test_compatible_for_substitution() {
+        use ibc::core::host::types::identifiers::ChainId;
+
+        use crate::fixtures::clients::tendermint::ClientStateConfig;
+        use crate::testapp::ibc::clients::mock::header::MockHeader;
+
+        let tm_client_state = |chain_id: &str, height: u64| {
+            AnyClientState::from(
+                ClientStateConfig::builder()
+                    .build()
+                    .into_client_state(
+                        ChainId::new(chain_id).expect("no error"),
+                        Height::new(0, height).expect("no error"),
+                    )
+                    .expect("no error"),
+            )
+        };
+
+        let subject = tm_client_state("ibc-0", 10);
+
+        subject
+            .compatible_for_substitution(&tm_client_state("ibc-0", 20))
+            .expect("same chain and trust level");
+
+        let err = subject
+            .compatible_for_substitution(&tm_client_state("ibc-1", 20))
+            .expect_err("different chain ID");
+        assert!(
+            err.to_string().contains("ibc-1") && err.to_string().contains("ibc-0"),
+            "error must name both chains, got: {err}"
+        );
+
+        let mock_client_state =
+            AnyClientState::from(MockClientState::new(MockHeader::new(Height::min(0))));
+        assert!(subject
+            .compatible_for_substitution(&mock_client_state)
+            .is_err());
+    }
+
+    #[test]
+    fn test_verification_params() {
+        use ibc::core::host::types::identifiers::ChainId;
+
+        use crate::fixtures::clients::tendermint::ClientStateConfig;
+        use crate::testapp::ibc::clients::mock::header::MockHeader;
+
+        let tm_client_state = ClientStateConfig::builder()
+            .trust_level(TrustThreshold::TWO_THIRDS)
+            .trusting_period(Duration::from_secs(1000))
+            .unbonding_period(Duration::from_secs(2000))
+            .max_clock_drift(Duration::from_secs(5))
+            .build()
+            .into_client_state(
+                ChainId::new("ibc-0").expect("no error"),
+                Height::new(0, 10).expect("no error"),
+            )
+            .expect("no error");
+
+        let inner = tm_client_state.inner().clone();
+        let client_state = AnyClientState::from(tm_client_state);
+
+        assert_eq!(
+            client_state.verification_params(),
+            Some(LightClientParams {
+                trust_level: inner.trust_level,
+                trusting_period: inner.trusting_period,
+                unbonding_period: inner.unbonding_period,
+                max_clock_drift: inner.max_clock_drift,
+            })
+        );
+
+        let mock_client_state =
+            AnyClientState::from(MockClientState::new(MockHeader::new(Height::min(0))));
+        assert_eq!(mock_client_state.verification_params(), None);
+    }
+
+    #[test]
+    fn test_root_hex() {
+        let root = vec![0x00, 0x1f, 0xab, 0xff];
+
+        let consensus_state = AnyConsensusState::from(ConsensusStateType::new(
+            root.clone().into(),
+            tendermint::Time::unix_epoch(),
+            Hash::None,
+        ));
+
+        assert_eq!(consensus_state.root_hex(), "001fabff");
+        assert_eq!(consensus_state.root_hex(), hex::encode(root));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_next_validators_hash() {
+        use crate::fixtures::clients::tendermint::dummy_tendermint_header;
+        use crate::testapp::ibc::clients::mock::header::MockHeader;
+
+        let header = dummy_tendermint_header();
+
+        let tm_consensus_state = AnyConsensusState::from(ConsensusStateType::from(header.clone()));
+        assert_eq!(
+            tm_consensus_state.next_validators_hash(),
+            Some(header.next_validators_hash)
+        );
+
+        let mock_consensus_state =
+            AnyConsensusState::from(MockConsensusState::new(MockHeader::new(Height::min(0))));
+        assert_eq!(mock_consensus_state.next_validators_hash(), None);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_latest_height_proto_matches_json() {
+        use crate::fixtures::clients::tendermint::{
+            dummy_tendermint_header, dummy_tm_client_state_from_header,
+        };
+
+        let client_state =
+            AnyClientState::from(dummy_tm_client_state_from_header(dummy_tendermint_header()));
+
+        let proto_height = client_state.latest_height_proto();
+        let json_height = serde_json::to_value(client_state.latest_height()).expect("no error");
+
+        assert_eq!(json_height["revision_number"], proto_height.revision_number);
+        assert_eq!(json_height["revision_height"], proto_height.revision_height);
+        assert_eq!(
+            Height::try_from(proto_height).expect("no error"),
+            client_state.latest_height()
+        );
+    }
+}
@@ -0,0 +1,236 @@
+//! Fallback client state for client types the host does not recognize.
+use core::str::FromStr;
+
+use ibc::core::client::context::prelude::*;
+use ibc::core::client::types::error::ClientError;
+use ibc::core::client::types::{Height, Status};
+use ibc::core::commitment_types::commitment::{
+    CommitmentPrefix, CommitmentProofBytes, CommitmentRoot,
+};
+use ibc::core::host::types::identifiers::{ClientId, ClientType};
+use ibc::core::host::types::path::Path;
+use ibc::core::primitives::prelude::*;
+use ibc::primitives::proto::Any;
+
+pub const UNKNOWN_CLIENT_TYPE: &str = "unknown";
+
+pub fn client_type() -> ClientType {
+    ClientType::from_str(UNKNOWN_CLIENT_TYPE).expect("never fails because it's valid client type")
+}
+
+/// A client state of a type the host does not understand, kept as its raw
+/// encoding so that it can be stored and forwarded as is.
+///
+/// Such a client can neither be validated nor updated: its status is always
+/// [`Status::Unauthorized`] and every verification or state transition fails
+/// with [`ClientError::UnknownClientStateType`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct UnknownClientState(pub Any);
+
+impl UnknownClientState {
+    /// Returns the type URL of the raw client state.
+    pub fn type_url(&self) -> &str {
+        &self.0.type_url
+    }
+
+    fn unsupported(&self) -> ClientError {
+        ClientError::UnknownClientStateType {
+            client_state_type: self.0.type_url.clone(),
+        }
+    }
+}
+
+impl From<Any> for UnknownClientState {
+    fn from(raw: Any) -> Self {
+        Self(raw)
+    }
+}
+
+impl From<UnknownClientState> for Any {
+    fn from(client_state: UnknownClientState) -> Self {
+        client_state.0
+    }
+}
+
+impl ClientStateCommon for UnknownClientState {
+    fn verify_consensus_state(&self, _consensus_state: Any) -> Result<(), ClientError> {
+        Err(self.unsupported())
+    }
+
+    fn client_type(&self) -> ClientType {
+        client_type()
+    }
+
+    /// The height cannot be decoded from the raw client state, hence the
+    /// minimum height is reported.
+    fn latest_height(&self) -> Height {
+        Height::min(0)
+    }
+
+    fn validate_proof_height(&self, _proof_height: Height) -> Result<(), ClientError> {
+        Err(self.unsupported())
+    }
+
+    fn verify_upgrade_client(
+        &self,
+        _upgraded_client_state: Any,
+        _upgraded_consensus_state: Any,
+        _proof_upgrade_client: CommitmentProofBytes,
+        _proof_upgrade_consensus_state: CommitmentProofBytes,
+        _root: &CommitmentRoot,
+    ) -> Result<(), ClientError> {
+        Err(self.unsupported())
+    }
+
+    fn verify_membership(
+        &self,
+        _prefix: &CommitmentPrefix,
+        _proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
+        _path: Path,
+        _value: Vec<u8>,
+    ) -> Result<(), ClientError> {
+        Err(self.unsupported())
+    }
+
+    fn verify_non_membership(
+        &self,
+        _prefix: &CommitmentPrefix,
+        _proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
+        _path: Path,
+    ) -> Result<(), ClientError> {
+        Err(self.unsupported())
+    }
+}
+
+impl<V> ClientStateValidation<V> for UnknownClientState
+where
+    V: ClientValidationContext,
+{
+    fn verify_client_message(
+        &self,
+        _ctx: &V,
+        _client_id: &ClientId,
+        _client_message: Any,
+    ) -> Result<(), ClientError> {
+        Err(self.unsupported())
+    }
+
+    fn check_for_misbehaviour(
+        &self,
+        _ctx: &V,
+        _client_id: &ClientId,
+        _client_message: Any,
+    ) -> Result<bool, ClientError> {
+        Err(self.unsupported())
+    }
+
+    fn status(&self, _ctx: &V, _client_id: &ClientId) -> Result<Status, ClientError> {
+        Ok(Status::Unauthorized)
+    }
+
+    fn check_substitute(&self, _ctx: &V, _substitute_client_state: Any) -> Result<(), ClientError> {
+        Err(self.unsupported())
+    }
+}
+
+impl<E> ClientStateExecution<E> for UnknownClientState
+where
+    E: ClientExecutionContext,
+{
+    fn initialise(
+        &self,
+        _ctx: &mut E,
+        _client_id: &ClientId,
+        _consensus_state: Any,
+    ) -> Result<(), ClientError> {
+        Err(self.unsupported())
+    }
+
+    fn update_state(
+        &self,
+        _ctx: &mut E,
+        _client_id: &ClientId,
+        _header: Any,
+    ) -> Result<Vec<Height>, ClientError> {
+        Err(self.unsupported())
+    }
+
+    fn update_state_on_misbehaviour(
+        &self,
+        _ctx: &mut E,
+        _client_id: &ClientId,
+        _client_message: Any,
+    ) -> Result<(), ClientError> {
+        Err(self.unsupported())
+    }
+
+    fn update_state_on_upgrade(
+        &self,
+        _ctx: &mut E,
+        _client_id: &ClientId,
+        _upgraded_client_state: Any,
+        _upgraded_consensus_state: Any,
+    ) -> Result<Height, ClientError> {
+        Err(self.unsupported())
+    }
+
+    fn update_on_recovery(
+        &self,
+        _ctx: &mut E,
+        _subject_client_id: &ClientId,
+        _substitute_client_state: Any,
+        _substitute_consensus_state: Any,
+    ) -> Result<(), ClientError> {
+        Err(self.unsupported())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ibc::core::host::types::path::ClientStatePath;
+
+    use super::*;
+    use crate::testapp::ibc::clients::AnyClientState;
+    use crate::testapp::ibc::core::types::DefaultIbcStore;
+
+    #[test]
+    fn test_unknown_client_state_round_trip() {
+        let raw = Any {
+            type_url: "/ibc.lightclients.future.v1.ClientState".to_string(),
+            value: vec![1, 2, 3],
+        };
+
+        let client_state = AnyClientState::try_from(raw.clone()).expect("no error");
+
+        assert_eq!(
+            client_state,
+            AnyClientState::Unknown(UnknownClientState(raw.clone()))
+        );
+        assert_eq!(Any::from(client_state), raw);
+    }
+
+    #[test]
+    fn test_unknown_client_state_store_round_trip() {
+        let raw = Any {
+            type_url: "/ibc.lightclients.future.v1.ClientState".to_string(),
+            value: vec![1, 2, 3],
+        };
+        let client_id = client_type().build_client_id(0);
+        let client_state = AnyClientState::Unknown(UnknownClientState(raw));
+
+        let mut ibc_store = DefaultIbcStore::default();
+        ibc_store
+            .store_client_state(
+                ClientStatePath::new(client_id.clone()),
+                client_state.clone(),
+            )
+            .expect("no error");
+
+        assert_eq!(
+            ibc_store.client_state(&client_id).expect("no error"),
+            client_state
+        );
+    }
+}
@@ -6,7 +6,7 @@ use ibc::core::client::context::{
     ClientExecutionContext, ClientValidationContext, ExtClientValidationContext,
 };
 use ibc::core::client::types::error::ClientError;
-use ibc::core::client::types::Height;
+use ibc::core::client::types::{Height, Status};
 use ibc::core::handler::types::error::ContextError;
 use ibc::core::host::types::identifiers::{ChannelId, ClientId, PortId};
 use ibc::core::host::types::path::{
@@ -15,15 +15,33 @@ use ibc::core::host::types::path::{
 use ibc::core::host::ValidationContext;
 use ibc::core::primitives::Timestamp;
 use ibc::primitives::prelude::*;
+#[cfg(feature = "serde")]
+use ibc::primitives::proto::Any;
 
 use super::types::MockIbcStore;
-use crate::testapp::ibc::clients::mock::client_state::MockClientContext;
+use crate::testapp::ibc::clients::mock::client_state::{
+    MockClientContext, ProofVerificationFailure,
+};
 use crate::testapp::ibc::clients::{AnyClientState, AnyConsensusState};
 
 pub type PortChannelIdMap<V> = BTreeMap<PortId, BTreeMap<ChannelId, V>>;
 
+/// Returns the height a consensus state path refers to, which is invalid for
+/// a zero revision height, e.g. out of a malformed or fuzzed path.
+fn consensus_state_path_height(path: &ClientConsensusStatePath) -> Result<Height, ClientError> {
+    Height::new(path.revision_number, path.revision_height).map_err(|_| ClientError::InvalidHeight)
+}
+
 /// A mock of an IBC client record as it is stored in a mock context.
 /// For testing ICS02 handlers mostly, cf. `MockClientContext`.
+///
+/// With the `serde` feature, the states are serialized in their protobuf
+/// [`Any`] form, and the consensus states as a list ordered by height.
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(into = "RawMockClientRecord", try_from = "RawMockClientRecord")
+)]
 #[derive(Clone, Debug)]
 pub struct MockClientRecord {
     /// The client state (representing only the latest height at the moment).
@@ -33,6 +51,46 @@ pub struct MockClientRecord {
     pub consensus_states: BTreeMap<Height, AnyConsensusState>,
 }
 
+/// The serialized form of a [`MockClientRecord`].
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct RawMockClientRecord {
+    client_state: Option<Any>,
+    consensus_states: Vec<(Height, Any)>,
+}
+
+#[cfg(feature = "serde")]
+impl From<MockClientRecord> for RawMockClientRecord {
+    fn from(record: MockClientRecord) -> Self {
+        Self {
+            client_state: record.client_state.map(Into::into),
+            consensus_states: record
+                .consensus_states
+                .into_iter()
+                .map(|(height, consensus_state)| (height, consensus_state.into()))
+                .collect(),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<RawMockClientRecord> for MockClientRecord {
+    type Error = ClientError;
+
+    fn try_from(raw: RawMockClientRecord) -> Result<Self, Self::Error> {
+        Ok(Self {
+            client_state: raw.client_state.map(AnyClientState::try_from).transpose()?,
+            consensus_states: raw
+                .consensus_states
+                .into_iter()
+                .map(|(height, consensus_state)| {
+                    Ok((height, AnyConsensusState::try_from(consensus_state)?))
+                })
+                .collect::<Result<_, ClientError>>()?,
+        })
+    }
+}
+
 impl<S> MockClientContext for MockIbcStore<S>
 where
     S: ProvableStore + Debug,
@@ -44,6 +102,18 @@ where
     fn host_height(&self) -> Result<Height, ContextError> {
         ValidationContext::host_height(self)
     }
+
+    fn client_status_override(&self, client_id: &ClientId) -> Option<Status> {
+        self.client_status_overrides.lock().get(client_id).copied()
+    }
+
+    fn client_status_schedule(&self, client_id: &ClientId) -> Vec<(Height, Status)> {
+        self.client_status_schedules
+            .lock()
+            .get(client_id)
+            .cloned()
+            .unwrap_or_default()
+    }
 }
 
 impl<S> ExtClientValidationContext for MockIbcStore<S>
@@ -54,6 +124,23 @@ where
         ValidationContext::host_timestamp(self)
     }
 
+    /// Skewed by the time offset of the client, see
+    /// `MockContext::set_client_time_offset`.
+    fn client_host_timestamp(&self, client_id: &ClientId) -> Result<Timestamp, ContextError> {
+        let host_timestamp = ValidationContext::host_timestamp(self)?;
+
+        let Some(offset) = self.client_time_offsets.lock().get(client_id).copied() else {
+            return Ok(host_timestamp);
+        };
+
+        offset.apply(host_timestamp).map_err(|e| {
+            ClientError::Other {
+                description: format!("failed to skew the host time: {e}"),
+            }
+            .into()
+        })
+    }
+
     fn host_height(&self) -> Result<Height, ContextError> {
         ValidationContext::host_height(self)
     }
@@ -92,17 +179,22 @@ where
     ) -> Result<Option<Self::ConsensusStateRef>, ContextError> {
         let path = format!("clients/{client_id}/consensusStates").into();
 
+        // Keys are ordered lexicographically, not by height, so the closest
+        // height has to be searched among all of them.
         let keys = self.store.get_keys(&path);
-        let found_path = keys.into_iter().find_map(|path| {
-            if let Ok(Path::ClientConsensusState(path)) = path.try_into() {
-                if height
-                    < &Height::new(path.revision_number, path.revision_height).expect("no error")
-                {
-                    return Some(path);
+        let found_path = keys
+            .into_iter()
+            .filter_map(|path| {
+                if let Ok(Path::ClientConsensusState(path)) = path.try_into() {
+                    let path_height = consensus_state_path_height(&path).ok()?;
+                    if height < &path_height {
+                        return Some((path_height, path));
+                    }
                 }
-            }
-            None
-        });
+                None
+            })
+            .min_by_key(|(path_height, _)| *path_height)
+            .map(|(_, path)| path);
 
         let consensus_state = found_path
             .map(|path| {
@@ -125,17 +217,22 @@ where
     ) -> Result<Option<Self::ConsensusStateRef>, ContextError> {
         let path = format!("clients/{client_id}/consensusStates").into();
 
+        // Keys are ordered lexicographically, not by height, so the closest
+        // height has to be searched among all of them.
         let keys = self.store.get_keys(&path);
-        let found_path = keys.into_iter().rev().find_map(|path| {
-            if let Ok(Path::ClientConsensusState(path)) = path.try_into() {
-                if height
-                    > &Height::new(path.revision_number, path.revision_height).expect("no error")
-                {
-                    return Some(path);
+        let found_path = keys
+            .into_iter()
+            .filter_map(|path| {
+                if let Ok(Path::ClientConsensusState(path)) = path.try_into() {
+                    let path_height = consensus_state_path_height(&path).ok()?;
+                    if height > &path_height {
+                        return Some((path_height, path));
+                    }
                 }
-            }
-            None
-        });
+                None
+            })
+            .max_by_key(|(path_height, _)| *path_height)
+            .map(|(_, path)| path);
 
         let consensus_state = found_path
             .map(|path| {
@@ -160,23 +257,28 @@ where
     type ConsensusStateRef = AnyConsensusState;
 
     fn client_state(&self, client_id: &ClientId) -> Result<Self::ClientStateRef, ContextError> {
-        Ok(self
+        let mut client_state = self
             .client_state_store
             .get(StoreHeight::Pending, &ClientStatePath(client_id.clone()))
             .ok_or(ClientError::ClientStateNotFound {
                 client_id: client_id.clone(),
-            })?)
+            })?;
+
+        // The pending proof verification failure stays in the store, and is
+        // only cleared by the next proof verified against a mock client state.
+        if let AnyClientState::Mock(mock_client_state) = &mut client_state {
+            mock_client_state.proof_verification_failure =
+                ProofVerificationFailure::new(self.fail_next_proof_verification.clone());
+        }
+
+        Ok(client_state)
     }
 
     fn consensus_state(
         &self,
         client_cons_state_path: &ClientConsensusStatePath,
     ) -> Result<AnyConsensusState, ContextError> {
-        let height = Height::new(
-            client_cons_state_path.revision_number,
-            client_cons_state_path.revision_height,
-        )
-        .map_err(|_| ClientError::InvalidHeight)?;
+        let height = consensus_state_path_height(client_cons_state_path)?;
         let consensus_state = self
             .consensus_state_store
             .get(StoreHeight::Pending, client_cons_state_path)
@@ -188,6 +290,30 @@ where
         Ok(consensus_state)
     }
 
+    /// Returns the heights of the given client with a stored processed time,
+    /// which is always stored along with the processed height.
+    fn client_update_meta_heights(
+        &self,
+        client_id: &ClientId,
+    ) -> Result<Vec<Height>, ContextError> {
+        let mut heights = self
+            .client_processed_times
+            .get_keys(&format!("clients/{client_id}/consensusStates").into())
+            .into_iter()
+            .filter_map(|path| match path.try_into() {
+                Ok(Path::ClientUpdateTime(path)) => Some(path),
+                _ => None,
+            })
+            .map(|path| {
+                Height::new(path.revision_number, path.revision_height).map_err(ContextError::from)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        heights.sort();
+
+        Ok(heights)
+    }
+
     /// Returns the time and height when the client state for the given
     /// [`ClientId`] was updated with a header for the given [`Height`]
     fn client_update_meta(
@@ -251,6 +377,10 @@ where
         consensus_state_path: ClientConsensusStatePath,
         consensus_state: Self::ConsensusStateRef,
     ) -> Result<(), ContextError> {
+        // A consensus state stored under an invalid height could never be
+        // read back.
+        consensus_state_path_height(&consensus_state_path)?;
+
         self.consensus_state_store
             .set(consensus_state_path, consensus_state)
             .map_err(|_| ClientError::Other {
@@ -263,6 +393,8 @@ where
         &mut self,
         consensus_state_path: ClientConsensusStatePath,
     ) -> Result<(), ContextError> {
+        consensus_state_path_height(&consensus_state_path)?;
+
         self.consensus_state_store.delete(consensus_state_path);
         Ok(())
     }
@@ -300,6 +432,23 @@ where
         host_timestamp: Timestamp,
         host_height: Height,
     ) -> Result<(), ContextError> {
+        if *self.strict_update_meta.lock() {
+            for existing_height in self.client_update_meta_heights(&client_id)? {
+                let (processed_time, processed_height) =
+                    self.client_update_meta(&client_id, &existing_height)?;
+
+                if host_timestamp < processed_time || host_height < processed_height {
+                    return Err(ClientError::Other {
+                        description: format!(
+                            "update metadata of client {client_id} at height {height} goes back to host time {host_timestamp} and height {host_height}, \
+                             while the update at height {existing_height} was processed at host time {processed_time} and height {processed_height}"
+                        ),
+                    }
+                    .into());
+                }
+            }
+        }
+
         let client_update_time_path = ClientUpdateTimePath::new(
             client_id.clone(),
             height.revision_number(),
@@ -9,6 +9,7 @@ use ibc::core::channel::types::channel::{ChannelEnd, IdentifiedChannelEnd};
 use ibc::core::channel::types::commitment::{AcknowledgementCommitment, PacketCommitment};
 use ibc::core::channel::types::error::{ChannelError, PacketError};
 use ibc::core::channel::types::packet::{PacketState, Receipt};
+use ibc::core::channel::types::Version as ChannelVersion;
 use ibc::core::client::context::consensus_state::ConsensusState;
 use ibc::core::client::types::error::ClientError;
 use ibc::core::client::types::Height;
@@ -52,7 +53,13 @@ where
     fn host_timestamp(&self) -> Result<Timestamp, ContextError> {
         let host_height = self.host_height()?;
         let host_cons_state = self.host_consensus_state(&host_height)?;
-        Ok(host_cons_state.timestamp())
+
+        (host_cons_state.timestamp() + *self.host_halt.lock()).map_err(|e| {
+            ClientError::Other {
+                description: format!("failed to add the halt to the host time: {e}"),
+            }
+            .into()
+        })
     }
 
     fn client_counter(&self) -> Result<u64, ContextError> {
@@ -124,6 +131,13 @@ where
     }
 
     fn connection_end(&self, conn_id: &ConnectionId) -> Result<ConnectionEnd, ContextError> {
+        if core::mem::take(&mut *self.fail_next_connection_read.lock()) {
+            return Err(ConnectionError::Other {
+                description: format!("injected failure reading connection `{conn_id}`"),
+            }
+            .into());
+        }
+
         Ok(self
             .connection_end_store
             .get(StoreHeight::Pending, &ConnectionPath::new(conn_id))
@@ -148,6 +162,16 @@ where
     }
 
     fn channel_end(&self, channel_end_path: &ChannelEndPath) -> Result<ChannelEnd, ContextError> {
+        if core::mem::take(&mut *self.fail_next_channel_read.lock()) {
+            return Err(ChannelError::Other {
+                description: format!(
+                    "injected failure reading channel `{}` on port `{}`",
+                    channel_end_path.1, channel_end_path.0
+                ),
+            }
+            .into());
+        }
+
         Ok(self
             .channel_end_store
             .get(
@@ -157,6 +181,43 @@ where
             .ok_or(ChannelError::MissingChannel)?)
     }
 
+    fn expected_channel_version(
+        &self,
+        channel_end_path: &ChannelEndPath,
+    ) -> Result<Option<ChannelVersion>, ContextError> {
+        Ok(self
+            .expected_channel_versions
+            .lock()
+            .get(channel_end_path)
+            .cloned())
+    }
+
+    fn packet_commitment_timeout_timestamp(
+        &self,
+        commitment_path: &CommitmentPath,
+    ) -> Result<Option<Timestamp>, ContextError> {
+        Ok(self
+            .packet_timeout_timestamps
+            .lock()
+            .get(commitment_path)
+            .copied())
+    }
+
+    fn packet_metadata(
+        &self,
+        commitment_path: &CommitmentPath,
+    ) -> Result<Option<Vec<u8>>, ContextError> {
+        Ok(self.packet_metadata.lock().get(commitment_path).cloned())
+    }
+
+    fn validate_packet_metadata(
+        &self,
+        _commitment_path: &CommitmentPath,
+        _metadata: &[u8],
+    ) -> Result<(), ContextError> {
+        Ok(())
+    }
+
     fn get_next_sequence_send(
         &self,
         seq_send_path: &SeqSendPath,
@@ -728,11 +789,37 @@ where
         Ok(())
     }
 
+    fn store_packet_commitment_timeout_timestamp(
+        &mut self,
+        commitment_path: &CommitmentPath,
+        timeout_timestamp: Timestamp,
+    ) -> Result<(), ContextError> {
+        self.packet_timeout_timestamps
+            .lock()
+            .insert(commitment_path.clone(), timeout_timestamp);
+        Ok(())
+    }
+
     fn delete_packet_commitment(
         &mut self,
         commitment_path: &CommitmentPath,
     ) -> Result<(), ContextError> {
         self.packet_commitment_store.delete(commitment_path.clone());
+        self.packet_timeout_timestamps
+            .lock()
+            .remove(commitment_path);
+        self.packet_metadata.lock().remove(commitment_path);
+        Ok(())
+    }
+
+    fn store_packet_metadata(
+        &mut self,
+        commitment_path: &CommitmentPath,
+        metadata: Vec<u8>,
+    ) -> Result<(), ContextError> {
+        self.packet_metadata
+            .lock()
+            .insert(commitment_path.clone(), metadata);
         Ok(())
     }
 
@@ -827,6 +914,12 @@ where
     }
 
     fn emit_ibc_event(&mut self, event: IbcEvent) -> Result<(), ContextError> {
+        // The sink is called without holding any lock of the store.
+        let event_sink = self.event_sink.lock().clone();
+        if let Some(event_sink) = event_sink {
+            event_sink.send(&event);
+        }
+
         self.events.lock().push(event);
         Ok(())
     }
@@ -1,26 +1,30 @@
 //! Implementation of a global context mock. Used in testing handlers of all IBC modules.
 
+use alloc::collections::BTreeSet;
 use alloc::sync::Arc;
-use core::fmt::Debug;
+use core::fmt::{Debug, Formatter, Result as FmtResult};
+use core::sync::atomic::AtomicBool;
+use core::time::Duration;
 
 use basecoin_store::context::{ProvableStore, Store};
 use basecoin_store::impls::SharedStore;
 use basecoin_store::types::{BinStore, JsonStore, ProtobufStore, TypedSet, TypedStore};
 use ibc::core::channel::types::channel::ChannelEnd;
 use ibc::core::channel::types::commitment::{AcknowledgementCommitment, PacketCommitment};
+use ibc::core::channel::types::Version as ChannelVersion;
 use ibc::core::client::context::client_state::ClientStateValidation;
-use ibc::core::client::types::Height;
+use ibc::core::client::types::{Height, Status};
 use ibc::core::connection::types::ConnectionEnd;
 use ibc::core::handler::types::events::IbcEvent;
-use ibc::core::host::types::identifiers::{ConnectionId, Sequence};
+use ibc::core::host::types::identifiers::{ClientId, ConnectionId, Sequence};
 use ibc::core::host::types::path::{
     AckPath, ChannelEndPath, ClientConnectionPath, ClientConsensusStatePath, ClientStatePath,
     ClientUpdateHeightPath, ClientUpdateTimePath, CommitmentPath, ConnectionPath,
-    NextChannelSequencePath, NextClientSequencePath, NextConnectionSequencePath, ReceiptPath,
+    NextChannelSequencePath, NextClientSequencePath, NextConnectionSequencePath, Path, ReceiptPath,
     SeqAckPath, SeqRecvPath, SeqSendPath,
 };
 use ibc::core::primitives::prelude::*;
-use ibc::core::primitives::Timestamp;
+use ibc::core::primitives::{Timestamp, TimestampOverflowError};
 use ibc_proto::google::protobuf::Any;
 use ibc_proto::ibc::core::channel::v1::Channel as RawChannelEnd;
 use ibc_proto::ibc::core::client::v1::Height as RawHeight;
@@ -38,6 +42,48 @@ pub const DEFAULT_BLOCK_TIME_SECS: u64 = 3;
 
 pub type DefaultIbcStore = MockIbcStore<MockStore>;
 
+/// A callback to which the events emitted by a [`MockIbcStore`] are forwarded
+/// as they are produced.
+#[derive(Clone)]
+pub struct EventSink(Arc<dyn Fn(&IbcEvent) + Send + Sync>);
+
+impl EventSink {
+    pub fn new(sink: impl Fn(&IbcEvent) + Send + Sync + 'static) -> Self {
+        Self(Arc::new(sink))
+    }
+
+    /// Forwards the given event to the sink.
+    pub fn send(&self, event: &IbcEvent) {
+        (self.0)(event)
+    }
+}
+
+impl Debug for EventSink {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.write_str("EventSink")
+    }
+}
+
+/// The offset of the host time seen by a client, see
+/// [`MockContext::set_client_time_offset`](crate::context::MockContext::set_client_time_offset).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ClientTimeOffset {
+    /// The client sees the host time ahead by the given duration.
+    Ahead(Duration),
+    /// The client sees the host time behind by the given duration.
+    Behind(Duration),
+}
+
+impl ClientTimeOffset {
+    /// Applies the offset to the given host time.
+    pub fn apply(self, host_timestamp: Timestamp) -> Result<Timestamp, TimestampOverflowError> {
+        match self {
+            Self::Ahead(offset) => host_timestamp + offset,
+            Self::Behind(offset) => host_timestamp - offset,
+        }
+    }
+}
+
 /// An object that stores all IBC related data.
 #[derive(Debug)]
 pub struct MockIbcStore<S>
@@ -89,8 +135,43 @@ where
     pub host_consensus_states: Arc<Mutex<BTreeMap<u64, AnyConsensusState>>>,
     /// Map of older ibc commitment proofs
     pub ibc_commiment_proofs: Arc<Mutex<BTreeMap<u64, CommitmentProof>>>,
+    /// Map of channel versions as negotiated during the channel handshakes
+    pub expected_channel_versions: Arc<Mutex<BTreeMap<ChannelEndPath, ChannelVersion>>>,
+    /// Map of timeout timestamps of the committed packets
+    pub packet_timeout_timestamps: Arc<Mutex<BTreeMap<CommitmentPath, Timestamp>>>,
+    /// Map of the out-of-band metadata attached to the committed packets
+    pub packet_metadata: Arc<Mutex<BTreeMap<CommitmentPath, Vec<u8>>>>,
+    /// Whether storing client update metadata that goes back in host time or
+    /// height is rejected
+    pub strict_update_meta: Arc<Mutex<bool>>,
+    /// Map of the statuses mock clients are forced to report
+    pub client_status_overrides: Arc<Mutex<BTreeMap<ClientId, Status>>>,
+    /// Map of the statuses mock clients report from the paired host heights
+    /// on, sorted by height
+    pub client_status_schedules: Arc<Mutex<BTreeMap<ClientId, Vec<(Height, Status)>>>>,
+    /// Time elapsed since the latest block while the host chain is halted,
+    /// added to the host timestamp until the next block is produced
+    pub host_halt: Arc<Mutex<Duration>>,
+    /// Map of the offsets of the host time seen by clients
+    pub client_time_offsets: Arc<Mutex<BTreeMap<ClientId, ClientTimeOffset>>>,
+    /// Whether the next proof verified against a mock client state read from
+    /// the store fails
+    pub fail_next_proof_verification: Arc<AtomicBool>,
+    /// Whether the next connection end read from the store fails
+    pub fail_next_connection_read: Arc<Mutex<bool>>,
+    /// Whether the next channel end read from the store fails
+    pub fail_next_channel_read: Arc<Mutex<bool>>,
+    /// Set of the stored acknowledgements confirmed as relayed back to the
+    /// sender chain
+    pub relayed_acks: Arc<Mutex<BTreeSet<AckPath>>>,
+    /// Map of the consensus states conflicting with the stored ones, kept
+    /// aside to build misbehaviour evidence
+    pub conflicting_consensus_states:
+        Arc<Mutex<BTreeMap<ClientConsensusStatePath, AnyConsensusState>>>,
     /// IBC Events
     pub events: Arc<Mutex<Vec<IbcEvent>>>,
+    /// Sink the IBC events are forwarded to, on top of being recorded
+    pub event_sink: Arc<Mutex<Option<EventSink>>>,
     /// message logs
     pub logs: Arc<Mutex<Vec<String>>>,
 }
@@ -127,6 +208,19 @@ where
             client_processed_heights: TypedStore::new(shared_store.clone()),
             host_consensus_states: Arc::new(Mutex::new(Default::default())),
             ibc_commiment_proofs: Arc::new(Mutex::new(Default::default())),
+            expected_channel_versions: Arc::new(Mutex::new(Default::default())),
+            packet_timeout_timestamps: Arc::new(Mutex::new(Default::default())),
+            packet_metadata: Arc::new(Mutex::new(Default::default())),
+            strict_update_meta: Arc::new(Mutex::new(false)),
+            client_status_overrides: Arc::new(Mutex::new(Default::default())),
+            client_status_schedules: Arc::new(Mutex::new(Default::default())),
+            host_halt: Arc::new(Mutex::new(Duration::ZERO)),
+            client_time_offsets: Arc::new(Mutex::new(Default::default())),
+            fail_next_proof_verification: Arc::new(AtomicBool::new(false)),
+            fail_next_connection_read: Arc::new(Mutex::new(false)),
+            fail_next_channel_read: Arc::new(Mutex::new(false)),
+            relayed_acks: Arc::new(Mutex::new(Default::default())),
+            conflicting_consensus_states: Arc::new(Mutex::new(Default::default())),
             client_state_store: TypedStore::new(shared_store.clone()),
             consensus_state_store: TypedStore::new(shared_store.clone()),
             connection_end_store: TypedStore::new(shared_store.clone()),
@@ -139,6 +233,7 @@ where
             packet_receipt_store: TypedStore::new(shared_store.clone()),
             packet_ack_store: TypedStore::new(shared_store.clone()),
             events: Arc::new(Mutex::new(Vec::new())),
+            event_sink: Arc::new(Mutex::new(None)),
             logs: Arc::new(Mutex::new(Vec::new())),
             store: shared_store,
         }
@@ -171,11 +266,63 @@ where
 
     pub fn prune_host_consensus_states_till(&self, height: &Height) {
         assert!(height.revision_number() == *self.revision_number.lock());
-        let mut history = self.host_consensus_states.lock();
-        history.retain(|h, _| h > &height.revision_height());
-        let mut commitment_proofs = self.ibc_commiment_proofs.lock();
-        commitment_proofs.retain(|h, _| h > &height.revision_height());
+        // Each lock is released before the next one is taken.
+        self.host_consensus_states
+            .lock()
+            .retain(|h, _| h > &height.revision_height());
+        self.ibc_commiment_proofs
+            .lock()
+            .retain(|h, _| h > &height.revision_height());
     }
+
+    /// Returns the paths of all the entries of type `P` held by the store.
+    pub fn typed_keys<P: TypedPath>(&self) -> Vec<P> {
+        self.store
+            .get_keys(&P::PREFIX.to_owned().into())
+            .into_iter()
+            .filter_map(|path| P::from_path(path.try_into().ok()?))
+            .collect()
+    }
+}
+
+/// A path of the entries of a given type in the IBC store, which can be listed
+/// with [`MockIbcStore::typed_keys`].
+pub trait TypedPath: Sized {
+    /// The key prefix shared by all the entries of this type.
+    const PREFIX: &'static str;
+
+    /// Returns the typed path if `path` is one, or `None` otherwise.
+    fn from_path(path: Path) -> Option<Self>;
+}
+
+macro_rules! impl_typed_path {
+    ($($path:ty => $variant:ident under $prefix:literal),* $(,)?) => {
+        $(
+            impl TypedPath for $path {
+                const PREFIX: &'static str = $prefix;
+
+                fn from_path(path: Path) -> Option<Self> {
+                    match path {
+                        Path::$variant(path) => Some(path),
+                        _ => None,
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_typed_path! {
+    ClientStatePath => ClientState under "clients",
+    ClientConsensusStatePath => ClientConsensusState under "clients",
+    ClientConnectionPath => ClientConnection under "clients",
+    ConnectionPath => Connection under "connections",
+    ChannelEndPath => ChannelEnd under "channelEnds",
+    SeqSendPath => SeqSend under "nextSequenceSend",
+    SeqRecvPath => SeqRecv under "nextSequenceRecv",
+    SeqAckPath => SeqAck under "nextSequenceAck",
+    ReceiptPath => Receipt under "receipts",
+    AckPath => Ack under "acks",
 }
 
 impl<S> Default for MockIbcStore<S>
@@ -1,12 +1,20 @@
+use core::str::FromStr;
+
+use ibc::apps::nft_transfer::handler::process_recv_packet_execute;
 use ibc::apps::nft_transfer::module::{
     on_chan_open_init_execute, on_chan_open_init_validate, on_chan_open_try_execute,
     on_chan_open_try_validate,
 };
-use ibc::apps::nft_transfer::types::VERSION;
+use ibc::apps::nft_transfer::types::packet::PacketData;
+use ibc::apps::nft_transfer::types::{PrefixedClassId, TokenIds, VERSION};
 use ibc::core::channel::types::channel::{Counterparty, Order};
+use ibc::core::channel::types::packet::Packet;
 use ibc::core::channel::types::Version;
 use ibc::core::host::types::identifiers::{ChannelId, ConnectionId, PortId};
 use ibc::core::primitives::prelude::*;
+use ibc::core::primitives::Signer;
+use ibc_testkit::fixtures::core::channel::dummy_raw_packet;
+use ibc_testkit::fixtures::core::signer::dummy_account_id;
 use ibc_testkit::testapp::ibc::applications::nft_transfer::types::DummyNftTransferModule;
 
 fn get_defaults() -> (
@@ -133,3 +141,50 @@ fn test_on_chan_open_try_counterparty_incorrect_version() {
 
     assert!(res.is_err());
 }
+
+/// The class ID predicted by `PrefixedClassId::after_send` on the sending
+/// chain is the one the receiving chain mints vouchers of.
+#[test]
+fn test_after_send_matches_received_class() {
+    let mut packet: Packet = dummy_raw_packet(10, 0).try_into().expect("valid packet");
+    packet.chan_id_on_a = ChannelId::new(0);
+    packet.chan_id_on_b = ChannelId::new(1);
+
+    let class_id = PrefixedClassId::from_str("transfer/channel-5/myclass").expect("valid class");
+    let account: Signer = dummy_account_id();
+    let data = PacketData::new(
+        class_id.clone(),
+        None,
+        None,
+        TokenIds::try_from(vec!["token_0".to_string()]).expect("valid token IDs"),
+        vec![],
+        vec![],
+        account.clone(),
+        account,
+        "".into(),
+    )
+    .expect("valid packet data");
+
+    let extras = process_recv_packet_execute(&mut DummyNftTransferModule, &packet, data)
+        .expect("recv succeeds");
+
+    let received_class = extras
+        .events
+        .iter()
+        .flat_map(|event| &event.attributes)
+        .find(|attribute| attribute.key == "class")
+        .map(|attribute| attribute.value.clone())
+        .expect("token trace event emitted");
+
+    assert_eq!(
+        received_class,
+        class_id
+            .after_send(
+                &packet.port_id_on_b,
+                &packet.chan_id_on_b,
+                &packet.port_id_on_a,
+                &packet.chan_id_on_a,
+            )
+            .to_string()
+    );
+}
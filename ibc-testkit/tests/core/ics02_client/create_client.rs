@@ -21,6 +21,7 @@ use ibc_testkit::fixtures::clients::tendermint::{
 };
 use ibc_testkit::fixtures::core::context::TestContextConfig;
 use ibc_testkit::fixtures::core::signer::dummy_account_id;
+use ibc_testkit::hosts::TendermintHost;
 use ibc_testkit::testapp::ibc::clients::mock::client_state::{
     client_type as mock_client_type, MockClientState,
 };
@@ -28,7 +29,9 @@ use ibc_testkit::testapp::ibc::clients::mock::consensus_state::MockConsensusStat
 use ibc_testkit::testapp::ibc::clients::mock::header::MockHeader;
 use ibc_testkit::testapp::ibc::clients::{AnyClientState, AnyConsensusState};
 use ibc_testkit::testapp::ibc::core::router::MockRouter;
-use ibc_testkit::testapp::ibc::core::types::{DefaultIbcStore, LightClientBuilder, MockIbcStore};
+use ibc_testkit::testapp::ibc::core::types::{
+    DefaultIbcStore, LightClientBuilder, LightClientState, MockIbcStore,
+};
 use test_log::test;
 
 #[test]
@@ -212,3 +215,40 @@ fn test_tm_create_client_proof_verification_ok() {
         ClientError::Ics23Verification(CommitmentError::VerificationFailure)
     ));
 }
+
+#[test]
+fn test_create_client_ids_are_deterministic() {
+    let ctx_tm = TendermintContext::default();
+    let light_client: LightClientState<TendermintHost> =
+        LightClientBuilder::init().context(&ctx_tm).build();
+
+    let mut ctx = MockContext::default();
+
+    for counter in 0..3 {
+        let expected_client_id = ClientId::new("07-tendermint", counter).expect("no error");
+
+        assert_eq!(ctx.next_client_id(&tm_client_type()), expected_client_id);
+
+        let msg = MsgCreateClient::new(
+            light_client.client_state.clone().into(),
+            light_client
+                .consensus_states
+                .values()
+                .next()
+                .expect("at least one")
+                .clone()
+                .into(),
+            dummy_account_id(),
+        );
+
+        ctx.dispatch(MsgEnvelope::from(ClientMsg::from(msg)))
+            .expect("client creation succeeds");
+
+        assert!(ctx.ibc_store().client_state(&expected_client_id).is_ok());
+    }
+
+    assert_eq!(
+        ctx.next_client_id(&tm_client_type()),
+        ClientId::new("07-tendermint", 3).expect("no error")
+    );
+}
@@ -11,11 +11,13 @@ use ibc::clients::tendermint::types::{
 };
 use ibc::core::client::context::client_state::ClientStateValidation;
 use ibc::core::client::context::ClientValidationContext;
+use ibc::core::client::types::error::ClientError;
 use ibc::core::client::types::msgs::{ClientMsg, MsgUpdateClient};
 use ibc::core::client::types::proto::v1::Height as RawHeight;
-use ibc::core::client::types::Height;
+use ibc::core::client::types::{Height, Status};
 use ibc::core::commitment_types::specs::ProofSpecs;
 use ibc::core::entrypoint::{execute, validate};
+use ibc::core::handler::types::error::ContextError;
 use ibc::core::handler::types::events::{IbcEvent, MessageEvent};
 use ibc::core::handler::types::msgs::MsgEnvelope;
 use ibc::core::host::types::identifiers::{ChainId, ClientId, ClientType};
@@ -36,14 +38,16 @@ use ibc_testkit::relayer::error::RelayerError;
 use ibc_testkit::testapp::ibc::clients::mock::client_state::{
     client_type as mock_client_type, MockClientState,
 };
+use ibc_testkit::testapp::ibc::clients::mock::consensus_state::MockConsensusState;
 use ibc_testkit::testapp::ibc::clients::mock::header::MockHeader;
 use ibc_testkit::testapp::ibc::clients::mock::misbehaviour::Misbehaviour as MockMisbehaviour;
 use ibc_testkit::testapp::ibc::clients::AnyConsensusState;
 use ibc_testkit::testapp::ibc::core::router::MockRouter;
 use ibc_testkit::testapp::ibc::core::types::{
-    DefaultIbcStore, LightClientBuilder, LightClientState, MockIbcStore,
+    ClientTimeOffset, DefaultIbcStore, LightClientBuilder, LightClientState, MockIbcStore,
 };
 use rstest::*;
+use tendermint::validator::Set as ValidatorSet;
 use tendermint_testgen::Validator as TestgenValidator;
 use tracing::debug;
 
@@ -1199,6 +1203,63 @@ fn test_misbehaviour_client_ok(fixture: Fixture) {
     ensure_misbehaviour(&ctx.ibc_store, &client_id, &mock_client_type());
 }
 
+/// Tests misbehaviour handling for the mock client, with evidence built from
+/// a pair of conflicting consensus states.
+#[rstest]
+fn test_misbehaviour_from_conflicting_consensus_states(fixture: Fixture) {
+    let Fixture { ctx, mut router } = fixture;
+
+    let client_id = ClientId::new("07-tendermint", 0).expect("no error");
+    let height = Height::new(0, 46).unwrap();
+    let timestamp = Timestamp::now();
+
+    let state_a = MockConsensusState::new(MockHeader::new(height).with_timestamp(timestamp));
+    let state_b = MockConsensusState::new(
+        MockHeader::new(height).with_timestamp((timestamp + Duration::from_secs(1)).unwrap()),
+    );
+
+    let mut ctx = ctx.with_conflicting_consensus_state(
+        &client_id,
+        height,
+        state_a.clone().into(),
+        state_b.clone().into(),
+    );
+
+    let stored = ctx
+        .ibc_store
+        .consensus_state(&ClientConsensusStatePath::new(
+            client_id.clone(),
+            height.revision_number(),
+            height.revision_height(),
+        ))
+        .unwrap();
+    assert_eq!(stored, state_a.clone().into());
+    assert_eq!(
+        ctx.conflicting_consensus_state(&client_id, height),
+        Some(state_b.clone().into())
+    );
+
+    let msg = MsgUpdateClient {
+        client_id: client_id.clone(),
+        client_message: MockMisbehaviour {
+            client_id: client_id.clone(),
+            header1: state_a.header,
+            header2: state_b.header,
+        }
+        .into(),
+        signer: dummy_account_id(),
+    };
+    let msg_envelope = MsgEnvelope::from(ClientMsg::from(msg));
+
+    let res = validate(&ctx.ibc_store, &router, msg_envelope.clone());
+    assert!(res.is_ok());
+
+    let res = execute(&mut ctx.ibc_store, &mut router, msg_envelope);
+    assert!(res.is_ok());
+
+    ensure_misbehaviour(&ctx.ibc_store, &client_id, &mock_client_type());
+}
+
 #[rstest]
 fn test_submit_misbehaviour_nonexisting_client(fixture: Fixture) {
     let Fixture { router, .. } = fixture;
@@ -1307,6 +1368,425 @@ fn test_misbehaviour_synthetic_tendermint_equivocation() {
     ensure_misbehaviour(&ctx_a.ibc_store, &client_id, &tm_client_type());
 }
 
+/// Tests that misbehaviour evidence made of the same header twice is rejected
+/// for the synthetic Tendermint client.
+#[rstest]
+fn test_misbehaviour_synthetic_tendermint_identical_headers() {
+    let client_id = tm_client_type().build_client_id(0);
+    let client_height = Height::new(1, 20).unwrap();
+    let misbehaviour_height = Height::new(1, 21).unwrap();
+    let chain_id_b = ChainId::new("mockgaiaB-1").unwrap();
+
+    let ctx_b = TestContextConfig::builder()
+        .host(TendermintHost::builder().chain_id(chain_id_b).build())
+        .latest_height(misbehaviour_height)
+        .build::<TendermintContext>();
+
+    let ctx_a = TestContextConfig::builder()
+        .host(
+            MockHost::builder()
+                .chain_id(ChainId::new("mockgaiaA-1").unwrap())
+                .build(),
+        )
+        .latest_height(Height::new(1, 1).unwrap())
+        .build::<MockContext>()
+        .with_light_client(
+            &client_id,
+            LightClientBuilder::init()
+                .context(&ctx_b)
+                .consensus_heights([client_height])
+                .build(),
+        );
+
+    let router_a = MockRouter::new_with_transfer();
+
+    let header: TmHeader = {
+        let block = ctx_b.host_block(&misbehaviour_height).unwrap();
+        let mut block = block.into_header();
+        block.set_trusted_height(client_height);
+        block.into()
+    };
+
+    let msg = MsgUpdateClient {
+        client_id: client_id.clone(),
+        client_message: TmMisbehaviour::new(client_id.clone(), header.clone(), header).into(),
+        signer: dummy_account_id(),
+    };
+    let msg_envelope = MsgEnvelope::from(ClientMsg::from(msg));
+
+    let res = validate(&ctx_a.ibc_store, &router_a, msg_envelope);
+    assert!(
+        matches!(
+            res,
+            Err(ContextError::ClientError(
+                ClientError::MisbehaviourHeadersIdentical
+            ))
+        ),
+        "unexpected result: {res:?}"
+    );
+}
+
+#[rstest]
+fn test_misbehaviour_synthetic_tendermint_verify_with_states() {
+    let client_id = tm_client_type().build_client_id(0);
+    let client_height = Height::new(1, 20).unwrap();
+    let misbehaviour_height = Height::new(1, 21).unwrap();
+    let chain_id_b = ChainId::new("mockgaiaB-1").unwrap();
+
+    let ctx_b = TestContextConfig::builder()
+        .host(
+            TendermintHost::builder()
+                .chain_id(chain_id_b.clone())
+                .build(),
+        )
+        .latest_height(misbehaviour_height)
+        .build::<TendermintContext>();
+
+    let ctx_a = TestContextConfig::builder()
+        .host(
+            MockHost::builder()
+                .chain_id(ChainId::new("mockgaiaA-1").unwrap())
+                .build(),
+        )
+        .latest_height(Height::new(1, 1).unwrap())
+        .build::<MockContext>()
+        .with_light_client(
+            &client_id,
+            LightClientBuilder::init()
+                .context(&ctx_b)
+                .consensus_heights([client_height])
+                .build(),
+        );
+
+    let header1: TmHeader = {
+        let block = ctx_b.host_block(&misbehaviour_height).unwrap();
+        let mut block = block.into_header();
+        block.set_trusted_height(client_height);
+        block.into()
+    };
+
+    let header2 = {
+        let mut tm_block = TendermintHost::builder()
+            .chain_id(chain_id_b)
+            .build()
+            .generate_block(
+                Vec::new(),
+                misbehaviour_height.revision_height(),
+                Timestamp::now(),
+                &Default::default(),
+            )
+            .into_header();
+        tm_block.set_trusted_height(client_height);
+        tm_block.into()
+    };
+
+    let misbehaviour = TmMisbehaviour::new(client_id.clone(), header1, header2);
+
+    // The states a relayer would hold in memory, read once from the context.
+    let client_state = ctx_a.ibc_store.client_state(&client_id).unwrap();
+    let tm_client_state = client_state.as_tendermint().unwrap();
+    let AnyConsensusState::Tendermint(trusted_cs) = ctx_a
+        .ibc_store
+        .consensus_state(&ClientConsensusStatePath::new(
+            client_id.clone(),
+            client_height.revision_number(),
+            client_height.revision_height(),
+        ))
+        .unwrap()
+    else {
+        panic!("tendermint consensus state is expected")
+    };
+    let trusted_cs = trusted_cs.into_inner();
+    let current_timestamp = ctx_a.ibc_store.host_timestamp().unwrap();
+
+    tm_client_state
+        .verify_misbehaviour_with_states(
+            misbehaviour.clone(),
+            trusted_cs.clone(),
+            trusted_cs.clone(),
+            current_timestamp,
+        )
+        .expect("misbehaviour is verified");
+
+    // ten years ahead, well beyond the trusting period of the client
+    let later_timestamp =
+        (current_timestamp + Duration::from_secs(10 * 365 * 24 * 60 * 60)).unwrap();
+
+    let res = tm_client_state.verify_misbehaviour_with_states(
+        misbehaviour,
+        trusted_cs.clone(),
+        trusted_cs,
+        later_timestamp,
+    );
+    assert!(
+        matches!(res, Err(ClientError::ClientSpecific { ref description }) if description.contains("trusting period")),
+        "unexpected result: {res:?}"
+    );
+}
+
+/// Tests that misbehaviour evidence is rejected once the host time seen by
+/// the client is skewed far ahead, while it is accepted without skew.
+#[rstest]
+fn test_misbehaviour_synthetic_tendermint_client_time_offset() {
+    let client_id = tm_client_type().build_client_id(0);
+    let client_height = Height::new(1, 20).unwrap();
+    let misbehaviour_height = Height::new(1, 21).unwrap();
+    let chain_id_b = ChainId::new("mockgaiaB-1").unwrap();
+
+    let ctx_b = TestContextConfig::builder()
+        .host(
+            TendermintHost::builder()
+                .chain_id(chain_id_b.clone())
+                .build(),
+        )
+        .latest_height(misbehaviour_height)
+        .build::<TendermintContext>();
+
+    let mut ctx_a = TestContextConfig::builder()
+        .host(
+            MockHost::builder()
+                .chain_id(ChainId::new("mockgaiaA-1").unwrap())
+                .build(),
+        )
+        .latest_height(Height::new(1, 1).unwrap())
+        .build::<MockContext>()
+        .with_light_client(
+            &client_id,
+            LightClientBuilder::init()
+                .context(&ctx_b)
+                .consensus_heights([client_height])
+                .build(),
+        );
+
+    let router_a = MockRouter::new_with_transfer();
+
+    let header1: TmHeader = {
+        let block = ctx_b.host_block(&misbehaviour_height).unwrap();
+        let mut block = block.into_header();
+        block.set_trusted_height(client_height);
+        block.into()
+    };
+
+    let header2 = {
+        let mut tm_block = TendermintHost::builder()
+            .chain_id(chain_id_b)
+            .build()
+            .generate_block(
+                Vec::new(),
+                misbehaviour_height.revision_height(),
+                Timestamp::now(),
+                &Default::default(),
+            )
+            .into_header();
+        tm_block.set_trusted_height(client_height);
+        tm_block.into()
+    };
+
+    let msg = MsgUpdateClient {
+        client_id: client_id.clone(),
+        client_message: TmMisbehaviour::new(client_id.clone(), header1, header2).into(),
+        signer: dummy_account_id(),
+    };
+    let msg_envelope = MsgEnvelope::from(ClientMsg::from(msg));
+
+    // ten years ahead, well beyond the trusting period of the client
+    ctx_a.set_client_time_offset(
+        &client_id,
+        ClientTimeOffset::Ahead(Duration::from_secs(10 * 365 * 24 * 60 * 60)),
+    );
+
+    let res = validate(&ctx_a.ibc_store, &router_a, msg_envelope.clone());
+    assert!(
+        matches!(
+            res,
+            Err(ContextError::ClientError(ClientError::ClientNotActive {
+                status: Status::Expired
+            }))
+        ),
+        "unexpected result: {res:?}"
+    );
+
+    ctx_a.set_client_time_offset(&client_id, ClientTimeOffset::Ahead(Duration::ZERO));
+
+    let res = validate(&ctx_a.ibc_store, &router_a, msg_envelope);
+    assert!(res.is_ok(), "unexpected result: {res:?}");
+}
+
+/// Tests that a header is rejected once the host time seen by the client is
+/// skewed behind it by more than the max clock drift, while it is accepted
+/// without skew.
+#[rstest]
+fn test_update_synthetic_tendermint_client_time_offset_max_clock_drift() {
+    let client_id = tm_client_type().build_client_id(0);
+    let client_height = Height::new(1, 20).unwrap();
+    let update_height = Height::new(1, 21).unwrap();
+    let chain_id_b = ChainId::new("mockgaiaB-1").unwrap();
+
+    let ctx_b = TestContextConfig::builder()
+        .host(TendermintHost::builder().chain_id(chain_id_b).build())
+        .block_time(Duration::from_secs(60))
+        .latest_height(update_height)
+        .build::<TendermintContext>();
+
+    let mut ctx = TestContextConfig::builder()
+        .host(
+            MockHost::builder()
+                .chain_id(ChainId::new("mockgaiaA-1").unwrap())
+                .build(),
+        )
+        .latest_height(Height::new(1, 1).unwrap())
+        .build::<MockContext>()
+        .with_light_client(
+            &client_id,
+            LightClientBuilder::init()
+                .context(&ctx_b)
+                .consensus_heights([client_height])
+                .build(),
+        );
+
+    let router = MockRouter::new_with_transfer();
+
+    let block = ctx_b.host_block(&update_height).unwrap();
+    let mut block = block.into_header();
+    block.set_trusted_height(client_height);
+
+    let msg = MsgUpdateClient {
+        client_id: client_id.clone(),
+        client_message: block.into(),
+        signer: dummy_account_id(),
+    };
+    let msg_envelope = MsgEnvelope::from(ClientMsg::from(msg));
+
+    // the trusted consensus state is a minute old, while the header is seen
+    // 30 seconds ahead of the client clock, beyond its max clock drift
+    ctx.set_client_time_offset(
+        &client_id,
+        ClientTimeOffset::Behind(Duration::from_secs(30)),
+    );
+
+    let res = validate(&ctx.ibc_store, &router, msg_envelope.clone());
+    assert!(
+        matches!(
+            res,
+            Err(ContextError::ClientError(ClientError::ClientSpecific { ref description }))
+                if description.contains("header from the future")
+        ),
+        "unexpected result: {res:?}"
+    );
+
+    ctx.set_client_time_offset(&client_id, ClientTimeOffset::Ahead(Duration::ZERO));
+
+    let res = validate(&ctx.ibc_store, &router, msg_envelope);
+    assert!(res.is_ok(), "unexpected result: {res:?}");
+}
+
+#[rstest]
+fn test_misbehaviour_synthetic_tendermint_trusting_period_elapsed() {
+    let client_id = tm_client_type().build_client_id(0);
+    let trusted_height = Height::new(1, 10).unwrap();
+    let client_height = Height::new(1, 20).unwrap();
+    let misbehaviour_height = Height::new(1, 21).unwrap();
+    let chain_id_b = ChainId::new("mockgaiaB-1").unwrap();
+
+    let trusting_period = Duration::from_secs(60);
+
+    // The blocks of chain-B are 3 seconds apart, the latest one being at the
+    // same time as the one of chain-A.
+    let ctx_b = TestContextConfig::builder()
+        .host(
+            TendermintHost::builder()
+                .chain_id(chain_id_b.clone())
+                .build(),
+        )
+        .latest_height(misbehaviour_height)
+        .build::<TendermintContext>();
+
+    let mut ctx_a = TestContextConfig::builder()
+        .host(
+            MockHost::builder()
+                .chain_id(ChainId::new("mockgaiaA-1").unwrap())
+                .build(),
+        )
+        .latest_height(Height::new(1, 1).unwrap())
+        .build::<MockContext>()
+        .with_light_client(
+            &client_id,
+            LightClientBuilder::init()
+                .context(&ctx_b)
+                .consensus_heights([trusted_height, client_height])
+                .params(
+                    ClientStateConfig::builder()
+                        .trusting_period(trusting_period)
+                        .build(),
+                )
+                .build(),
+        );
+
+    let router_a = MockRouter::new_with_transfer();
+
+    let header1: TmHeader = {
+        let block = ctx_b.host_block(&misbehaviour_height).unwrap();
+        let mut block = block.into_header();
+        block.set_trusted_height(trusted_height);
+        block.into()
+    };
+
+    let header2 = {
+        let mut tm_block = TendermintHost::builder()
+            .chain_id(chain_id_b)
+            .build()
+            .generate_block(
+                Vec::new(),
+                misbehaviour_height.revision_height(),
+                (ctx_b.latest_timestamp() + Duration::from_secs(1)).unwrap(),
+                &Default::default(),
+            )
+            .into_header();
+        tm_block.set_trusted_height(trusted_height);
+        tm_block.into()
+    };
+
+    let msg = MsgUpdateClient {
+        client_id: client_id.clone(),
+        client_message: TmMisbehaviour::new(client_id.clone(), header1, header2).into(),
+        signer: dummy_account_id(),
+    };
+    let msg_envelope = MsgEnvelope::from(ClientMsg::from(msg));
+
+    // The trusted consensus state is 33 seconds old and the latest one 3
+    // seconds old. Another 30 seconds take the former out of the trusting
+    // period, while the latter stays within it and the client active.
+    let host_height = ctx_a.ibc_store.host_height().unwrap();
+    let host_timestamp = ctx_a.ibc_store.host_timestamp().unwrap();
+
+    ctx_a.advance_host_time(Duration::from_secs(30));
+
+    assert_eq!(
+        ctx_a.ibc_store.host_height().unwrap(),
+        host_height.increment()
+    );
+    assert_eq!(
+        ctx_a.ibc_store.host_timestamp().unwrap(),
+        (host_timestamp + Duration::from_secs(30)).unwrap()
+    );
+
+    let client_state = ctx_a.ibc_store.client_state(&client_id).unwrap();
+    assert_eq!(
+        client_state.status(&ctx_a.ibc_store, &client_id).unwrap(),
+        Status::Active
+    );
+
+    let res = validate(&ctx_a.ibc_store, &router_a, msg_envelope);
+    assert!(
+        matches!(
+            res,
+            Err(ContextError::ClientError(ClientError::ClientSpecific { ref description }))
+                if description.contains("trusting period")
+        ),
+        "unexpected result: {res:?}"
+    );
+}
+
 #[rstest]
 fn test_misbehaviour_synthetic_tendermint_bft_time() {
     let client_id = tm_client_type().build_client_id(0);
@@ -1390,6 +1870,82 @@ fn test_misbehaviour_synthetic_tendermint_bft_time() {
     ensure_misbehaviour(&ctx_a.ibc_store, &client_id, &tm_client_type());
 }
 
+/// Tests that misbehaviour evidence with a header lacking the trusted next
+/// validator set is rejected with a dedicated error.
+#[rstest]
+fn test_misbehaviour_missing_next_validator_set() {
+    let client_id = tm_client_type().build_client_id(0);
+    let client_height = Height::new(1, 20).unwrap();
+    let misbehaviour_height = Height::new(1, 21).unwrap();
+    let chain_id_b = ChainId::new("mockgaiaB-1").unwrap();
+
+    let ctx_b = TestContextConfig::builder()
+        .host(
+            TendermintHost::builder()
+                .chain_id(chain_id_b.clone())
+                .build(),
+        )
+        .latest_height(misbehaviour_height)
+        .build::<TendermintContext>();
+
+    let ctx_a = TestContextConfig::builder()
+        .host(
+            MockHost::builder()
+                .chain_id(ChainId::new("mockgaiaA-1").unwrap())
+                .build(),
+        )
+        .latest_height(Height::new(1, 1).unwrap())
+        .build::<MockContext>()
+        .with_light_client(
+            &client_id,
+            LightClientBuilder::init()
+                .context(&ctx_b)
+                .consensus_heights([client_height])
+                .build(),
+        );
+
+    let router_a = MockRouter::new_with_transfer();
+
+    let header1: TmHeader = {
+        let block = ctx_b.host_block(&misbehaviour_height).unwrap();
+        let mut block = block.into_header();
+        block.set_trusted_height(client_height);
+        block.into()
+    };
+
+    // Generate an equivocal header whose trusted next validator set was omitted
+    let header2 = {
+        let mut tm_block = TendermintHost::builder()
+            .chain_id(chain_id_b)
+            .build()
+            .generate_block(
+                Vec::new(),
+                misbehaviour_height.revision_height(),
+                Timestamp::now(),
+                &Default::default(),
+            )
+            .into_header();
+        tm_block.set_trusted_height(client_height);
+        tm_block.set_trusted_next_validators_set(ValidatorSet::without_proposer(Vec::new()));
+        tm_block.into()
+    };
+
+    let msg = MsgUpdateClient {
+        client_id: client_id.clone(),
+        client_message: TmMisbehaviour::new(client_id, header1, header2).into(),
+        signer: dummy_account_id(),
+    };
+    let msg_envelope = MsgEnvelope::from(ClientMsg::from(msg));
+
+    let res = validate(&ctx_a.ibc_store, &router_a, msg_envelope);
+    assert!(matches!(
+        res,
+        Err(ContextError::ClientError(
+            ClientError::MissingNextValidatorSet
+        ))
+    ));
+}
+
 #[rstest]
 fn test_expired_client() {
     let chain_id_b = ChainId::new("mockgaiaB-1").unwrap();
@@ -1,13 +1,13 @@
 use ibc::clients::tendermint::types::client_type;
-use ibc::core::client::context::{ClientValidationContext, ExtClientValidationContext};
-use ibc::core::client::types::error::{ClientError, UpgradeClientError};
+use ibc::core::client::context::{ClientExecutionContext, ClientValidationContext};
+use ibc::core::client::types::error::ClientError;
 use ibc::core::client::types::msgs::{ClientMsg, MsgUpgradeClient};
-use ibc::core::client::types::Height;
+use ibc::core::client::types::{Height, Status};
 use ibc::core::entrypoint::{execute, validate};
 use ibc::core::handler::types::error::ContextError;
 use ibc::core::handler::types::events::{IbcEvent, MessageEvent};
 use ibc::core::handler::types::msgs::MsgEnvelope;
-use ibc::core::host::types::path::ClientConsensusStatePath;
+use ibc::core::host::types::path::{ClientConsensusStatePath, ClientStatePath};
 use ibc_testkit::context::MockContext;
 use ibc_testkit::fixtures::clients::tendermint::{
     dummy_tendermint_header, dummy_tm_client_state_from_header,
@@ -15,7 +15,10 @@ use ibc_testkit::fixtures::clients::tendermint::{
 use ibc_testkit::fixtures::core::client::dummy_msg_upgrade_client;
 use ibc_testkit::fixtures::{Expect, Fixture};
 use ibc_testkit::hosts::MockHost;
-use ibc_testkit::testapp::ibc::clients::mock::client_state::client_type as mock_client_type;
+use ibc_testkit::testapp::ibc::clients::mock::client_state::{
+    client_type as mock_client_type, MockClientState,
+};
+use ibc_testkit::testapp::ibc::clients::mock::header::MockHeader;
 use ibc_testkit::testapp::ibc::clients::{AnyClientState, AnyConsensusState};
 use ibc_testkit::testapp::ibc::core::router::MockRouter;
 use ibc_testkit::testapp::ibc::core::types::LightClientState;
@@ -27,6 +30,7 @@ enum Ctx {
 
 enum Msg {
     Default,
+    EqualUpgradeHeight,
     LowUpgradeHeight,
     UnknownUpgradedClientStateType,
 }
@@ -47,6 +51,10 @@ fn msg_upgrade_client_fixture(ctx_variant: Ctx, msg_variant: Msg) -> Fixture<Msg
     let upgrade_height = Height::new(1, 26).unwrap();
     let msg_default = dummy_msg_upgrade_client(client_id.clone(), upgrade_height);
 
+    let equal_upgrade_height = Height::new(0, 42).unwrap();
+    let msg_with_equal_upgrade_height =
+        dummy_msg_upgrade_client(client_id.clone(), equal_upgrade_height);
+
     let low_upgrade_height = Height::new(0, 26).unwrap();
     let msg_with_low_upgrade_height = dummy_msg_upgrade_client(client_id, low_upgrade_height);
 
@@ -57,6 +65,7 @@ fn msg_upgrade_client_fixture(ctx_variant: Ctx, msg_variant: Msg) -> Fixture<Msg
 
     let msg = match msg_variant {
         Msg::Default => msg_default,
+        Msg::EqualUpgradeHeight => msg_with_equal_upgrade_height,
         Msg::LowUpgradeHeight => msg_with_low_upgrade_height,
         Msg::UnknownUpgradedClientStateType => msg_with_unknown_upgraded_cs,
     };
@@ -84,6 +93,10 @@ fn upgrade_client_validate(fxt: &Fixture<MsgUpgradeClient>, expect: Expect) {
 fn upgrade_client_execute(fxt: &mut Fixture<MsgUpgradeClient>, expect: Expect) {
     let mut router = MockRouter::new_with_transfer();
     let msg_envelope = MsgEnvelope::from(ClientMsg::from(fxt.msg.clone()));
+    let previous_height = fxt
+        .ctx
+        .client_state(&fxt.msg.client_id)
+        .map(|client_state| client_state.latest_height());
     let res = execute(&mut fxt.ctx, &mut router, msg_envelope);
     let err_msg = fxt.generate_error_msg(&expect, "execution", &res);
     match expect {
@@ -110,6 +123,10 @@ fn upgrade_client_execute(fxt: &mut Fixture<MsgUpgradeClient>, expect: Expect) {
             assert_eq!(upgrade_client_event.client_id(), &fxt.msg.client_id);
             assert_eq!(upgrade_client_event.client_type(), &mock_client_type());
             assert_eq!(upgrade_client_event.consensus_height(), &plan_height);
+            assert_eq!(
+                upgrade_client_event.previous_height(),
+                &previous_height.expect("client exists")
+            );
 
             let client_state = fxt.ctx.client_state(&fxt.msg.client_id).unwrap();
             let msg_client_state: AnyClientState =
@@ -138,6 +155,75 @@ fn msg_upgrade_client_healthy() {
     upgrade_client_execute(&mut fxt, Expect::Success);
 }
 
+#[test]
+fn upgrade_client_fail_frozen_client() {
+    let client_id = mock_client_type().build_client_id(0);
+
+    let mut ctx = MockContext::default().with_light_client(
+        &client_id,
+        LightClientState::<MockHost>::with_latest_height(Height::new(0, 42).unwrap()),
+    );
+    ctx.set_client_status(&client_id, Status::Frozen);
+
+    let msg = dummy_msg_upgrade_client(client_id, Height::new(1, 26).unwrap());
+    let router = MockRouter::new_with_transfer();
+
+    let res = validate(
+        &ctx.ibc_store,
+        &router,
+        MsgEnvelope::from(ClientMsg::from(msg)),
+    );
+
+    assert!(
+        matches!(
+            res,
+            Err(ContextError::ClientError(ClientError::ClientNotActive {
+                status: Status::Frozen
+            }))
+        ),
+        "upgrade_client must reject a frozen client, got: {res:?}"
+    );
+}
+
+#[test]
+fn upgrade_client_fail_client_with_frozen_height() {
+    let client_id = mock_client_type().build_client_id(0);
+    let latest_height = Height::new(0, 42).unwrap();
+
+    let mut ctx = MockContext::default().with_light_client(
+        &client_id,
+        LightClientState::<MockHost>::with_latest_height(latest_height),
+    );
+
+    let frozen_client_state = MockClientState::new(MockHeader::new(latest_height))
+        .with_frozen_height(Height::new(0, 40).unwrap());
+    ctx.ibc_store
+        .store_client_state(
+            ClientStatePath::new(client_id.clone()),
+            frozen_client_state.into(),
+        )
+        .unwrap();
+
+    let msg = dummy_msg_upgrade_client(client_id, Height::new(1, 26).unwrap());
+    let router = MockRouter::new_with_transfer();
+
+    let res = validate(
+        &ctx.ibc_store,
+        &router,
+        MsgEnvelope::from(ClientMsg::from(msg)),
+    );
+
+    assert!(
+        matches!(
+            res,
+            Err(ContextError::ClientError(ClientError::ClientNotActive {
+                status: Status::Frozen
+            }))
+        ),
+        "upgrade_client must reject a client with a frozen height, got: {res:?}"
+    );
+}
+
 #[test]
 fn upgrade_client_fail_nonexisting_client() {
     let fxt = msg_upgrade_client_fixture(Ctx::Default, Msg::Default);
@@ -147,19 +233,35 @@ fn upgrade_client_fail_nonexisting_client() {
     upgrade_client_validate(&fxt, Expect::Failure(Some(expected_err)));
 }
 
+fn upgrade_client_fail_upgrade_height(msg_variant: Msg, upgraded_height: Height) {
+    let fxt = msg_upgrade_client_fixture(Ctx::WithClient, msg_variant);
+    let router = MockRouter::new_with_transfer();
+
+    let res = validate(
+        &fxt.ctx,
+        &router,
+        MsgEnvelope::from(ClientMsg::from(fxt.msg.clone())),
+    );
+
+    let expected_current = Height::new(0, 42).unwrap();
+    assert!(
+        matches!(
+            res,
+            Err(ContextError::ClientError(ClientError::InvalidUpgradeHeight { current, upgraded }))
+                if current == expected_current && upgraded == upgraded_height
+        ),
+        "upgrade_client must reject an upgrade to height {upgraded_height}, got: {res:?}"
+    );
+}
+
+#[test]
+fn upgrade_client_fail_equal_upgrade_height() {
+    upgrade_client_fail_upgrade_height(Msg::EqualUpgradeHeight, Height::new(0, 42).unwrap());
+}
+
 #[test]
 fn upgrade_client_fail_low_upgrade_height() {
-    let fxt: Fixture<MsgUpgradeClient> =
-        msg_upgrade_client_fixture(Ctx::WithClient, Msg::LowUpgradeHeight);
-    let expected_err: ClientError = UpgradeClientError::LowUpgradeHeight {
-        upgraded_height: Height::new(0, 26).unwrap(),
-        client_height: fxt.ctx.host_height().unwrap(),
-    }
-    .into();
-    upgrade_client_validate(
-        &fxt,
-        Expect::Failure(Some(ContextError::from(expected_err))),
-    );
+    upgrade_client_fail_upgrade_height(Msg::LowUpgradeHeight, Height::new(0, 26).unwrap());
 }
 
 #[test]
@@ -1,24 +1,33 @@
+use core::time::Duration;
+
+use ibc::core::channel::types::acknowledgement::Acknowledgement;
 use ibc::core::channel::types::channel::{ChannelEnd, Counterparty, Order, State};
+use ibc::core::channel::types::commitment::compute_ack_commitment;
+use ibc::core::channel::types::error::{ChannelError, PacketError};
 use ibc::core::channel::types::msgs::{MsgRecvPacket, PacketMsg};
 use ibc::core::channel::types::packet::Packet;
 use ibc::core::channel::types::Version;
 use ibc::core::client::types::Height;
 use ibc::core::commitment_types::commitment::CommitmentPrefix;
+use ibc::core::connection::types::error::ConnectionError;
 use ibc::core::connection::types::version::Version as ConnectionVersion;
 use ibc::core::connection::types::{
     ConnectionEnd, Counterparty as ConnectionCounterparty, State as ConnectionState,
 };
 use ibc::core::entrypoint::{execute, validate};
+use ibc::core::handler::types::error::ContextError;
 use ibc::core::handler::types::events::{IbcEvent, MessageEvent};
 use ibc::core::handler::types::msgs::MsgEnvelope;
-use ibc::core::host::types::identifiers::{ChannelId, ClientId, ConnectionId, PortId};
+use ibc::core::host::types::identifiers::{ChannelId, ClientId, ConnectionId, PortId, Sequence};
+use ibc::core::host::types::path::AckPath;
+use ibc::core::host::ExecutionContext;
 use ibc::core::primitives::*;
 use ibc_testkit::context::MockContext;
 use ibc_testkit::fixtures::core::channel::{dummy_msg_recv_packet, dummy_raw_msg_recv_packet};
 use ibc_testkit::fixtures::core::signer::dummy_account_id;
 use ibc_testkit::hosts::MockHost;
 use ibc_testkit::testapp::ibc::core::router::MockRouter;
-use ibc_testkit::testapp::ibc::core::types::LightClientState;
+use ibc_testkit::testapp::ibc::core::types::{LightClientState, DEFAULT_BLOCK_TIME_SECS};
 use rstest::*;
 use test_log::test;
 
@@ -151,6 +160,128 @@ fn recv_packet_validate_happy_path(fixture: Fixture) {
     )
 }
 
+#[rstest]
+fn recv_packet_waits_for_connection_delay(fixture: Fixture) {
+    let Fixture {
+        context,
+        router,
+        msg,
+        conn_end_on_b,
+        chan_end_on_b,
+        client_height,
+        host_height,
+        ..
+    } = fixture;
+
+    // a delay of a single block, so that one block of that duration is enough
+    let delay = Duration::from_secs(DEFAULT_BLOCK_TIME_SECS);
+
+    let packet = &msg.packet;
+    let mut context = context
+        .advance_block_up_to_height(host_height)
+        // the client update metadata is recorded at the current host time
+        .with_light_client(
+            &ClientId::new("07-tendermint", 0).expect("no error"),
+            LightClientState::<MockHost>::with_latest_height(client_height),
+        )
+        .with_connection(ConnectionId::zero(), conn_end_on_b)
+        .with_connection_delay(delay)
+        .with_channel(
+            packet.port_id_on_b.clone(),
+            packet.chan_id_on_b.clone(),
+            chan_end_on_b,
+        )
+        .with_recv_sequence(
+            packet.port_id_on_b.clone(),
+            packet.chan_id_on_b.clone(),
+            packet.seq_on_a,
+        );
+
+    let msg_envelope = MsgEnvelope::from(PacketMsg::from(msg));
+
+    let res = validate(&context.ibc_store, &router, msg_envelope.clone());
+
+    assert!(
+        matches!(
+            res,
+            Err(ContextError::ConnectionError(
+                ConnectionError::NotEnoughTimeElapsed { .. }
+            ))
+        ),
+        "validation must wait for the connection delay, got: {res:?}"
+    );
+
+    context.advance_host_time(delay);
+
+    let res = validate(&context.ibc_store, &router, msg_envelope);
+
+    assert!(
+        res.is_ok(),
+        "validation succeeds once the delay has elapsed. err: {res:?}"
+    )
+}
+
+#[rstest]
+fn recv_packet_rejected_on_failed_proof_verification(fixture: Fixture) {
+    let Fixture {
+        context,
+        router,
+        msg,
+        conn_end_on_b,
+        chan_end_on_b,
+        client_height,
+        host_height,
+        ..
+    } = fixture;
+
+    let packet = &msg.packet;
+    let mut context = context
+        .with_light_client(
+            &ClientId::new("07-tendermint", 0).expect("no error"),
+            LightClientState::<MockHost>::with_latest_height(client_height),
+        )
+        .with_connection(ConnectionId::zero(), conn_end_on_b)
+        .with_channel(
+            packet.port_id_on_b.clone(),
+            packet.chan_id_on_b.clone(),
+            chan_end_on_b,
+        )
+        .with_send_sequence(
+            packet.port_id_on_b.clone(),
+            packet.chan_id_on_b.clone(),
+            1.into(),
+        )
+        .advance_block_up_to_height(host_height)
+        .with_recv_sequence(
+            packet.port_id_on_b.clone(),
+            packet.chan_id_on_b.clone(),
+            packet.seq_on_a,
+        );
+
+    context.fail_next_proof_verification();
+
+    let msg_envelope = MsgEnvelope::from(PacketMsg::from(msg.clone()));
+
+    let res = validate(&context.ibc_store, &router, msg_envelope);
+
+    assert!(
+        matches!(
+            res,
+            Err(ContextError::PacketError(PacketError::Channel(
+                ChannelError::PacketVerificationFailed { .. }
+            )))
+        ),
+        "Validation fails because the proof verification is faulted. err: {res:?}"
+    );
+
+    // The failure only applies once.
+    let msg_envelope = MsgEnvelope::from(PacketMsg::from(msg));
+
+    let res = validate(&context.ibc_store, &router, msg_envelope);
+
+    assert!(res.is_ok(), "Validation should succeed. err: {res:?}");
+}
+
 #[rstest]
 fn recv_packet_timeout_expired(fixture: Fixture) {
     let Fixture {
@@ -241,3 +372,41 @@ fn recv_packet_execute_happy_path(fixture: Fixture) {
     ));
     assert!(matches!(&ibc_events[3], &IbcEvent::WriteAcknowledgement(_)));
 }
+
+#[test]
+fn pending_acknowledgements_until_relayed() {
+    let port_id = PortId::transfer();
+    let chan_id = ChannelId::zero();
+
+    let mut context = MockContext::default();
+
+    let ack_commitments: Vec<_> = [1u64, 2]
+        .into_iter()
+        .map(|seq| {
+            let ack_commitment =
+                compute_ack_commitment(&Acknowledgement::try_from(vec![seq as u8]).unwrap());
+
+            context
+                .ibc_store
+                .store_packet_acknowledgement(
+                    &AckPath::new(&port_id, &chan_id, seq.into()),
+                    ack_commitment.clone(),
+                )
+                .expect("no error");
+
+            (Sequence::from(seq), ack_commitment.into_vec())
+        })
+        .collect();
+
+    assert_eq!(
+        context.pending_acknowledgements(&port_id, &chan_id),
+        ack_commitments
+    );
+
+    context.confirm_acknowledgement_relayed(&port_id, &chan_id, 1.into());
+
+    assert_eq!(
+        context.pending_acknowledgements(&port_id, &chan_id),
+        ack_commitments[1..]
+    );
+}
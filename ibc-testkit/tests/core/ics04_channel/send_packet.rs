@@ -1,24 +1,45 @@
+use core::cell::RefCell;
 use core::ops::Add;
 use core::time::Duration;
+use std::sync::{Arc, Mutex};
 
-use ibc::core::channel::handler::send_packet;
+use ibc::core::channel::context::{SendPacketExecutionContext, SendPacketValidationContext};
+use ibc::core::channel::handler::{
+    packet_metadata, send_packet, send_packet_deferred, send_packet_dry_run, send_packet_msg,
+    send_packet_streaming, send_packet_with_metadata, send_packet_with_rejection_code,
+    send_packet_with_router, PacketRejectionCode,
+};
 use ibc::core::channel::types::channel::{ChannelEnd, Counterparty, Order, State};
+use ibc::core::channel::types::commitment::{
+    compute_packet_commitment, estimate_packet_commitment_size, PacketCommitment,
+};
+use ibc::core::channel::types::error::{ChannelError, PacketError};
+use ibc::core::channel::types::events::SendPacket as SendPacketEvent;
+use ibc::core::channel::types::msgs::MsgSendPacket;
 use ibc::core::channel::types::packet::Packet;
-use ibc::core::channel::types::timeout::TimeoutHeight;
+use ibc::core::channel::types::timeout::{TimeoutHeight, TimeoutReason};
 use ibc::core::channel::types::Version;
-use ibc::core::client::types::Height;
-use ibc::core::commitment_types::commitment::CommitmentPrefix;
-use ibc::core::connection::types::version::Version as ConnectionVersion;
-use ibc::core::connection::types::{
-    ConnectionEnd, Counterparty as ConnectionCounterparty, State as ConnectionState,
-};
+use ibc::core::client::context::consensus_state::ConsensusState;
+use ibc::core::client::context::ClientValidationContext;
+use ibc::core::client::types::{Height, Status};
+use ibc::core::connection::types::error::ConnectionError;
+use ibc::core::connection::types::{ConnectionEnd, State as ConnectionState};
+use ibc::core::handler::types::error::ContextError;
 use ibc::core::handler::types::events::{IbcEvent, MessageEvent};
-use ibc::core::host::types::identifiers::{ChannelId, ClientId, ConnectionId, PortId};
+use ibc::core::host::types::identifiers::{ChannelId, ClientId, ConnectionId, PortId, Sequence};
+use ibc::core::host::types::path::{
+    ChannelEndPath, ClientConsensusStatePath, CommitmentPath, SeqSendPath,
+};
+use ibc::core::host::{ExecutionContext, ValidationContext};
 use ibc::core::primitives::*;
-use ibc_testkit::context::MockContext;
+use ibc::core::router::types::event::ModuleEvent;
+use ibc_testkit::context::{assert_deterministic, MockContext, MockStore, SharedIbcStore};
 use ibc_testkit::fixtures::core::channel::dummy_raw_packet;
+use ibc_testkit::fixtures::core::connection::ConnectionEndConfig;
 use ibc_testkit::hosts::MockHost;
-use ibc_testkit::testapp::ibc::core::types::LightClientState;
+use ibc_testkit::testapp::ibc::core::router::MockRouter;
+use ibc_testkit::testapp::ibc::core::types::{DefaultIbcStore, LightClientState};
+use rstest::rstest;
 use test_log::test;
 
 #[test]
@@ -41,18 +62,9 @@ fn send_packet_processing() {
     )
     .unwrap();
 
-    let conn_end_on_a = ConnectionEnd::new(
-        ConnectionState::Open,
-        default_client_id.clone(),
-        ConnectionCounterparty::new(
-            default_client_id,
-            Some(ConnectionId::zero()),
-            CommitmentPrefix::try_from(vec![0]).expect("no error"),
-        ),
-        ConnectionVersion::compatibles(),
-        ZERO_DURATION,
-    )
-    .unwrap();
+    let conn_end_on_a = ConnectionEndConfig::builder()
+        .client_id(default_client_id)
+        .build();
 
     let timestamp_future = Timestamp::now().add(Duration::from_secs(10)).unwrap();
     let timestamp_ns_past = 1;
@@ -172,7 +184,7 @@ fn send_packet_processing() {
         let res = send_packet(&mut test.ctx.ibc_store, test.packet.clone());
         // Additionally check the events and the output objects in the result.
         match res {
-            Ok(()) => {
+            Ok(result) => {
                 assert!(
                         test.want_pass,
                         "send_packet: test passed but was supposed to fail for test: {}, \nparams {:?} {:?}",
@@ -181,17 +193,43 @@ fn send_packet_processing() {
                         test.ctx
                     );
 
-                let ibc_events = test.ctx.get_events();
+                test.ctx.assert_event_count(2);
+                assert!(test
+                    .ctx
+                    .find_event(|event| matches!(event, IbcEvent::Message(MessageEvent::Channel)))
+                    .is_some());
+                assert_eq!(test.ctx.events_of_kind("send_packet").len(), 1);
 
-                assert!(!ibc_events.is_empty()); // Some events must exist.
+                let send_packet_event = test
+                    .ctx
+                    .last_send_packet()
+                    .expect("send packet event is emitted");
+                assert_eq!(send_packet_event.packet_sequence(), Sequence::from(1));
+                assert_eq!(send_packet_event.seq_on_a(), &result.packet.seq_on_a);
+                assert_eq!(
+                    send_packet_event.port_id_on_a(),
+                    &result.packet.port_id_on_a
+                );
+                assert_eq!(
+                    send_packet_event.chan_id_on_a(),
+                    &result.packet.chan_id_on_a
+                );
+                assert_eq!(
+                    send_packet_event.timeout_height_on_b(),
+                    &result.packet.timeout_height_on_b
+                );
 
-                assert_eq!(ibc_events.len(), 2);
-                assert!(matches!(
-                    &ibc_events[0],
-                    &IbcEvent::Message(MessageEvent::Channel)
-                ));
-                // TODO: The object in the output is a PacketResult what can we check on it?
-                assert!(matches!(&ibc_events[1], &IbcEvent::SendPacket(_)));
+                let commitment_path = CommitmentPath::new(
+                    &result.packet.port_id_on_a,
+                    &result.packet.chan_id_on_a,
+                    result.packet.seq_on_a,
+                );
+                assert_eq!(
+                    ValidationContext::get_packet_commitment(&test.ctx.ibc_store, &commitment_path)
+                        .expect("commitment is stored"),
+                    result.commitment
+                );
+                assert!(!result.duplicate);
             }
             Err(e) => {
                 assert!(
@@ -206,3 +244,1769 @@ fn send_packet_processing() {
         }
     }
 }
+
+/// Returns a context with an open `transfer/channel-0` channel whose next send
+/// sequence is `seq_send`.
+fn ctx_with_open_channel(seq_send: Sequence) -> MockContext {
+    let client_id = ClientId::new("07-tendermint", 0).expect("no error");
+
+    let chan_end_on_a = ChannelEnd::new(
+        State::Open,
+        Order::Unordered,
+        Counterparty::new(PortId::transfer(), Some(ChannelId::zero())),
+        vec![ConnectionId::zero()],
+        Version::new("ics20-1".to_string()),
+    )
+    .unwrap();
+
+    let conn_end_on_a = ConnectionEndConfig::builder()
+        .client_id(client_id.clone())
+        .build();
+
+    MockContext::default()
+        .with_light_client(
+            &client_id,
+            LightClientState::<MockHost>::with_latest_height(Height::new(0, 5).unwrap()),
+        )
+        .with_connection(ConnectionId::zero(), conn_end_on_a)
+        .with_channel(PortId::transfer(), ChannelId::zero(), chan_end_on_a)
+        .with_send_sequence(PortId::transfer(), ChannelId::zero(), seq_send)
+}
+
+/// Returns a packet on `transfer/channel-0` with the given sequence that
+/// times out in the future.
+fn packet_with_sequence(seq_on_a: Sequence) -> Packet {
+    let timestamp_future = Timestamp::now().add(Duration::from_secs(10)).unwrap();
+
+    let mut packet: Packet = dummy_raw_packet(10, timestamp_future.nanoseconds())
+        .try_into()
+        .unwrap();
+    packet.seq_on_a = seq_on_a;
+    packet
+}
+
+/// A send packet context forwarding to the given IBC store, except for the
+/// hooks overridden by `hooks`.
+struct HookedCtx<'a, H> {
+    ibc_store: &'a mut DefaultIbcStore,
+    hooks: H,
+}
+
+impl<'a, H: SendHooks> HookedCtx<'a, H> {
+    fn new(ibc_store: &'a mut DefaultIbcStore, hooks: H) -> Self {
+        Self { ibc_store, hooks }
+    }
+}
+
+/// The hooks of the send packet contexts a test overrides on a [`HookedCtx`].
+///
+/// Each hook defaults to the default of the send packet contexts, the ones
+/// reading or writing the store being given the wrapped IBC store.
+trait SendHooks {
+    fn validate_message_signer(&self, _signer: &Signer) -> Result<(), ContextError> {
+        Ok(())
+    }
+
+    fn allocate_send_sequence(
+        &self,
+        ibc_store: &DefaultIbcStore,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+    ) -> Result<Sequence, ContextError> {
+        ValidationContext::get_next_sequence_send(ibc_store, &SeqSendPath::new(port_id, channel_id))
+    }
+
+    fn default_timeout(
+        &self,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+    ) -> (Option<Height>, Option<Timestamp>) {
+        (None, None)
+    }
+
+    fn min_timeout_window(&self) -> (Option<u64>, Option<Duration>) {
+        (None, None)
+    }
+
+    fn commitment_domain_tag(&self) -> &[u8] {
+        &[]
+    }
+
+    fn commitment_version(&self) -> u8 {
+        0
+    }
+
+    fn packet_commitment(
+        &self,
+        _ibc_store: &DefaultIbcStore,
+        _commitment_path: &CommitmentPath,
+    ) -> Option<PacketCommitment> {
+        None
+    }
+
+    fn validate_packet_data(
+        &self,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+        _data: &[u8],
+    ) -> Result<(), PacketError> {
+        Ok(())
+    }
+
+    fn check_rate_limit(
+        &self,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+        _packet: &Packet,
+    ) -> Result<(), PacketError> {
+        Ok(())
+    }
+
+    fn store_packet_commitment(
+        &mut self,
+        ibc_store: &mut DefaultIbcStore,
+        commitment_path: &CommitmentPath,
+        commitment: PacketCommitment,
+    ) -> Result<(), ContextError> {
+        ExecutionContext::store_packet_commitment(ibc_store, commitment_path, commitment)
+    }
+
+    fn charge_send_packet_weight(&mut self, _data_len: usize) {}
+
+    fn on_commitment_stored(
+        &self,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+        _sequence: Sequence,
+        _commitment: &[u8],
+    ) {
+    }
+
+    fn record_rate_limit(&mut self, _port_id: &PortId, _channel_id: &ChannelId, _packet: &Packet) {}
+
+    fn app_send_events(&self, _packet: &Packet) -> Vec<IbcEvent> {
+        Vec::new()
+    }
+}
+
+impl<H: SendHooks> SendPacketValidationContext for HookedCtx<'_, H> {
+    type V = DefaultIbcStore;
+
+    fn get_client_validation_context(&self) -> &Self::V {
+        &*self.ibc_store
+    }
+
+    fn channel_end(&self, channel_end_path: &ChannelEndPath) -> Result<ChannelEnd, ContextError> {
+        ValidationContext::channel_end(&*self.ibc_store, channel_end_path)
+    }
+
+    fn connection_end(&self, connection_id: &ConnectionId) -> Result<ConnectionEnd, ContextError> {
+        ValidationContext::connection_end(&*self.ibc_store, connection_id)
+    }
+
+    fn get_next_sequence_send(
+        &self,
+        seq_send_path: &SeqSendPath,
+    ) -> Result<Sequence, ContextError> {
+        ValidationContext::get_next_sequence_send(&*self.ibc_store, seq_send_path)
+    }
+
+    fn validate_message_signer(&self, signer: &Signer) -> Result<(), ContextError> {
+        self.hooks.validate_message_signer(signer)
+    }
+
+    fn allocate_send_sequence(
+        &self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+    ) -> Result<Sequence, ContextError> {
+        self.hooks
+            .allocate_send_sequence(&*self.ibc_store, port_id, channel_id)
+    }
+
+    fn default_timeout(
+        &self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+    ) -> (Option<Height>, Option<Timestamp>) {
+        self.hooks.default_timeout(port_id, channel_id)
+    }
+
+    fn min_timeout_window(&self) -> (Option<u64>, Option<Duration>) {
+        self.hooks.min_timeout_window()
+    }
+
+    fn commitment_domain_tag(&self) -> &[u8] {
+        self.hooks.commitment_domain_tag()
+    }
+
+    fn commitment_version(&self) -> u8 {
+        self.hooks.commitment_version()
+    }
+
+    fn packet_commitment(&self, commitment_path: &CommitmentPath) -> Option<PacketCommitment> {
+        self.hooks
+            .packet_commitment(&*self.ibc_store, commitment_path)
+    }
+
+    fn validate_packet_data(
+        &self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        data: &[u8],
+    ) -> Result<(), PacketError> {
+        self.hooks.validate_packet_data(port_id, channel_id, data)
+    }
+
+    fn check_rate_limit(
+        &self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        packet: &Packet,
+    ) -> Result<(), PacketError> {
+        self.hooks.check_rate_limit(port_id, channel_id, packet)
+    }
+}
+
+impl<H: SendHooks> SendPacketExecutionContext for HookedCtx<'_, H> {
+    fn store_next_sequence_send(
+        &mut self,
+        seq_send_path: &SeqSendPath,
+        seq: Sequence,
+    ) -> Result<(), ContextError> {
+        ExecutionContext::store_next_sequence_send(&mut *self.ibc_store, seq_send_path, seq)
+    }
+
+    fn store_packet_commitment(
+        &mut self,
+        commitment_path: &CommitmentPath,
+        commitment: PacketCommitment,
+    ) -> Result<(), ContextError> {
+        self.hooks
+            .store_packet_commitment(&mut *self.ibc_store, commitment_path, commitment)
+    }
+
+    fn emit_ibc_event(&mut self, event: IbcEvent) -> Result<(), ContextError> {
+        ExecutionContext::emit_ibc_event(&mut *self.ibc_store, event)
+    }
+
+    fn log_message(&mut self, message: String) -> Result<(), ContextError> {
+        ExecutionContext::log_message(&mut *self.ibc_store, message)
+    }
+
+    fn charge_send_packet_weight(&mut self, data_len: usize) {
+        self.hooks.charge_send_packet_weight(data_len);
+    }
+
+    fn on_commitment_stored(
+        &self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        sequence: Sequence,
+        commitment: &[u8],
+    ) {
+        self.hooks
+            .on_commitment_stored(port_id, channel_id, sequence, commitment);
+    }
+
+    fn record_rate_limit(&mut self, port_id: &PortId, channel_id: &ChannelId, packet: &Packet) {
+        self.hooks.record_rate_limit(port_id, channel_id, packet);
+    }
+
+    fn app_send_events(&self, packet: &Packet) -> Vec<IbcEvent> {
+        self.hooks.app_send_events(packet)
+    }
+}
+
+#[test]
+fn send_packet_emits_well_formed_events() {
+    let mut ctx = ctx_with_open_channel(1.into());
+
+    ctx.send_packet(packet_with_sequence(1.into()))
+        .expect("send_packet succeeds");
+
+    assert_eq!(ctx.validate_events(), Ok(()));
+
+    let mut malformed_packet = packet_with_sequence(0.into());
+    malformed_packet.timeout_height_on_b = TimeoutHeight::Never;
+    malformed_packet.timeout_timestamp_on_b = Timestamp::none();
+
+    ExecutionContext::emit_ibc_event(
+        &mut ctx.ibc_store,
+        IbcEvent::SendPacket(SendPacketEvent::new(
+            malformed_packet,
+            Order::Unordered,
+            ConnectionId::zero(),
+        )),
+    )
+    .expect("no error");
+
+    let violations = ctx
+        .validate_events()
+        .expect_err("the injected event is malformed");
+
+    assert_eq!(violations.len(), 2);
+    assert!(violations[0].contains("zero sequence"));
+    assert!(violations[1].contains("neither a timeout height nor a timeout timestamp"));
+}
+
+#[test]
+fn send_packet_sequence_overflow() {
+    let mut ctx = ctx_with_open_channel(u64::MAX.into());
+
+    let res = send_packet(&mut ctx.ibc_store, packet_with_sequence(u64::MAX.into()));
+
+    assert!(
+        matches!(
+            res,
+            Err(ContextError::PacketError(PacketError::SequenceOverflow { ref channel_id }))
+                if channel_id == &ChannelId::zero()
+        ),
+        "send_packet must fail with a sequence overflow, got: {res:?}"
+    );
+    assert!(ctx.get_events().is_empty());
+}
+
+#[test]
+fn send_packet_with_metadata_stores_metadata() {
+    let mut ctx = ctx_with_open_channel(1.into());
+    let packet = packet_with_sequence(1.into());
+    let metadata = b"origin=transfer;memo=hello".to_vec();
+
+    send_packet_with_metadata(&mut ctx.ibc_store, packet.clone(), metadata.clone())
+        .expect("send_packet_with_metadata succeeds");
+
+    let read_metadata = |ctx: &MockContext, seq: Sequence| {
+        packet_metadata(
+            &ctx.ibc_store,
+            &packet.port_id_on_a,
+            &packet.chan_id_on_a,
+            seq,
+        )
+        .expect("no error")
+    };
+
+    assert_eq!(read_metadata(&ctx, packet.seq_on_a), Some(metadata.clone()));
+    assert_eq!(read_metadata(&ctx, 2.into()), None);
+
+    // resending the packet keeps the metadata of the original send
+    send_packet_with_metadata(&mut ctx.ibc_store, packet.clone(), b"other".to_vec())
+        .expect("resending the packet succeeds");
+
+    assert_eq!(read_metadata(&ctx, packet.seq_on_a), Some(metadata));
+}
+
+#[test]
+fn send_packet_with_unsupported_metadata_commits_nothing() {
+    let mut ctx = ctx_with_open_channel(1.into());
+    let packet = packet_with_sequence(1.into());
+
+    // `HookedCtx` keeps the default metadata hooks of the context
+    let mut hooked_ctx = HookedCtx::new(&mut ctx.ibc_store, AppEvents);
+
+    let res = send_packet_with_metadata(&mut hooked_ctx, packet.clone(), b"memo".to_vec());
+    assert!(res.is_err(), "send_packet_with_metadata must fail");
+
+    let commitment_path =
+        CommitmentPath::new(&packet.port_id_on_a, &packet.chan_id_on_a, packet.seq_on_a);
+    assert!(ValidationContext::get_packet_commitment(&ctx.ibc_store, &commitment_path).is_err());
+    assert_eq!(
+        ValidationContext::get_next_sequence_send(
+            &ctx.ibc_store,
+            &SeqSendPath::new(&packet.port_id_on_a, &packet.chan_id_on_a)
+        )
+        .expect("send sequence exists"),
+        1.into()
+    );
+    assert!(ctx.get_events().is_empty());
+}
+
+#[test]
+fn send_packet_on_unbound_port() {
+    let mut ctx = ctx_with_open_channel(1.into());
+    let packet = packet_with_sequence(1.into());
+
+    let res = send_packet_with_router(&mut ctx.ibc_store, &MockRouter::default(), packet.clone());
+
+    assert!(
+        matches!(
+            res,
+            Err(ContextError::PacketError(PacketError::PortNotBound { ref port_id }))
+                if port_id == &PortId::transfer()
+        ),
+        "send_packet must fail with an unbound port, got: {res:?}"
+    );
+    assert!(ctx.get_events().is_empty());
+
+    send_packet_with_router(&mut ctx.ibc_store, &MockRouter::new_with_transfer(), packet)
+        .expect("send_packet succeeds on a bound port");
+}
+
+#[test]
+fn send_packet_commitment_survives_state_export() {
+    let mut ctx = ctx_with_open_channel(1.into());
+    let packet = packet_with_sequence(1.into());
+
+    send_packet(&mut ctx.ibc_store, packet.clone()).expect("send_packet succeeds");
+
+    let commitment_path = CommitmentPath::new(&packet.port_id_on_a, &packet.chan_id_on_a, 1.into());
+    let commitment = ctx
+        .ibc_store
+        .get_packet_commitment(&commitment_path)
+        .expect("commitment stored");
+
+    let state = ctx.export_state();
+
+    let mut restored_ctx = MockContext::default();
+    assert!(restored_ctx
+        .ibc_store
+        .get_packet_commitment(&commitment_path)
+        .is_err());
+
+    restored_ctx.import_state(state);
+
+    assert_eq!(
+        restored_ctx
+            .ibc_store
+            .get_packet_commitment(&commitment_path)
+            .expect("commitment restored"),
+        commitment
+    );
+    assert_eq!(
+        restored_ctx
+            .ibc_store
+            .get_next_sequence_send(&SeqSendPath::new(
+                &packet.port_id_on_a,
+                &packet.chan_id_on_a
+            ))
+            .expect("sequence restored"),
+        2.into()
+    );
+    assert!(restored_ctx.get_events().is_empty());
+}
+
+#[test]
+fn send_packet_commitment_size_matches_estimate() {
+    let packet = packet_with_sequence(1.into());
+
+    for version in [0, 1] {
+        let mut ctx = ctx_with_open_channel(1.into());
+
+        let mut audit_ctx = HookedCtx::new(&mut ctx.ibc_store, Audit::default());
+        audit_ctx.hooks.commitment_version = version;
+
+        send_packet(&mut audit_ctx, packet.clone()).expect("send_packet succeeds");
+
+        let commitment = ctx
+            .ibc_store
+            .get_packet_commitment(&CommitmentPath::new(
+                &packet.port_id_on_a,
+                &packet.chan_id_on_a,
+                packet.seq_on_a,
+            ))
+            .expect("commitment stored");
+
+        assert_eq!(
+            commitment.as_ref().len(),
+            estimate_packet_commitment_size(version)
+        );
+    }
+}
+
+#[test]
+fn send_packet_channel_version_drift() {
+    let packet = packet_with_sequence(1.into());
+
+    let mut ctx = ctx_with_open_channel(1.into()).with_expected_channel_version(
+        PortId::transfer(),
+        ChannelId::zero(),
+        Version::new("ics20-1".to_string()),
+    );
+    send_packet(&mut ctx.ibc_store, packet.clone()).expect("consistent channel version");
+
+    let mut ctx = ctx_with_open_channel(1.into()).with_expected_channel_version(
+        PortId::transfer(),
+        ChannelId::zero(),
+        Version::new("ics20-2".to_string()),
+    );
+    let res = send_packet(&mut ctx.ibc_store, packet);
+
+    assert!(
+        matches!(
+            res,
+            Err(ContextError::PacketError(
+                PacketError::ChannelVersionMismatch { .. }
+            ))
+        ),
+        "send_packet must fail on a channel version drift, got: {res:?}"
+    );
+    assert!(ctx.get_events().is_empty());
+}
+
+/// Attaches an application event to every sent packet.
+struct AppEvents;
+
+impl AppEvents {
+    fn app_event() -> IbcEvent {
+        IbcEvent::Module(ModuleEvent {
+            kind: "fungible_token_packet".to_string(),
+            attributes: vec![("module", "transfer").into()],
+        })
+    }
+}
+
+impl SendHooks for AppEvents {
+    fn app_send_events(&self, _packet: &Packet) -> Vec<IbcEvent> {
+        vec![Self::app_event()]
+    }
+}
+
+#[test]
+fn send_packet_emits_app_events_after_core_events() {
+    let mut ctx = ctx_with_open_channel(1.into());
+
+    send_packet(
+        &mut HookedCtx::new(&mut ctx.ibc_store, AppEvents),
+        packet_with_sequence(1.into()),
+    )
+    .expect("send_packet succeeds");
+
+    let events = ctx.get_events();
+
+    assert_eq!(events.len(), 3);
+    assert!(matches!(
+        events[0],
+        IbcEvent::Message(MessageEvent::Channel)
+    ));
+    assert!(matches!(events[1], IbcEvent::SendPacket(_)));
+    assert_eq!(events[2], AppEvents::app_event());
+}
+
+/// Records the stored commitments and charged weights, under a configurable
+/// domain tag and commitment version.
+#[derive(Default)]
+struct Audit {
+    stored: RefCell<Vec<(PortId, ChannelId, Sequence, Vec<u8>)>>,
+    domain_tag: Vec<u8>,
+    commitment_version: u8,
+    charged_weights: Vec<usize>,
+}
+
+impl SendHooks for Audit {
+    fn commitment_domain_tag(&self) -> &[u8] {
+        &self.domain_tag
+    }
+
+    fn commitment_version(&self) -> u8 {
+        self.commitment_version
+    }
+
+    fn charge_send_packet_weight(&mut self, data_len: usize) {
+        self.charged_weights.push(data_len);
+    }
+
+    fn on_commitment_stored(
+        &self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        sequence: Sequence,
+        commitment: &[u8],
+    ) {
+        self.stored.borrow_mut().push((
+            port_id.clone(),
+            channel_id.clone(),
+            sequence,
+            commitment.to_vec(),
+        ));
+    }
+}
+
+#[test]
+fn send_packet_reports_stored_commitment() {
+    let mut ctx = ctx_with_open_channel(1.into());
+    let packet = packet_with_sequence(1.into());
+
+    let mut audit_ctx = HookedCtx::new(&mut ctx.ibc_store, Audit::default());
+
+    send_packet(&mut audit_ctx, packet.clone()).expect("send_packet succeeds");
+
+    let stored = audit_ctx.hooks.stored.into_inner();
+
+    let commitment = ValidationContext::get_packet_commitment(
+        &ctx.ibc_store,
+        &CommitmentPath::new(&packet.port_id_on_a, &packet.chan_id_on_a, packet.seq_on_a),
+    )
+    .expect("commitment is stored");
+
+    assert_eq!(
+        stored,
+        vec![(
+            packet.port_id_on_a,
+            packet.chan_id_on_a,
+            packet.seq_on_a,
+            commitment.into_vec(),
+        )]
+    );
+}
+
+#[test]
+fn send_packet_charges_weight_by_data_length() {
+    let mut ctx = ctx_with_open_channel(1.into());
+    let packet = packet_with_sequence(1.into());
+
+    let mut audit_ctx = HookedCtx::new(&mut ctx.ibc_store, Audit::default());
+
+    send_packet(&mut audit_ctx, packet.clone()).expect("send_packet succeeds");
+
+    assert_eq!(audit_ctx.hooks.charged_weights, vec![packet.data.len()]);
+}
+
+#[test]
+fn send_packet_commitment_domain_tag() {
+    let packet = packet_with_sequence(1.into());
+
+    let stored_commitment = |domain_tag: &[u8]| {
+        let mut ctx = ctx_with_open_channel(1.into());
+
+        let mut audit_ctx = HookedCtx::new(&mut ctx.ibc_store, Audit::default());
+        audit_ctx.hooks.domain_tag = domain_tag.to_vec();
+
+        send_packet(&mut audit_ctx, packet.clone()).expect("send_packet succeeds");
+
+        ValidationContext::get_packet_commitment(
+            &ctx.ibc_store,
+            &CommitmentPath::new(&packet.port_id_on_a, &packet.chan_id_on_a, packet.seq_on_a),
+        )
+        .expect("commitment is stored")
+    };
+
+    let default_commitment = stored_commitment(&[]);
+
+    assert_eq!(
+        default_commitment,
+        compute_packet_commitment(
+            &packet.data,
+            &packet.timeout_height_on_b,
+            &packet.timeout_timestamp_on_b,
+        )
+    );
+    assert_ne!(stored_commitment(b"my-chain"), default_commitment);
+}
+
+#[test]
+fn send_packet_commitment_version() {
+    let packet = packet_with_sequence(1.into());
+
+    let stored_commitment = |version: u8| {
+        let mut ctx = ctx_with_open_channel(1.into());
+
+        let mut audit_ctx = HookedCtx::new(&mut ctx.ibc_store, Audit::default());
+        audit_ctx.hooks.commitment_version = version;
+
+        send_packet(&mut audit_ctx, packet.clone()).expect("send_packet succeeds");
+
+        ValidationContext::get_packet_commitment(
+            &ctx.ibc_store,
+            &CommitmentPath::new(&packet.port_id_on_a, &packet.chan_id_on_a, packet.seq_on_a),
+        )
+        .expect("commitment is stored")
+    };
+
+    let unversioned_commitment = compute_packet_commitment(
+        &packet.data,
+        &packet.timeout_height_on_b,
+        &packet.timeout_timestamp_on_b,
+    );
+
+    assert_eq!(stored_commitment(0), unversioned_commitment);
+
+    let v1_commitment = stored_commitment(1);
+    let v2_commitment = stored_commitment(2);
+
+    assert_ne!(v1_commitment, v2_commitment);
+    assert_eq!(v1_commitment.as_ref()[0], 1);
+    assert_eq!(
+        &v1_commitment.as_ref()[1..],
+        unversioned_commitment.as_ref()
+    );
+    assert_eq!(v2_commitment.as_ref()[0], 2);
+}
+
+#[test]
+fn failed_send_packet_emits_no_events() {
+    let mut ctx = ctx_with_open_channel(1.into());
+
+    ctx.send_packet(packet_with_sequence(2.into()))
+        .expect_err("send_packet fails on a sequence mismatch");
+
+    ctx.assert_no_events();
+}
+
+/// Assigns a fixed sequence to every packet, regardless of the send sequence counter.
+struct FixedSequence(Sequence);
+
+impl SendHooks for FixedSequence {
+    fn allocate_send_sequence(
+        &self,
+        _ibc_store: &DefaultIbcStore,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+    ) -> Result<Sequence, ContextError> {
+        Ok(self.0)
+    }
+}
+
+#[test]
+fn send_packet_with_custom_sequence_allocator() {
+    let mut ctx = ctx_with_open_channel(1.into());
+
+    let mut fixed_ctx = HookedCtx::new(&mut ctx.ibc_store, FixedSequence(42.into()));
+
+    // the send sequence counter is not used by the allocator
+    let res = send_packet(&mut fixed_ctx, packet_with_sequence(1.into()));
+    assert!(
+        matches!(
+            res,
+            Err(ContextError::PacketError(PacketError::InvalidPacketSequence {
+                given_sequence,
+                next_sequence,
+            })) if given_sequence == 1.into() && next_sequence == 42.into()
+        ),
+        "send_packet must follow the allocated sequence, got: {res:?}"
+    );
+
+    let packet = packet_with_sequence(42.into());
+    send_packet(&mut fixed_ctx, packet.clone()).expect("send_packet succeeds");
+
+    let commitment_path =
+        CommitmentPath::new(&packet.port_id_on_a, &packet.chan_id_on_a, packet.seq_on_a);
+    ValidationContext::get_packet_commitment(&ctx.ibc_store, &commitment_path)
+        .expect("commitment is stored at the allocated sequence");
+
+    let next_seq_send = ValidationContext::get_next_sequence_send(
+        &ctx.ibc_store,
+        &SeqSendPath::new(&packet.port_id_on_a, &packet.chan_id_on_a),
+    )
+    .expect("no error");
+    assert_eq!(next_seq_send, 43.into());
+}
+
+/// Returns the given default timeouts for every channel.
+struct DefaultTimeout(Option<Height>, Option<Timestamp>);
+
+impl SendHooks for DefaultTimeout {
+    fn default_timeout(
+        &self,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+    ) -> (Option<Height>, Option<Timestamp>) {
+        (self.0, self.1)
+    }
+}
+
+#[test]
+fn send_packet_applies_default_timeout() {
+    let mut ctx = ctx_with_open_channel(1.into());
+
+    let default_timestamp = Timestamp::now().add(Duration::from_secs(10)).unwrap();
+    let explicit_timestamp = Timestamp::now().add(Duration::from_secs(20)).unwrap();
+
+    let mut default_ctx = HookedCtx::new(
+        &mut ctx.ibc_store,
+        DefaultTimeout(None, Some(default_timestamp)),
+    );
+
+    let mut packet = packet_with_sequence(1.into());
+    packet.timeout_height_on_b = TimeoutHeight::Never;
+    packet.timeout_timestamp_on_b = Timestamp::none();
+
+    // the packet alone would be rejected for having no timeout
+    send_packet(&mut default_ctx, packet.clone()).expect("default timeout is applied");
+
+    // an explicit timeout takes precedence over the default one
+    let mut explicit_packet = packet_with_sequence(2.into());
+    explicit_packet.timeout_height_on_b = TimeoutHeight::Never;
+    explicit_packet.timeout_timestamp_on_b = explicit_timestamp;
+
+    send_packet(&mut default_ctx, explicit_packet.clone()).expect("send_packet succeeds");
+
+    for (packet, timeout_timestamp) in [
+        (packet, default_timestamp),
+        (explicit_packet, explicit_timestamp),
+    ] {
+        let commitment_path =
+            CommitmentPath::new(&packet.port_id_on_a, &packet.chan_id_on_a, packet.seq_on_a);
+        let commitment = ValidationContext::get_packet_commitment(&ctx.ibc_store, &commitment_path)
+            .expect("commitment is stored");
+
+        assert_eq!(
+            commitment,
+            compute_packet_commitment(&packet.data, &TimeoutHeight::Never, &timeout_timestamp)
+        );
+    }
+}
+
+#[test]
+fn send_packet_call_log() {
+    let mut ctx = ctx_with_open_channel(1.into());
+
+    ctx.send_packet(packet_with_sequence(1.into()))
+        .expect("send_packet succeeds");
+    assert!(ctx.call_log().is_empty(), "recording is off by default");
+
+    ctx.record_calls(true);
+
+    for seq in [2, 3] {
+        ctx.send_packet(packet_with_sequence(seq.into()))
+            .expect("send_packet succeeds");
+    }
+
+    let call_log = ctx.call_log();
+
+    assert_eq!(call_log.len(), 2);
+    for (call, seq) in call_log.iter().zip(["2", "3"]) {
+        assert_eq!(call.handler, "send_packet");
+        assert!(call
+            .args
+            .contains(&("sequence".to_string(), seq.to_string())));
+    }
+}
+
+#[test]
+fn send_packet_rejects_decreasing_timeout_timestamp() {
+    let mut ctx = ctx_with_open_channel(1.into());
+
+    let packet = packet_with_sequence(1.into());
+    let committed_timestamp = packet.timeout_timestamp_on_b;
+
+    // sent straight through the handler, so the store records the timeout itself
+    send_packet(&mut ctx.ibc_store, packet.clone()).expect("send_packet succeeds");
+
+    let mut earlier_packet = packet.clone();
+    earlier_packet.timeout_timestamp_on_b = (committed_timestamp - Duration::from_secs(5)).unwrap();
+
+    let res = ctx.send_packet(earlier_packet.clone());
+
+    assert!(
+        matches!(
+            res,
+            Err(ContextError::PacketError(PacketError::DecreasingTimeoutTimestamp {
+                sequence,
+                committed,
+                given,
+            })) if sequence == packet.seq_on_a
+                && committed == committed_timestamp
+                && given == earlier_packet.timeout_timestamp_on_b
+        ),
+        "send_packet must reject an earlier timeout, got: {res:?}"
+    );
+
+    // a retry that does not shorten the timeout is only rejected for reusing the sequence
+    let mut later_packet = packet;
+    later_packet.timeout_timestamp_on_b = (committed_timestamp + Duration::from_secs(5)).unwrap();
+
+    let res = ctx.send_packet(later_packet);
+
+    assert!(
+        matches!(
+            res,
+            Err(ContextError::PacketError(
+                PacketError::InvalidPacketSequence { .. }
+            ))
+        ),
+        "send_packet must fail with an invalid sequence, got: {res:?}"
+    );
+}
+
+#[test]
+fn send_packet_on_non_open_connection() {
+    let ctx = ctx_with_open_channel(1.into());
+
+    let mut conn_end_on_a = ctx
+        .ibc_store
+        .connection_end(&ConnectionId::zero())
+        .expect("connection exists");
+    conn_end_on_a.set_state(ConnectionState::Init);
+
+    let mut ctx = ctx.with_connection(ConnectionId::zero(), conn_end_on_a);
+
+    let res = send_packet(&mut ctx.ibc_store, packet_with_sequence(1.into()));
+
+    assert!(
+        matches!(
+            res,
+            Err(ContextError::PacketError(PacketError::ConnectionNotOpen { ref connection_id }))
+                if connection_id == &ConnectionId::zero()
+        ),
+        "send_packet must fail on a non-open connection, got: {res:?}"
+    );
+    assert!(ctx.get_events().is_empty());
+}
+
+#[rstest]
+#[case::both_future(false, false)]
+#[case::height_past(true, false)]
+#[case::timestamp_past(false, true)]
+#[case::both_past(true, true)]
+fn send_packet_requires_both_timeouts_in_the_future(
+    #[case] height_passed: bool,
+    #[case] timestamp_passed: bool,
+) {
+    // the client on `ctx_with_open_channel` is at height 5
+    let timeout_height = if height_passed { 4 } else { 10 };
+    let timeout_timestamp = if timestamp_passed {
+        1
+    } else {
+        Timestamp::now()
+            .add(Duration::from_secs(10))
+            .unwrap()
+            .nanoseconds()
+    };
+
+    let mut packet: Packet = dummy_raw_packet(timeout_height, timeout_timestamp)
+        .try_into()
+        .unwrap();
+    packet.seq_on_a = 1.into();
+
+    let mut ctx = ctx_with_open_channel(1.into());
+
+    let res = send_packet(&mut ctx.ibc_store, packet);
+
+    match (height_passed, timestamp_passed) {
+        (false, false) => assert!(res.is_ok(), "send_packet must succeed, got: {res:?}"),
+        // the height is checked first when both have passed
+        (true, _) => assert!(
+            matches!(
+                res,
+                Err(ContextError::PacketError(PacketError::SendTimeout {
+                    reason: TimeoutReason::HeightAlreadyPassed { .. },
+                }))
+            ),
+            "send_packet must fail as the timeout height has passed, got: {res:?}"
+        ),
+        (false, true) => assert!(
+            matches!(
+                res,
+                Err(ContextError::PacketError(PacketError::SendTimeout {
+                    reason: TimeoutReason::TimestampAlreadyPassed { .. },
+                }))
+            ),
+            "send_packet must fail as the timeout timestamp has passed, got: {res:?}"
+        ),
+    }
+}
+
+#[test]
+fn send_packet_timeout_reasons() {
+    let timestamp_future = Timestamp::now().add(Duration::from_secs(10)).unwrap();
+
+    // the client on `ctx_with_open_channel` is at height 5
+    let mut packet_height_passed: Packet = dummy_raw_packet(4, timestamp_future.nanoseconds())
+        .try_into()
+        .unwrap();
+    packet_height_passed.seq_on_a = 1.into();
+
+    let mut packet_timestamp_passed: Packet = dummy_raw_packet(10, 1).try_into().unwrap();
+    packet_timestamp_passed.seq_on_a = 1.into();
+
+    let mut packet_without_timeout = packet_with_sequence(1.into());
+    packet_without_timeout.timeout_height_on_b = TimeoutHeight::no_timeout();
+    packet_without_timeout.timeout_timestamp_on_b = Timestamp::none();
+
+    let mut ctx = ctx_with_open_channel(1.into());
+
+    let res = send_packet(&mut ctx.ibc_store, packet_height_passed);
+    assert!(
+        matches!(
+            res,
+            Err(ContextError::PacketError(PacketError::SendTimeout {
+                reason: TimeoutReason::HeightAlreadyPassed {
+                    chain_height,
+                    timeout_height: TimeoutHeight::At(timeout_height),
+                },
+            })) if chain_height == Height::new(0, 5).unwrap()
+                && timeout_height == Height::new(0, 4).unwrap()
+        ),
+        "send_packet must fail as the timeout height has passed, got: {res:?}"
+    );
+
+    let res = send_packet(&mut ctx.ibc_store, packet_timestamp_passed);
+    assert!(
+        matches!(
+            res,
+            Err(ContextError::PacketError(PacketError::SendTimeout {
+                reason: TimeoutReason::TimestampAlreadyPassed { .. },
+            }))
+        ),
+        "send_packet must fail as the timeout timestamp has passed, got: {res:?}"
+    );
+
+    let res = send_packet(&mut ctx.ibc_store, packet_without_timeout);
+    assert!(
+        matches!(
+            res,
+            Err(ContextError::PacketError(PacketError::SendTimeout {
+                reason: TimeoutReason::BothUnset,
+            }))
+        ),
+        "send_packet must fail as no timeout is set, got: {res:?}"
+    );
+
+    assert!(ctx.get_events().is_empty());
+}
+
+/// Only accepts packet data encoded in JSON.
+struct JsonData;
+
+impl SendHooks for JsonData {
+    fn validate_packet_data(
+        &self,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+        data: &[u8],
+    ) -> Result<(), PacketError> {
+        serde_json::from_slice::<serde_json::Value>(data)
+            .map(|_| ())
+            .map_err(|e| PacketError::Other {
+                description: format!("packet data is not valid JSON: {e}"),
+            })
+    }
+}
+
+#[test]
+fn send_packet_rejects_invalid_packet_data() {
+    let mut ctx = ctx_with_open_channel(1.into());
+
+    let mut json_ctx = HookedCtx::new(&mut ctx.ibc_store, JsonData);
+
+    let mut packet = packet_with_sequence(1.into());
+    packet.data = b"not json".to_vec();
+
+    let res = send_packet(&mut json_ctx, packet.clone());
+    assert!(
+        matches!(
+            res,
+            Err(ContextError::PacketError(PacketError::Other { .. }))
+        ),
+        "send_packet must reject the packet data, got: {res:?}"
+    );
+
+    let commitment_path =
+        CommitmentPath::new(&packet.port_id_on_a, &packet.chan_id_on_a, packet.seq_on_a);
+    assert!(
+        ValidationContext::get_packet_commitment(&ctx.ibc_store, &commitment_path).is_err(),
+        "no commitment is stored for a rejected packet"
+    );
+
+    let mut json_ctx = HookedCtx::new(&mut ctx.ibc_store, JsonData);
+
+    packet.data = br#"{"amount":"1"}"#.to_vec();
+    send_packet(&mut json_ctx, packet).expect("send_packet succeeds");
+}
+
+#[test]
+fn send_packet_concurrently_on_shared_ibc_store() {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<SharedIbcStore<'_, MockStore>>();
+
+    let ctx = ctx_with_open_channel(1.into());
+
+    let chan_end_on_a = ValidationContext::channel_end(
+        &ctx.ibc_store,
+        &ChannelEndPath::new(&PortId::transfer(), &ChannelId::zero()),
+    )
+    .expect("channel exists");
+
+    let mut ctx = ctx
+        .with_channel(PortId::transfer(), ChannelId::new(1), chan_end_on_a)
+        .with_send_sequence(PortId::transfer(), ChannelId::new(1), 1.into());
+
+    let packets: Vec<Packet> = (0..2)
+        .map(|channel_index| {
+            let mut packet = packet_with_sequence(1.into());
+            packet.chan_id_on_a = ChannelId::new(channel_index);
+            packet
+        })
+        .collect();
+
+    ctx.with_shared_ibc_store(|shared_ibc_store| {
+        std::thread::scope(|scope| {
+            let handles: Vec<_> = packets
+                .iter()
+                .cloned()
+                .map(|packet| scope.spawn(move || shared_ibc_store.send_packet(packet)))
+                .collect();
+
+            for handle in handles {
+                handle
+                    .join()
+                    .expect("thread does not panic")
+                    .expect("send_packet succeeds");
+            }
+        })
+    });
+
+    for packet in &packets {
+        let commitment_path =
+            CommitmentPath::new(&packet.port_id_on_a, &packet.chan_id_on_a, packet.seq_on_a);
+        ValidationContext::get_packet_commitment(&ctx.ibc_store, &commitment_path)
+            .expect("commitment is stored on each channel");
+    }
+
+    // each packet emits a message event and a send packet event
+    assert_eq!(ctx.get_events().len(), 4);
+
+    // a panicking closure leaves the store in the context
+    let res = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        ctx.with_shared_ibc_store(|_| panic!("closure panics"))
+    }));
+    assert!(res.is_err());
+    assert_eq!(ctx.get_events().len(), 4);
+}
+
+#[test]
+fn send_packet_events_are_forwarded_to_sink() {
+    let mut ctx = ctx_with_open_channel(1.into());
+
+    let sink_events = Arc::new(Mutex::new(Vec::new()));
+    let sink = sink_events.clone();
+    ctx.set_event_sink(move |event| sink.lock().unwrap().push(event.clone()));
+
+    ctx.send_packet(packet_with_sequence(1.into()))
+        .expect("send_packet succeeds");
+
+    assert!(!ctx.get_events().is_empty());
+    assert_eq!(*sink_events.lock().unwrap(), ctx.get_events());
+}
+
+#[test]
+fn send_packet_is_idempotent_for_duplicate_sends() {
+    let mut ctx = ctx_with_open_channel(1.into());
+    let packet = packet_with_sequence(1.into());
+
+    ctx.send_packet(packet.clone())
+        .expect("send_packet succeeds");
+
+    let events = ctx.get_events();
+    let commitment_path =
+        CommitmentPath::new(&packet.port_id_on_a, &packet.chan_id_on_a, packet.seq_on_a);
+    let commitment = ValidationContext::get_packet_commitment(&ctx.ibc_store, &commitment_path)
+        .expect("commitment is stored");
+
+    // the duplicate send succeeds without storing nor emitting anything
+    ctx.send_packet(packet.clone())
+        .expect("duplicate send_packet succeeds");
+
+    assert_eq!(ctx.get_events(), events);
+    assert_eq!(
+        ValidationContext::get_packet_commitment(&ctx.ibc_store, &commitment_path)
+            .expect("commitment is stored"),
+        commitment
+    );
+
+    let next_seq_send = ValidationContext::get_next_sequence_send(
+        &ctx.ibc_store,
+        &SeqSendPath::new(&packet.port_id_on_a, &packet.chan_id_on_a),
+    )
+    .expect("no error");
+    assert_eq!(next_seq_send, 2.into());
+
+    // a different packet at the same sequence is not a duplicate
+    let mut other_packet = packet;
+    other_packet.data = b"other data".to_vec();
+
+    let res = ctx.send_packet(other_packet);
+    assert!(
+        matches!(
+            res,
+            Err(ContextError::PacketError(
+                PacketError::InvalidPacketSequence { .. }
+            ))
+        ),
+        "send_packet must fail with an invalid sequence, got: {res:?}"
+    );
+}
+
+#[test]
+fn send_packet_duplicate_on_closed_channel() {
+    let mut ctx = ctx_with_open_channel(1.into());
+    let packet = packet_with_sequence(1.into());
+
+    ctx.send_packet(packet.clone())
+        .expect("send_packet succeeds");
+
+    let closed_chan_end = ChannelEnd::new(
+        State::Closed,
+        Order::Unordered,
+        Counterparty::new(PortId::transfer(), Some(ChannelId::zero())),
+        vec![ConnectionId::zero()],
+        Version::new("ics20-1".to_string()),
+    )
+    .unwrap();
+
+    let mut ctx = ctx.with_channel(PortId::transfer(), ChannelId::zero(), closed_chan_end);
+
+    // a duplicate send is still validated against the state of the channel
+    let res = send_packet_dry_run(&ctx.ibc_store, packet.clone());
+    assert!(
+        matches!(res, Err(PacketError::Channel(ChannelError::ChannelClosed))),
+        "send_packet_dry_run must fail on a closed channel, got: {res:?}"
+    );
+
+    let res = send_packet_deferred(&mut ctx.ibc_store, packet.clone());
+    assert!(
+        matches!(res, Err(PacketError::Channel(ChannelError::ChannelClosed))),
+        "send_packet_deferred must fail on a closed channel, got: {res:?}"
+    );
+
+    let res = send_packet(&mut ctx.ibc_store, packet);
+    assert!(
+        matches!(
+            res,
+            Err(ContextError::ChannelError(ChannelError::ChannelClosed))
+        ),
+        "send_packet must fail on a closed channel, got: {res:?}"
+    );
+}
+
+#[test]
+fn send_packet_dry_run_matches_send_packet() {
+    let valid = packet_with_sequence(1.into());
+
+    let wrong_sequence = packet_with_sequence(2.into());
+
+    let mut no_timeout = packet_with_sequence(1.into());
+    no_timeout.timeout_height_on_b = TimeoutHeight::Never;
+    no_timeout.timeout_timestamp_on_b = Timestamp::none();
+
+    let mut expired_height = packet_with_sequence(1.into());
+    expired_height.timeout_height_on_b = TimeoutHeight::At(Height::new(0, 3).unwrap());
+
+    let mut wrong_channel = packet_with_sequence(1.into());
+    wrong_channel.chan_id_on_a = ChannelId::new(1);
+
+    for packet in [
+        valid,
+        wrong_sequence,
+        no_timeout,
+        expired_height,
+        wrong_channel,
+    ] {
+        let mut ctx = ctx_with_open_channel(1.into());
+        let events = ctx.get_events();
+
+        let dry_run = send_packet_dry_run(&ctx.ibc_store, packet.clone());
+
+        // the dry run neither emits events nor stores the commitment
+        assert_eq!(ctx.get_events(), events);
+
+        let commitment_path =
+            CommitmentPath::new(&packet.port_id_on_a, &packet.chan_id_on_a, packet.seq_on_a);
+        assert!(
+            ValidationContext::get_packet_commitment(&ctx.ibc_store, &commitment_path).is_err()
+        );
+
+        let res = send_packet(&mut ctx.ibc_store, packet.clone());
+
+        match (dry_run, res) {
+            (Ok(result), Ok(sent)) => {
+                assert_eq!(sent, result);
+                assert!(!result.duplicate);
+                assert_eq!(
+                    ValidationContext::get_packet_commitment(&ctx.ibc_store, &commitment_path)
+                        .expect("commitment is stored"),
+                    result.commitment
+                );
+            }
+            (Err(dry_run_err), Err(err)) => {
+                let err = match err {
+                    ContextError::PacketError(e) => e,
+                    ContextError::ChannelError(e) => PacketError::Channel(e),
+                    err => panic!("unexpected send_packet error for {packet:?}: {err:?}"),
+                };
+                assert_eq!(dry_run_err.to_string(), err.to_string());
+            }
+            (dry_run, res) => {
+                panic!("dry run and send_packet disagree for {packet:?}: {dry_run:?} vs {res:?}")
+            }
+        }
+    }
+}
+
+/// Lets through at most `limit` packets.
+struct RateLimit {
+    limit: usize,
+    sent: usize,
+}
+
+impl SendHooks for RateLimit {
+    fn check_rate_limit(
+        &self,
+        _port_id: &PortId,
+        channel_id: &ChannelId,
+        _packet: &Packet,
+    ) -> Result<(), PacketError> {
+        if self.sent >= self.limit {
+            return Err(PacketError::Other {
+                description: format!("rate limit of channel {channel_id} exceeded"),
+            });
+        }
+
+        Ok(())
+    }
+
+    fn record_rate_limit(&mut self, _port_id: &PortId, _channel_id: &ChannelId, _packet: &Packet) {
+        self.sent += 1;
+    }
+}
+
+#[test]
+fn send_packet_rejected_once_rate_limit_is_reached() {
+    let limit = 2;
+
+    let mut ctx = ctx_with_open_channel(1.into());
+    let mut rate_limit_ctx = HookedCtx::new(&mut ctx.ibc_store, RateLimit { limit, sent: 0 });
+
+    for seq in 1..=limit as u64 {
+        send_packet(&mut rate_limit_ctx, packet_with_sequence(seq.into()))
+            .expect("send_packet succeeds within the rate limit");
+    }
+    assert_eq!(rate_limit_ctx.hooks.sent, limit);
+
+    let packet = packet_with_sequence((limit as u64 + 1).into());
+    let res = send_packet(&mut rate_limit_ctx, packet.clone());
+
+    assert!(
+        matches!(
+            res,
+            Err(ContextError::PacketError(PacketError::Other { .. }))
+        ),
+        "send_packet must fail once the rate limit is reached, got: {res:?}"
+    );
+    assert_eq!(rate_limit_ctx.hooks.sent, limit);
+
+    assert!(ValidationContext::get_packet_commitment(
+        &ctx.ibc_store,
+        &CommitmentPath::new(&packet.port_id_on_a, &packet.chan_id_on_a, packet.seq_on_a),
+    )
+    .is_err());
+}
+
+#[test]
+fn send_packet_deferred_returns_events_without_emitting_them() {
+    let mut ctx = ctx_with_open_channel(1.into());
+    let packet = packet_with_sequence(1.into());
+
+    let events = send_packet_deferred(&mut ctx.ibc_store, packet.clone())
+        .expect("send_packet_deferred succeeds");
+
+    // the state is committed
+    assert!(ValidationContext::get_packet_commitment(
+        &ctx.ibc_store,
+        &CommitmentPath::new(&packet.port_id_on_a, &packet.chan_id_on_a, packet.seq_on_a),
+    )
+    .is_ok());
+    assert_eq!(
+        ValidationContext::get_next_sequence_send(
+            &ctx.ibc_store,
+            &SeqSendPath::new(&packet.port_id_on_a, &packet.chan_id_on_a),
+        )
+        .expect("no error"),
+        2.into()
+    );
+
+    // but the events are left to the caller
+    ctx.assert_no_events();
+
+    assert_eq!(events.len(), 2);
+    assert!(matches!(
+        events[0],
+        IbcEvent::Message(MessageEvent::Channel)
+    ));
+    let IbcEvent::SendPacket(send_packet_event) = &events[1] else {
+        panic!("unexpected event: {:?}", events[1]);
+    };
+    assert_eq!(send_packet_event.packet_sequence(), packet.seq_on_a);
+}
+
+#[test]
+fn send_packet_surfaces_injected_store_read_failures() {
+    let mut ctx = ctx_with_open_channel(1.into());
+
+    ctx.fail_next_channel_read();
+
+    let res = send_packet(&mut ctx.ibc_store, packet_with_sequence(1.into()));
+    assert!(
+        matches!(
+            res,
+            Err(ContextError::ChannelError(ChannelError::Other { .. }))
+        ),
+        "send_packet must surface the channel read failure, got: {res:?}"
+    );
+
+    ctx.fail_next_connection_read();
+
+    let res = send_packet(&mut ctx.ibc_store, packet_with_sequence(1.into()));
+    assert!(
+        matches!(
+            res,
+            Err(ContextError::ConnectionError(ConnectionError::Other { .. }))
+        ),
+        "send_packet must surface the connection read failure, got: {res:?}"
+    );
+    ctx.assert_no_events();
+
+    // the failures only affect a single read
+    send_packet(&mut ctx.ibc_store, packet_with_sequence(1.into())).expect("no error");
+}
+
+/// Requires the given minimum timeout window.
+struct MinTimeoutWindow(Option<u64>, Option<Duration>);
+
+impl SendHooks for MinTimeoutWindow {
+    fn min_timeout_window(&self) -> (Option<u64>, Option<Duration>) {
+        (self.0, self.1)
+    }
+}
+
+#[test]
+fn send_packet_enforces_min_timeout_height_window() {
+    let mut ctx = ctx_with_open_channel(1.into());
+
+    // the client on `ctx_with_open_channel` is at height 5
+    let mut window_ctx = HookedCtx::new(&mut ctx.ibc_store, MinTimeoutWindow(Some(10), None));
+
+    let mut packet = packet_with_sequence(1.into());
+    packet.timeout_timestamp_on_b = Timestamp::none();
+
+    packet.timeout_height_on_b = TimeoutHeight::At(Height::new(0, 14).unwrap());
+    let res = send_packet(&mut window_ctx, packet.clone());
+    assert!(
+        matches!(
+            res,
+            Err(ContextError::PacketError(PacketError::SendTimeout {
+                reason: TimeoutReason::HeightTooSoon {
+                    min_timeout_height,
+                    ..
+                },
+            })) if min_timeout_height == Height::new(0, 15).unwrap()
+        ),
+        "send_packet must reject a timeout height just outside the window, got: {res:?}"
+    );
+
+    packet.timeout_height_on_b = TimeoutHeight::At(Height::new(0, 15).unwrap());
+    send_packet(&mut window_ctx, packet).expect("timeout height just inside the window");
+}
+
+#[test]
+fn send_packet_enforces_min_timeout_timestamp_window() {
+    let mut ctx = ctx_with_open_channel(1.into());
+
+    let client_id = ClientId::new("07-tendermint", 0).expect("no error");
+    let latest_timestamp = ctx
+        .ibc_store
+        .consensus_state(&ClientConsensusStatePath::new(client_id, 0, 5))
+        .expect("consensus state exists")
+        .timestamp();
+
+    let min_duration = Duration::from_secs(60);
+    let mut window_ctx = HookedCtx::new(
+        &mut ctx.ibc_store,
+        MinTimeoutWindow(None, Some(min_duration)),
+    );
+
+    let mut packet = packet_with_sequence(1.into());
+    packet.timeout_height_on_b = TimeoutHeight::Never;
+
+    packet.timeout_timestamp_on_b = (latest_timestamp + Duration::from_secs(59)).unwrap();
+    let res = send_packet(&mut window_ctx, packet.clone());
+    assert!(
+        matches!(
+            res,
+            Err(ContextError::PacketError(PacketError::SendTimeout {
+                reason: TimeoutReason::TimestampTooSoon { .. },
+            }))
+        ),
+        "send_packet must reject a timeout timestamp just outside the window, got: {res:?}"
+    );
+
+    packet.timeout_timestamp_on_b = (latest_timestamp + min_duration).unwrap();
+    send_packet(&mut window_ctx, packet).expect("timeout timestamp just inside the window");
+}
+
+#[test]
+fn send_packet_dry_run_reports_timeout_budget() {
+    let ctx = ctx_with_open_channel(1.into());
+
+    // the client on `ctx_with_open_channel` is at height 5
+    let client_id = ClientId::new("07-tendermint", 0).expect("no error");
+    let latest_timestamp = ctx
+        .ibc_store
+        .consensus_state(&ClientConsensusStatePath::new(client_id, 0, 5))
+        .expect("consensus state exists")
+        .timestamp();
+
+    let mut packet = packet_with_sequence(1.into());
+    packet.timeout_height_on_b = TimeoutHeight::At(Height::new(0, 12).unwrap());
+    packet.timeout_timestamp_on_b = (latest_timestamp + Duration::from_secs(90)).unwrap();
+
+    let result = send_packet_dry_run(&ctx.ibc_store, packet).expect("no error");
+
+    assert_eq!(result.blocks_until_height_timeout, Some(7));
+    assert_eq!(
+        result.duration_until_timestamp_timeout,
+        Some(Duration::from_secs(90))
+    );
+
+    let mut no_timeout_height = packet_with_sequence(1.into());
+    no_timeout_height.timeout_height_on_b = TimeoutHeight::Never;
+
+    let result = send_packet_dry_run(&ctx.ibc_store, no_timeout_height).expect("no error");
+
+    assert_eq!(result.blocks_until_height_timeout, None);
+}
+
+/// Only accepts messages signed by the given signer.
+struct AllowedSigner(Signer);
+
+impl SendHooks for AllowedSigner {
+    fn validate_message_signer(&self, signer: &Signer) -> Result<(), ContextError> {
+        if signer != &self.0 {
+            return Err(ChannelError::Other {
+                description: format!("unauthorized signer: {signer}"),
+            }
+            .into());
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn send_packet_msg_rejects_invalid_signer_before_commitment() {
+    let mut ctx = ctx_with_open_channel(1.into());
+
+    let packet = packet_with_sequence(1.into());
+    let commitment_path =
+        CommitmentPath::new(&packet.port_id_on_a, &packet.chan_id_on_a, packet.seq_on_a);
+
+    let mut signer_ctx = HookedCtx::new(
+        &mut ctx.ibc_store,
+        AllowedSigner(Signer::from("relayer".to_string())),
+    );
+
+    let res = send_packet_msg(
+        &mut signer_ctx,
+        MsgSendPacket::new(packet.clone(), Signer::from("intruder".to_string())),
+    );
+    assert!(
+        matches!(
+            res,
+            Err(ContextError::ChannelError(ChannelError::Other { .. }))
+        ),
+        "send_packet_msg must reject the signer, got: {res:?}"
+    );
+    assert!(
+        ValidationContext::get_packet_commitment(&*signer_ctx.ibc_store, &commitment_path).is_err()
+    );
+    assert!(signer_ctx.ibc_store.events.lock().is_empty());
+
+    send_packet_msg(
+        &mut signer_ctx,
+        MsgSendPacket::new(packet, Signer::from("relayer".to_string())),
+    )
+    .expect("send_packet_msg succeeds");
+
+    ValidationContext::get_packet_commitment(&ctx.ibc_store, &commitment_path)
+        .expect("commitment is stored");
+}
+
+#[test]
+fn send_packet_rejection_codes() {
+    let client_id = ClientId::new("07-tendermint", 0).expect("no error");
+
+    let closed_chan_end = ChannelEnd::new(
+        State::Closed,
+        Order::Unordered,
+        Counterparty::new(PortId::transfer(), Some(ChannelId::zero())),
+        vec![ConnectionId::zero()],
+        Version::new("ics20-1".to_string()),
+    )
+    .unwrap();
+
+    let mut frozen_ctx = ctx_with_open_channel(1.into());
+    frozen_ctx.set_client_status(&client_id, Status::Frozen);
+
+    let packet_on_unknown_channel = {
+        let mut packet = packet_with_sequence(1.into());
+        packet.chan_id_on_a = ChannelId::new(7);
+        packet
+    };
+
+    let packet_to_other_counterparty = {
+        let mut packet = packet_with_sequence(1.into());
+        packet.chan_id_on_b = ChannelId::new(9);
+        packet
+    };
+
+    let packet_with_passed_timeout = {
+        let mut packet = packet_with_sequence(1.into());
+        packet.timeout_height_on_b = TimeoutHeight::At(Height::new(0, 1).unwrap());
+        packet
+    };
+
+    let packet_without_timeout = {
+        let mut packet = packet_with_sequence(1.into());
+        packet.timeout_height_on_b = TimeoutHeight::Never;
+        packet.timeout_timestamp_on_b = Timestamp::none();
+        packet
+    };
+
+    let cases = vec![
+        (
+            "unknown channel",
+            ctx_with_open_channel(1.into()),
+            packet_on_unknown_channel,
+            PacketRejectionCode::ChannelNotFound,
+        ),
+        (
+            "closed channel",
+            ctx_with_open_channel(1.into()).with_channel(
+                PortId::transfer(),
+                ChannelId::zero(),
+                closed_chan_end,
+            ),
+            packet_with_sequence(1.into()),
+            PacketRejectionCode::ChannelClosed,
+        ),
+        (
+            "counterparty mismatch",
+            ctx_with_open_channel(1.into()),
+            packet_to_other_counterparty,
+            PacketRejectionCode::CounterpartyMismatch,
+        ),
+        (
+            "frozen client",
+            frozen_ctx,
+            packet_with_sequence(1.into()),
+            PacketRejectionCode::ClientInactive,
+        ),
+        (
+            "timeout passed",
+            ctx_with_open_channel(1.into()),
+            packet_with_passed_timeout,
+            PacketRejectionCode::TimeoutPassed,
+        ),
+        (
+            "timeout unset",
+            ctx_with_open_channel(1.into()),
+            packet_without_timeout,
+            PacketRejectionCode::TimeoutUnset,
+        ),
+        (
+            "sequence gap",
+            ctx_with_open_channel(1.into()),
+            packet_with_sequence(3.into()),
+            PacketRejectionCode::SequenceGap,
+        ),
+        (
+            "sequence overflow",
+            ctx_with_open_channel(u64::MAX.into()),
+            packet_with_sequence(u64::MAX.into()),
+            PacketRejectionCode::SequenceOverflow,
+        ),
+    ];
+
+    for (name, mut ctx, packet, expected_code) in cases {
+        let res = send_packet_with_rejection_code(&mut ctx.ibc_store, packet);
+
+        match res {
+            Err((code, _)) => assert_eq!(code, expected_code, "{name}"),
+            Ok(_) => panic!("{name}: send_packet must fail"),
+        }
+        assert!(ctx.get_events().is_empty(), "{name}");
+    }
+
+    let mut ctx = ctx_with_open_channel(1.into());
+    send_packet_with_rejection_code(&mut ctx.ibc_store, packet_with_sequence(1.into()))
+        .expect("send_packet succeeds");
+
+    assert_eq!(PacketRejectionCode::Other.code(), 0);
+    assert_eq!(PacketRejectionCode::SequenceGap.code(), 11);
+}
+
+#[test]
+fn send_packet_is_deterministic() {
+    // The packet is built once, as its timeout is taken from the current time.
+    let packet = packet_with_sequence(1.into());
+
+    assert_deterministic(
+        || ctx_with_open_channel(1.into()),
+        |ctx| {
+            send_packet(&mut ctx.ibc_store, packet.clone()).expect("send_packet succeeds");
+        },
+    );
+}
+
+#[test]
+#[should_panic(expected = "the two runs emitted different events")]
+fn assert_deterministic_catches_order_dependence() {
+    let packets = [packet_with_sequence(1.into()), {
+        let mut packet = packet_with_sequence(1.into());
+        packet.data = b"other data".to_vec();
+        packet
+    }];
+
+    // Emulates a handler picking among candidates by iterating over a hash
+    // map, whose order changes from one instance to the next.
+    let runs = RefCell::new(0);
+
+    assert_deterministic(
+        || ctx_with_open_channel(1.into()),
+        |ctx| {
+            let mut runs = runs.borrow_mut();
+            let packet = packets[*runs % packets.len()].clone();
+            *runs += 1;
+
+            send_packet(&mut ctx.ibc_store, packet).expect("send_packet succeeds");
+        },
+    );
+}
+
+#[test]
+fn send_packet_streaming_matches_in_memory_commitment() {
+    // Spans several chunks of the reader.
+    let data: Vec<u8> = (0..100_000u32).map(|i| i as u8).collect();
+
+    let mut packet = packet_with_sequence(1.into());
+    packet.data = data.clone();
+
+    let mut header = packet.clone();
+    header.data = Vec::new();
+
+    let mut ctx = ctx_with_open_channel(1.into());
+    let in_memory = send_packet(&mut ctx.ibc_store, packet.clone()).expect("send_packet succeeds");
+
+    let mut streaming_ctx = ctx_with_open_channel(1.into());
+    let streamed = send_packet_streaming(&mut streaming_ctx.ibc_store, header, data.as_slice())
+        .expect("send_packet_streaming succeeds");
+
+    assert_eq!(streamed.commitment, in_memory.commitment);
+    assert_eq!(streamed.packet, in_memory.packet);
+
+    let commitment_path =
+        CommitmentPath::new(&packet.port_id_on_a, &packet.chan_id_on_a, packet.seq_on_a);
+    assert_eq!(
+        ValidationContext::get_packet_commitment(&streaming_ctx.ibc_store, &commitment_path)
+            .expect("commitment is stored"),
+        in_memory.commitment
+    );
+    assert_eq!(
+        ValidationContext::get_next_sequence_send(
+            &streaming_ctx.ibc_store,
+            &SeqSendPath::new(&packet.port_id_on_a, &packet.chan_id_on_a)
+        )
+        .expect("send sequence exists"),
+        2.into()
+    );
+    assert_eq!(streaming_ctx.get_events(), ctx.get_events());
+}